@@ -0,0 +1,292 @@
+// =========================================
+// benches/order_book.rs — Matcher Benchmarks
+// =========================================
+
+//! Throughput (orders/sec) and tail latency (p99) for the arena-backed
+//! order book: insert and cancel on the arena, and match through
+//! `OrderBook::submit_order` (authorization, reservation and execution).
+//!
+//! Run with `cargo bench --bench order_book`.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use domex_core::common::merkle::{merkle_path, merkle_root};
+use domex_core::matching::core::order_arena::{IdentityHandle, OrderArena, OrderId};
+use domex_core::matching::core::order_auth::{compute_order_commitment, derive_order_auth_key, entry_leaf, OrderAuthRegistry};
+use domex_core::matching::core::order_book::{OrderBook, SubmitOutcome};
+use domex_core::matching::core::types::order_book::{OrderInstruction, OrderIntent, OrderType, PoseidonHash, VaultState};
+use domex_core::matching::core::types::unboarding_verifier::UserZkEntryProof;
+use domex_core::matching::core::unboarding_verifier::verify_user_entry;
+use domex_core::matching::core::vault_registry::{VaultMetadata, VaultPair, VaultRegistry, VaultStatus};
+use pasta_curves::group::ff::PrimeField;
+use pasta_curves::Fp;
+
+use std::time::{Duration, Instant};
+
+const BOOK_DEPTH: usize = 10_000;
+const PRICE_LEVELS: u64 = 100;
+const BASE_PRICE: u64 = 6_000_000;
+const FAR_EXPIRY: u64 = u64::MAX;
+const MAKERS: u64 = 1_000;
+const VAULT_ID: &str = "BTC/USDT";
+const FUNDING: u64 = u64::MAX / 4;
+
+/// Book with `BOOK_DEPTH` asks spread over `PRICE_LEVELS` levels
+fn seeded_asks() -> (OrderArena, Vec<OrderId>) {
+    let mut arena = OrderArena::with_capacity(BOOK_DEPTH);
+    let ids = (0..BOOK_DEPTH as u64)
+        .map(|i| {
+            arena.insert(
+                IdentityHandle((i % 1_000) as u32),
+                OrderIntent::Sell,
+                BASE_PRICE + (i % PRICE_LEVELS),
+                1_000,
+                i,
+                FAR_EXPIRY,
+                1_000,
+            )
+        })
+        .collect();
+    (arena, ids)
+}
+
+/// Times each op individually and prints p50 / p99
+fn report_latency(name: &str, mut samples: Vec<Duration>) {
+    samples.sort_unstable();
+    let pick = |q: f64| samples[((samples.len() - 1) as f64 * q) as usize];
+    println!(
+        "[BENCH] {:<8} p50 = {:>8?}  p99 = {:>8?}  ({} ops)",
+        name,
+        pick(0.50),
+        pick(0.99),
+        samples.len()
+    );
+}
+
+fn bench_insert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("order_book");
+    group.throughput(Throughput::Elements(BOOK_DEPTH as u64));
+    group.bench_function("insert", |b| {
+        b.iter_batched(
+            || OrderArena::with_capacity(BOOK_DEPTH),
+            |mut arena| {
+                for i in 0..BOOK_DEPTH as u64 {
+                    black_box(arena.insert(
+                        IdentityHandle(i as u32),
+                        OrderIntent::Buy,
+                        BASE_PRICE - (i % PRICE_LEVELS),
+                        1_000,
+                        i,
+                        FAR_EXPIRY,
+                        1_000,
+                    ));
+                }
+                arena
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+
+    let mut arena = OrderArena::with_capacity(BOOK_DEPTH);
+    let samples = (0..BOOK_DEPTH as u64)
+        .map(|i| {
+            let start = Instant::now();
+            black_box(arena.insert(
+                IdentityHandle(i as u32),
+                OrderIntent::Buy,
+                BASE_PRICE - (i % PRICE_LEVELS),
+                1_000,
+                i,
+                FAR_EXPIRY,
+                1_000,
+            ));
+            start.elapsed()
+        })
+        .collect();
+    report_latency("insert", samples);
+}
+
+fn bench_cancel(c: &mut Criterion) {
+    let mut group = c.benchmark_group("order_book");
+    group.throughput(Throughput::Elements(BOOK_DEPTH as u64));
+    group.bench_function("cancel", |b| {
+        b.iter_batched(
+            seeded_asks,
+            |(mut arena, ids)| {
+                // Newest first: each cancel unlinks the tail of its level
+                for id in ids.iter().rev() {
+                    black_box(arena.cancel(*id));
+                }
+                arena
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+
+    let (mut arena, ids) = seeded_asks();
+    let samples = ids
+        .iter()
+        .rev()
+        .map(|id| {
+            let start = Instant::now();
+            black_box(arena.cancel(*id));
+            start.elapsed()
+        })
+        .collect();
+    report_latency("cancel", samples);
+}
+
+/// Vault, entered identities and pre-committed orders for the matcher bench
+struct MatchFixture {
+    meta: VaultMetadata,
+    registry: VaultRegistry,
+    order_auth: OrderAuthRegistry,
+    identities: Vec<PoseidonHash>, // Makers, then the taker last
+    asks: Vec<OrderInstruction>,
+    takers: Vec<OrderInstruction>,
+}
+
+fn identity(i: u64) -> PoseidonHash {
+    let mut hash = [0u8; 32];
+    hash[..8].copy_from_slice(&(i + 1).to_le_bytes());
+    hash
+}
+
+fn committed_order(auth_key: Fp, owner_hash: PoseidonHash, intent: OrderIntent, price: u64, nonce: u64) -> OrderInstruction {
+    let mut order = OrderInstruction {
+        vault_id: VAULT_ID.to_string(),
+        token: "BTC".to_string(),
+        intent,
+        order_type: OrderType::Limit,
+        size: 1_000,
+        price,
+        nonce,
+        expiry: FAR_EXPIRY,
+        commitment: None,
+        owner_hash,
+        counterparty_hash: [0u8; 32],
+    };
+    order.commitment = Some(compute_order_commitment(auth_key, &order));
+    order
+}
+
+/// Enters every identity through `verify_user_entry`, so auth keys are
+/// registered the way production registers them, and commits all orders up front
+fn match_fixture() -> MatchFixture {
+    let meta = VaultMetadata {
+        tick_size: 1,
+        lot_size: 1,
+        max_delta_bps: 10_000,
+        base_token: "BTC".to_string(),
+        quote_token: "USDT".to_string(),
+        base_decimals: 8,
+        quote_decimals: 2,
+        liquidity_price: BASE_PRICE,
+        status: VaultStatus::Active,
+        activation_ttl_epochs: u64::MAX / 2,
+        max_entry_share_bps: 10_000,
+    };
+    let mut registry = VaultRegistry::new();
+    registry.register_vault(VaultPair(VAULT_ID.to_string()), meta.clone());
+
+    let identities: Vec<PoseidonHash> = (0..=MAKERS).map(identity).collect();
+    let keys: Vec<Fp> = identities
+        .iter()
+        .enumerate()
+        .map(|(i, owner)| derive_order_auth_key(Fp::from(i as u64 + 1), owner))
+        .collect();
+    let leaves: Vec<_> = identities
+        .iter()
+        .zip(&keys)
+        .map(|(owner, key)| entry_leaf(owner, "BTC", FUNDING, *key))
+        .collect();
+    let root = hex::encode(merkle_root(&leaves));
+
+    let mut state = VaultState::new(VAULT_ID);
+    let mut order_auth = OrderAuthRegistry::new();
+    for (i, (owner, key)) in identities.iter().zip(&keys).enumerate() {
+        let path = merkle_path(&leaves, i).expect("leaf in tree");
+        let proof = UserZkEntryProof {
+            vault_id: VAULT_ID.to_string(),
+            token: "BTC".to_string(),
+            poseidon_hash: *owner,
+            balance: FUNDING,
+            total_liquidity: 0,
+            order_auth_key: hex::encode(key.to_repr()),
+            merkle_leaf: hex::encode(leaves[i]),
+            merkle_index: path.index,
+            merkle_leaf_count: path.leaf_count,
+            merkle_path: path.siblings.iter().map(hex::encode).collect(),
+        };
+        verify_user_entry(&proof, &mut state, &mut registry, &mut order_auth, &root, 0).expect("entry accepted");
+    }
+
+    let asks = (0..BOOK_DEPTH as u64)
+        .map(|i| {
+            let maker = (i % MAKERS) as usize;
+            committed_order(keys[maker], identities[maker], OrderIntent::Sell, BASE_PRICE + (i % PRICE_LEVELS), i)
+        })
+        .collect();
+    let taker = MAKERS as usize;
+    let takers = (0..BOOK_DEPTH as u64)
+        .map(|i| committed_order(keys[taker], identities[taker], OrderIntent::Buy, BASE_PRICE + PRICE_LEVELS, i))
+        .collect();
+
+    MatchFixture { meta, registry, order_auth, identities, asks, takers }
+}
+
+/// Funded vault and a book holding every fixture ask
+fn seeded_book(fixture: &MatchFixture) -> (OrderBook, VaultState) {
+    let mut state = VaultState::new(VAULT_ID);
+    for owner in &fixture.identities {
+        state.increase_balance(owner, &fixture.meta.base_token, FUNDING);
+        state.increase_balance(owner, &fixture.meta.quote_token, FUNDING);
+    }
+
+    let mut book = OrderBook::with_capacity(&fixture.meta, BOOK_DEPTH);
+    book.order_auth = fixture.order_auth.clone();
+    for ask in &fixture.asks {
+        match book.submit_order(&mut state, ask.clone(), &fixture.registry, 0, 0) {
+            SubmitOutcome::Rested(_) => {}
+            other => panic!("seed ask did not rest: {:?}", other),
+        }
+    }
+    (book, state)
+}
+
+fn bench_match(c: &mut Criterion) {
+    let fixture = match_fixture();
+
+    let mut group = c.benchmark_group("order_book");
+    group.throughput(Throughput::Elements(BOOK_DEPTH as u64));
+    group.bench_function("match", |b| {
+        b.iter_batched(
+            || seeded_book(&fixture),
+            |(mut book, mut state)| {
+                for taker in &fixture.takers {
+                    black_box(book.submit_order(&mut state, taker.clone(), &fixture.registry, 0, 0));
+                }
+                (book, state)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+
+    let (mut book, mut state) = seeded_book(&fixture);
+    let samples = fixture
+        .takers
+        .iter()
+        .map(|taker| {
+            let taker = taker.clone();
+            let start = Instant::now();
+            black_box(book.submit_order(&mut state, taker, &fixture.registry, 0, 0));
+            start.elapsed()
+        })
+        .collect();
+    report_latency("match", samples);
+}
+
+criterion_group!(benches, bench_insert, bench_cancel, bench_match);
+criterion_main!(benches);
@@ -0,0 +1,64 @@
+// ==========================================================
+// clock.rs — Injectable Time Source
+// ==========================================================
+//
+// Epoch, rotation and governance logic read time through `Clock`
+// instead of calling `Utc::now()` / `SystemTime::now()` directly,
+// so tests can drive time by hand and nodes share one definition
+// of "now" (Unix seconds).
+//
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Source of the current Unix time in seconds
+pub trait Clock: Send + Sync {
+    fn now_unix(&self) -> u64;
+}
+
+/// Wall clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_unix(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+}
+
+/// Clock that only moves when told to (tests, simulations)
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    now: AtomicU64,
+}
+
+impl ManualClock {
+    pub fn new(start_unix: u64) -> Self {
+        Self {
+            now: AtomicU64::new(start_unix),
+        }
+    }
+
+    pub fn set(&self, unix: u64) {
+        self.now.store(unix, Ordering::SeqCst);
+    }
+
+    pub fn advance(&self, secs: u64) {
+        self.now.fetch_add(secs, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now_unix(&self) -> u64 {
+        self.now.load(Ordering::SeqCst)
+    }
+}
+
+/// Shared handle to the wall clock
+pub fn system_clock() -> Arc<dyn Clock> {
+    Arc::new(SystemClock)
+}
@@ -7,6 +7,7 @@
 //
 
 use serde::{Serialize, Deserialize};
+use serde_big_array::BigArray;
 use std::fmt::{Debug, Formatter, Result as FmtResult};
 
 /// Vault identifier (e.g., BTC/USDT vault)
@@ -64,6 +65,7 @@ pub struct DelegationRecord {
     pub delegatee_pubkey: [u8; 32],
     pub vault_id: VaultId,
     pub nonce: u64,
+    #[serde(with = "BigArray")]
     pub signature: [u8; 64], // Signature from delegator
 }
 
@@ -8,7 +8,7 @@
 //
 
 use poseidon_rs::Poseidon;
-use crate::common_types::VaultId;
+use crate::common::common_types::VaultId;
 
 /// Generates a Poseidon-based identity hash from public key
 ///
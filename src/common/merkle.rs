@@ -0,0 +1,262 @@
+// ==========================================================
+// merkle.rs — Canonical Domex Merkle Tree (Poseidon, domain-separated)
+// ==========================================================
+//
+// The one Merkle construction every subsystem uses: vault state roots,
+// token balances, batch aggregation, onboarding and proof verification.
+//
+// Rules:
+// - Leaf hash  = Poseidon(LEAF_TAG || len || data as 16-byte limbs)
+// - Node hash  = Poseidon(NODE_TAG || left as 2 limbs || right as 2 limbs)
+//   Distinct tags mean a leaf can never be passed off as an inner node.
+// - Odd node   = promoted unchanged to the next level (never duplicated),
+//   so [a, b, c] and [a, b, c, c] have different roots.
+// - Empty tree = EMPTY_ROOT (all zero)
+// - Proofs carry (index, leaf_count, siblings); promoted levels have no
+//   sibling, and the verifier derives them from leaf_count.
+//
+
+use pasta_curves::group::ff::PrimeField;
+use pasta_curves::Fp;
+use crate::common::poseidon_hasher::PoseidonHasher;
+use serde::{Deserialize, Serialize};
+
+/// 32-byte Merkle hash (canonical Pasta Fp encoding)
+pub type MerkleHash = [u8; 32];
+
+/// Domain tag for leaf hashes
+pub const LEAF_TAG: u64 = 0x004d_4c45_4146; // "MLEAF"
+
+/// Domain tag for inner-node hashes
+pub const NODE_TAG: u64 = 0x004d_4e4f_4445; // "MNODE"
+
+/// Root of a tree with no leaves
+pub const EMPTY_ROOT: MerkleHash = [0u8; 32];
+
+/// Inclusion proof for one leaf
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerklePath {
+    pub index: u64,               // Leaf position
+    pub leaf_count: u64,          // Leaves in the tree the proof was built from
+    pub siblings: Vec<MerkleHash>, // Bottom-up, promoted levels skipped
+}
+
+/// 16-byte little-endian limb as a field element (always canonical)
+fn limb_to_fp(bytes: &[u8]) -> Fp {
+    let mut limb = [0u8; 16];
+    limb[..bytes.len()].copy_from_slice(bytes);
+    let value = u128::from_le_bytes(limb);
+    Fp::from((value >> 64) as u64) * Fp::from(1u64 << 32) * Fp::from(1u64 << 32) + Fp::from(value as u64)
+}
+
+/// Hashes raw leaf data under the leaf domain
+pub fn hash_leaf(data: &[u8]) -> MerkleHash {
+    let mut inputs = Vec::with_capacity(2 + data.len().div_ceil(16));
+    inputs.push(Fp::from(LEAF_TAG));
+    inputs.push(Fp::from(data.len() as u64));
+    inputs.extend(data.chunks(16).map(limb_to_fp));
+
+    let mut hasher = PoseidonHasher::new();
+    hasher.hash(&inputs).to_repr()
+}
+
+/// Hashes two children under the node domain
+pub fn hash_node(left: &MerkleHash, right: &MerkleHash) -> MerkleHash {
+    let mut hasher = PoseidonHasher::new();
+    hasher
+        .hash(&[
+            Fp::from(NODE_TAG),
+            limb_to_fp(&left[..16]),
+            limb_to_fp(&left[16..]),
+            limb_to_fp(&right[..16]),
+            limb_to_fp(&right[16..]),
+        ])
+        .to_repr()
+}
+
+/// Parent level of `level` under the promote rule
+fn next_level(level: &[MerkleHash]) -> Vec<MerkleHash> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => hash_node(left, right),
+            [single] => *single,
+            _ => unreachable!("chunks(2) yields one or two nodes"),
+        })
+        .collect()
+}
+
+/// Root over already-hashed leaves
+pub fn merkle_root(leaves: &[MerkleHash]) -> MerkleHash {
+    if leaves.is_empty() {
+        return EMPTY_ROOT;
+    }
+
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+/// Inclusion proof for the leaf at `index`
+pub fn merkle_path(leaves: &[MerkleHash], index: usize) -> Option<MerklePath> {
+    if index >= leaves.len() {
+        return None;
+    }
+
+    let mut siblings = Vec::new();
+    let mut level = leaves.to_vec();
+    let mut idx = index;
+
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(idx ^ 1) {
+            siblings.push(*sibling);
+        }
+        level = next_level(&level);
+        idx /= 2;
+    }
+
+    Some(MerklePath {
+        index: index as u64,
+        leaf_count: leaves.len() as u64,
+        siblings,
+    })
+}
+
+/// Recomputes the root a proof commits to, or None if the proof is malformed
+pub fn root_from_path(leaf_hash: &MerkleHash, path: &MerklePath) -> Option<MerkleHash> {
+    if path.index >= path.leaf_count {
+        return None;
+    }
+
+    let mut hash = *leaf_hash;
+    let mut idx = path.index;
+    let mut width = path.leaf_count;
+    let mut siblings = path.siblings.iter();
+
+    while width > 1 {
+        let promoted = idx == width - 1 && width % 2 == 1;
+        if !promoted {
+            let sibling = siblings.next()?;
+            hash = if idx.is_multiple_of(2) {
+                hash_node(&hash, sibling)
+            } else {
+                hash_node(sibling, &hash)
+            };
+        }
+        idx /= 2;
+        width = width.div_ceil(2);
+    }
+
+    // Every sibling must be consumed
+    if siblings.next().is_some() {
+        return None;
+    }
+    Some(hash)
+}
+
+/// Verifies that `leaf_hash` is at `path.index` under `root`
+pub fn verify_path(leaf_hash: &MerkleHash, path: &MerklePath, root: &MerkleHash) -> bool {
+    root_from_path(leaf_hash, path).as_ref() == Some(root)
+}
+
+/// Lower-case hex of a Merkle hash
+pub fn hash_to_hex(hash: &MerkleHash) -> String {
+    hex::encode(hash)
+}
+
+/// Parses a (optionally 0x-prefixed) hex Merkle hash
+pub fn hash_from_hex(hex_str: &str) -> Option<MerkleHash> {
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(hex_str.trim_start_matches("0x"), &mut bytes).ok()?;
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(n: u8) -> Vec<MerkleHash> {
+        (0..n).map(|i| hash_leaf(&[i])).collect()
+    }
+
+    /// Roots of `leaves(n)` for n = 1..=5, hex-encoded, under the neptune
+    /// Poseidon sponge (`common/poseidon_hasher.rs`)
+    const KNOWN_ROOTS: [&str; 5] = [
+        "86e1564298a856d24698d3021b1a3bfa989664973a6e41b959c1f5361bef4c22",
+        "09de87d8c81f644cc023564fbdf50c184accf0ecca4769752726df9f2a264838",
+        "41bcc2ffe4aa27ade844ef2d92c34e83a53557c51e1133ff8a4531fea2c7a202",
+        "21eef50f192c3b4d9f0a4906aa4e34b3fed640aa4444f2e37905465accb75d13",
+        "ec52dd96816ce1a8021ac0fdfac4681ce3348e8747aa098b89a039ca4e61e025",
+    ];
+
+    // Fixed vectors: any change to tags, limb encoding, Poseidon params or
+    // the odd-node rule changes these and must be a deliberate fork.
+    #[test]
+    fn test_known_answer_roots() {
+        assert_eq!(hash_to_hex(&merkle_root(&[])), "00".repeat(32));
+        for (n, expected) in (1..=5u8).zip(KNOWN_ROOTS) {
+            let root = hash_to_hex(&merkle_root(&leaves(n)));
+            assert_eq!(root, expected, "root of {} leaves changed", n);
+        }
+    }
+
+    // Tree shape rules spelled out per size (promote, never duplicate).
+    // Not a known-answer test: it checks structure, not the hash function.
+    #[test]
+    fn test_shape_rules() {
+        let l = leaves(5);
+        let (a, b, c, d, e) = (l[0], l[1], l[2], l[3], l[4]);
+
+        assert_eq!(merkle_root(&[]), EMPTY_ROOT);
+        assert_eq!(merkle_root(&l[..1]), a);
+        assert_eq!(merkle_root(&l[..2]), hash_node(&a, &b));
+        // Odd node promoted, not duplicated
+        assert_eq!(merkle_root(&l[..3]), hash_node(&hash_node(&a, &b), &c));
+        assert_eq!(
+            merkle_root(&l[..4]),
+            hash_node(&hash_node(&a, &b), &hash_node(&c, &d))
+        );
+        assert_eq!(
+            merkle_root(&l[..5]),
+            hash_node(&hash_node(&hash_node(&a, &b), &hash_node(&c, &d)), &e)
+        );
+    }
+
+    #[test]
+    fn test_domain_separation_and_no_duplication_ambiguity() {
+        let l = leaves(3);
+
+        // A node can't be re-presented as a leaf over its children's bytes
+        let mut concatenated = l[0].to_vec();
+        concatenated.extend_from_slice(&l[1]);
+        assert_ne!(hash_leaf(&concatenated), hash_node(&l[0], &l[1]));
+
+        // Appending a copy of the last leaf changes the root
+        let mut padded = l.clone();
+        padded.push(l[2]);
+        assert_ne!(merkle_root(&l), merkle_root(&padded));
+
+        // Leaf length is bound: trailing zeros are not free
+        assert_ne!(hash_leaf(&[1]), hash_leaf(&[1, 0]));
+    }
+
+    #[test]
+    fn test_every_path_verifies() {
+        for n in 1..=9u8 {
+            let l = leaves(n);
+            let root = merkle_root(&l);
+            for i in 0..l.len() {
+                let path = merkle_path(&l, i).unwrap();
+                assert!(verify_path(&l[i], &path, &root), "n={} i={}", n, i);
+
+                let mut wrong_index = path.clone();
+                wrong_index.index = (i as u64 + 1) % n as u64;
+                if n > 1 {
+                    assert!(!verify_path(&l[i], &wrong_index, &root), "n={} i={}", n, i);
+                }
+            }
+        }
+    }
+}
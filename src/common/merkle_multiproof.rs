@@ -0,0 +1,312 @@
+// ==========================================================
+// merkle_multiproof.rs — Compact Merkle Multi-Proofs
+// ==========================================================
+//
+// Proves many leaves of one canonical tree (`common/merkle.rs`) with a
+// single deduplicated sibling set, instead of one path per leaf.
+//
+// Used for withdrawal batches and by bridge clients checking many exits
+// against one finalized root.
+//
+// Sibling order is fixed: level by level from the leaves up, and within a
+// level by ascending node index. Nodes that are themselves proven, or whose
+// sibling is proven, need no sibling; promoted odd nodes need none either.
+//
+// Wire format (little-endian):
+//   version: u8 | leaf_count: u64 | n: u32 | indices: n × u64
+//   | m: u32 | siblings: m × 32 bytes
+//
+
+use crate::common::merkle::{hash_node, MerkleHash};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Current wire format version
+pub const MULTIPROOF_VERSION: u8 = 1;
+
+/// Inclusion proof for a set of leaf indices
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleMultiProof {
+    pub leaf_count: u64,
+    pub indices: Vec<u64>,         // Strictly ascending
+    pub siblings: Vec<MerkleHash>, // Deduplicated, in canonical order
+}
+
+/// Multi-proof generation, verification and decoding errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MultiProofError {
+    /// No leaves requested
+    NoIndices,
+
+    /// Index not below leaf count
+    IndexOutOfRange { index: u64, leaf_count: u64 },
+
+    /// Indices not strictly ascending
+    UnsortedIndices,
+
+    /// Number of leaf hashes differs from number of indices
+    LeafCountMismatch { indices: usize, leaves: usize },
+
+    /// Proof ran out of siblings, or has some left over
+    SiblingCountMismatch,
+
+    /// Unknown wire format version
+    UnsupportedVersion(u8),
+
+    /// Byte string ended early or has trailing bytes
+    MalformedBytes,
+}
+
+fn check_indices(indices: &[u64], leaf_count: u64) -> Result<(), MultiProofError> {
+    if indices.is_empty() {
+        return Err(MultiProofError::NoIndices);
+    }
+    if indices.windows(2).any(|w| w[0] >= w[1]) {
+        return Err(MultiProofError::UnsortedIndices);
+    }
+    let last = indices[indices.len() - 1];
+    if last >= leaf_count {
+        return Err(MultiProofError::IndexOutOfRange { index: last, leaf_count });
+    }
+    Ok(())
+}
+
+/// Builds a multi-proof for `indices` (deduplicated and sorted here)
+pub fn build_multiproof(leaves: &[MerkleHash], indices: &[u64]) -> Result<MerkleMultiProof, MultiProofError> {
+    let mut indices = indices.to_vec();
+    indices.sort_unstable();
+    indices.dedup();
+    check_indices(&indices, leaves.len() as u64)?;
+
+    let mut siblings = Vec::new();
+    let mut level = leaves.to_vec();
+    let mut known: Vec<u64> = indices.clone();
+
+    while level.len() > 1 {
+        let width = level.len() as u64;
+        let mut parents = Vec::with_capacity(known.len());
+        let mut i = 0;
+
+        while i < known.len() {
+            let idx = known[i];
+            let promoted = idx == width - 1 && width % 2 == 1;
+            let pair_known = idx.is_multiple_of(2) && known.get(i + 1) == Some(&(idx + 1));
+
+            if pair_known {
+                i += 1; // Sibling is proven too, skip it
+            } else if !promoted {
+                siblings.push(level[(idx ^ 1) as usize]);
+            }
+
+            parents.push(idx / 2);
+            i += 1;
+        }
+
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => hash_node(left, right),
+                [single] => *single,
+                _ => unreachable!("chunks(2) yields one or two nodes"),
+            })
+            .collect();
+        known = parents;
+    }
+
+    Ok(MerkleMultiProof {
+        leaf_count: leaves.len() as u64,
+        indices,
+        siblings,
+    })
+}
+
+/// Recomputes the root committed to by `proof` for `leaf_hashes`
+/// (given in the same order as `proof.indices`)
+pub fn multiproof_root(leaf_hashes: &[MerkleHash], proof: &MerkleMultiProof) -> Result<MerkleHash, MultiProofError> {
+    check_indices(&proof.indices, proof.leaf_count)?;
+    if leaf_hashes.len() != proof.indices.len() {
+        return Err(MultiProofError::LeafCountMismatch {
+            indices: proof.indices.len(),
+            leaves: leaf_hashes.len(),
+        });
+    }
+
+    let mut known: BTreeMap<u64, MerkleHash> = proof
+        .indices
+        .iter()
+        .copied()
+        .zip(leaf_hashes.iter().copied())
+        .collect();
+    let mut siblings = proof.siblings.iter();
+    let mut width = proof.leaf_count;
+
+    while width > 1 {
+        let nodes: Vec<(u64, MerkleHash)> = known.into_iter().collect();
+        let mut parents = BTreeMap::new();
+        let mut i = 0;
+
+        while i < nodes.len() {
+            let (idx, hash) = nodes[i];
+            let promoted = idx == width - 1 && width % 2 == 1;
+
+            let parent = if promoted {
+                hash
+            } else if idx % 2 == 0 && nodes.get(i + 1).map(|n| n.0) == Some(idx + 1) {
+                i += 1;
+                hash_node(&hash, &nodes[i].1)
+            } else {
+                let sibling = siblings.next().ok_or(MultiProofError::SiblingCountMismatch)?;
+                if idx % 2 == 0 {
+                    hash_node(&hash, sibling)
+                } else {
+                    hash_node(sibling, &hash)
+                }
+            };
+
+            parents.insert(idx / 2, parent);
+            i += 1;
+        }
+
+        known = parents;
+        width = width.div_ceil(2);
+    }
+
+    if siblings.next().is_some() {
+        return Err(MultiProofError::SiblingCountMismatch);
+    }
+    Ok(known[&0])
+}
+
+/// Verifies that every leaf hash sits at its index under `root`
+pub fn verify_multiproof(leaf_hashes: &[MerkleHash], proof: &MerkleMultiProof, root: &MerkleHash) -> bool {
+    multiproof_root(leaf_hashes, proof).as_ref() == Ok(root)
+}
+
+impl MerkleMultiProof {
+    /// Encodes the proof in the compact wire format
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(1 + 8 + 4 + self.indices.len() * 8 + 4 + self.siblings.len() * 32);
+        out.push(MULTIPROOF_VERSION);
+        out.extend_from_slice(&self.leaf_count.to_le_bytes());
+        out.extend_from_slice(&(self.indices.len() as u32).to_le_bytes());
+        for index in &self.indices {
+            out.extend_from_slice(&index.to_le_bytes());
+        }
+        out.extend_from_slice(&(self.siblings.len() as u32).to_le_bytes());
+        for sibling in &self.siblings {
+            out.extend_from_slice(sibling);
+        }
+        out
+    }
+
+    /// Decodes the compact wire format. Does not verify the proof.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MultiProofError> {
+        let mut reader = ByteReader { bytes, pos: 0 };
+
+        let version = reader.take(1)?[0];
+        if version != MULTIPROOF_VERSION {
+            return Err(MultiProofError::UnsupportedVersion(version));
+        }
+
+        let leaf_count = reader.u64()?;
+        let index_count = reader.u32()? as usize;
+        let indices = (0..index_count).map(|_| reader.u64()).collect::<Result<Vec<_>, _>>()?;
+
+        let sibling_count = reader.u32()? as usize;
+        let siblings = (0..sibling_count)
+            .map(|_| reader.take(32).map(|b| b.try_into().expect("32-byte slice")))
+            .collect::<Result<Vec<MerkleHash>, _>>()?;
+
+        if reader.pos != bytes.len() {
+            return Err(MultiProofError::MalformedBytes);
+        }
+
+        Ok(Self {
+            leaf_count,
+            indices,
+            siblings,
+        })
+    }
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], MultiProofError> {
+        let end = self.pos.checked_add(n).ok_or(MultiProofError::MalformedBytes)?;
+        let slice = self.bytes.get(self.pos..end).ok_or(MultiProofError::MalformedBytes)?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u32(&mut self) -> Result<u32, MultiProofError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().expect("4-byte slice")))
+    }
+
+    fn u64(&mut self) -> Result<u64, MultiProofError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().expect("8-byte slice")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::merkle::{hash_leaf, merkle_path, merkle_root};
+
+    fn leaves(n: u8) -> Vec<MerkleHash> {
+        (0..n).map(|i| hash_leaf(&[i])).collect()
+    }
+
+    #[test]
+    fn test_multiproof_verifies_every_subset_shape() {
+        for n in 1..=9u8 {
+            let l = leaves(n);
+            let root = merkle_root(&l);
+
+            for mask in 1u32..(1 << n) {
+                let indices: Vec<u64> = (0..n as u64).filter(|i| mask & (1 << i) != 0).collect();
+                let proof = build_multiproof(&l, &indices).unwrap();
+                let hashes: Vec<MerkleHash> = indices.iter().map(|i| l[*i as usize]).collect();
+
+                assert!(verify_multiproof(&hashes, &proof, &root), "n={} mask={:b}", n, mask);
+            }
+        }
+    }
+
+    #[test]
+    fn test_multiproof_is_smaller_than_separate_paths() {
+        let l = leaves(8);
+        let indices = [0u64, 1, 2, 3];
+        let proof = build_multiproof(&l, &indices).unwrap();
+
+        let separate: usize = indices
+            .iter()
+            .map(|i| merkle_path(&l, *i as usize).unwrap().siblings.len())
+            .sum();
+        assert_eq!(proof.siblings.len(), 1);
+        assert!(proof.siblings.len() < separate);
+
+        // Wrong leaf fails
+        let mut hashes: Vec<MerkleHash> = indices.iter().map(|i| l[*i as usize]).collect();
+        hashes[2] = l[7];
+        assert!(!verify_multiproof(&hashes, &proof, &merkle_root(&l)));
+    }
+
+    #[test]
+    fn test_multiproof_bytes_round_trip() {
+        let l = leaves(7);
+        let proof = build_multiproof(&l, &[6, 1, 3]).unwrap();
+        assert_eq!(proof.indices, vec![1, 3, 6]);
+
+        let bytes = proof.to_bytes();
+        assert_eq!(MerkleMultiProof::from_bytes(&bytes), Ok(proof.clone()));
+
+        assert_eq!(MerkleMultiProof::from_bytes(&bytes[..bytes.len() - 1]), Err(MultiProofError::MalformedBytes));
+        let mut bad_version = bytes.clone();
+        bad_version[0] = 9;
+        assert_eq!(MerkleMultiProof::from_bytes(&bad_version), Err(MultiProofError::UnsupportedVersion(9)));
+    }
+}
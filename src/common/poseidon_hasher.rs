@@ -0,0 +1,76 @@
+// ==========================================================
+// poseidon_hasher.rs — Domex Poseidon Hasher (Pasta Fp)
+// ==========================================================
+//
+// The Poseidon instance behind every Domex hash over Pasta Fp: Merkle
+// leaves and nodes, identity and attestation hashes, order auth keys.
+//
+// Rules:
+// - Width 3 (rate 2, capacity 1), x^5 S-box, 128-bit security; round
+//   constants and MDS matrix are generated by neptune (`Strength::Standard`)
+// - Inputs of any length go through the SAFE sponge API with the pattern
+//   Absorb(n), Squeeze(1); n is bound into the capacity, so [a] and [a, 0]
+//   hash differently
+// - Constants are generated once per process and shared
+//
+
+use std::sync::OnceLock;
+
+use generic_array::typenum::U2;
+use neptune::poseidon::PoseidonConstants;
+use neptune::sponge::api::{IOPattern, SpongeAPI, SpongeOp};
+use neptune::sponge::vanilla::{Mode, Sponge, SpongeTrait};
+use neptune::Strength;
+use pasta_curves::Fp;
+
+/// Shared sponge constants (width 3)
+fn constants() -> &'static PoseidonConstants<Fp, U2> {
+    static CONSTANTS: OnceLock<PoseidonConstants<Fp, U2>> = OnceLock::new();
+    CONSTANTS.get_or_init(|| Sponge::<Fp, U2>::api_constants(Strength::Standard))
+}
+
+/// Poseidon hash of a variable-length sequence of field elements
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PoseidonHasher;
+
+impl PoseidonHasher {
+    pub fn new() -> Self {
+        PoseidonHasher
+    }
+
+    /// Absorbs `inputs` and squeezes one element
+    pub fn hash(&mut self, inputs: &[Fp]) -> Fp {
+        let mut ops = Vec::with_capacity(2);
+        if !inputs.is_empty() {
+            ops.push(SpongeOp::Absorb(inputs.len() as u32));
+        }
+        ops.push(SpongeOp::Squeeze(1));
+
+        let acc = &mut ();
+        let mut sponge = Sponge::new_with_constants(constants(), Mode::Simplex);
+        sponge.start(IOPattern(ops), None, acc);
+        if !inputs.is_empty() {
+            SpongeAPI::absorb(&mut sponge, inputs.len() as u32, inputs, acc);
+        }
+        let out = SpongeAPI::squeeze(&mut sponge, 1, acc);
+        sponge.finish(acc).expect("IO pattern followed exactly");
+        out[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_length_is_bound_into_the_hash() {
+        let mut hasher = PoseidonHasher::new();
+        let a = Fp::from(7u64);
+        assert_eq!(hasher.hash(&[a]), hasher.hash(&[a]));
+        assert_ne!(hasher.hash(&[a]), hasher.hash(&[a, Fp::zero()]));
+        assert_ne!(hasher.hash(&[]), hasher.hash(&[Fp::zero()]));
+        // Longer than the rate: absorbed over several permutations
+        let long: Vec<Fp> = (0..9u64).map(Fp::from).collect();
+        assert_ne!(hasher.hash(&long), hasher.hash(&long[..8]));
+    }
+}
@@ -7,8 +7,8 @@
 // All validators must produce identical proof hashes for convergence.
 //
 
-use crate::poseidon_utils::poseidon_hash;
-use crate::common_types::{MerkleRoot, VaultId, DelegationHash};
+use crate::matching::core::poseidon_utils::poseidon_hash;
+use crate::common::common_types::{MerkleRoot, VaultId, DelegationHash};
 use crate::token_config::MIN_PROOF_FUEL_BURN;
 
 /// The unified data needed to construct a proof attestation hash
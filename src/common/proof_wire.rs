@@ -0,0 +1,306 @@
+// ==========================================================
+// proof_wire.rs — Vault Leader → Validator Proof Intake Protocol
+// ==========================================================
+//
+// Framed, versioned request/response messages spoken over TCP between
+// the vault leader (`matching/zk/validator_client.rs`) and a validator's
+// intake (`validator/proof_intake_server.rs`).
+//
+// Frame (little-endian):
+//   magic: "DMXP" | version: u8 | kind: u8 | len: u32 | body: len bytes
+//
+// Strings and byte blobs in bodies are u32 length-prefixed.
+//
+// Every submission carries an idempotency key; resubmitting the same key
+// returns the original proof ID, so the leader may retry freely.
+//
+
+use crate::common::common_types::ProofId;
+use std::io::{Read, Write};
+
+/// Frame magic
+pub const WIRE_MAGIC: [u8; 4] = *b"DMXP";
+
+/// Current protocol version
+pub const WIRE_VERSION: u8 = 2;
+
+/// Largest accepted frame body (4 MiB)
+pub const MAX_FRAME_BYTES: usize = 4 * 1024 * 1024;
+
+const KIND_SUBMIT: u8 = 1;
+const KIND_ACK: u8 = 2;
+const KIND_REJECT: u8 = 3;
+
+/// Proof submission from a vault leader
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofSubmitRequest {
+    pub idempotency_key: String, // Stable across retries, e.g. "<circuit>:<public inputs hash>"
+    pub vault_id: String,
+    pub token: String,
+    pub size: u64,
+    pub owner_hash: String,
+    pub circuit: String,
+    pub backend: String,         // Proving backend name, e.g. "plonky2"
+    pub public_inputs_hash: String,
+    pub zk_payload: Vec<u8>,
+    pub timestamp: u64,
+}
+
+/// Validator acknowledgement of an accepted proof
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofAck {
+    pub idempotency_key: String,
+    pub proof_id: ProofId,
+    pub duplicate: bool, // Key was already accepted; proof_id is the original
+}
+
+/// Validator reply to a submission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofSubmitResponse {
+    Accepted(ProofAck),
+    Rejected { retryable: bool, reason: String },
+}
+
+/// Any message that can appear in a frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WireMessage {
+    Submit(ProofSubmitRequest),
+    Response(ProofSubmitResponse),
+}
+
+/// Framing and decoding errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WireError {
+    /// Frame does not start with `WIRE_MAGIC`
+    BadMagic,
+
+    /// Peer speaks another protocol version
+    UnsupportedVersion(u8),
+
+    /// Unknown message kind
+    UnknownKind(u8),
+
+    /// Body larger than `MAX_FRAME_BYTES`
+    FrameTooLarge(usize),
+
+    /// Body ended early, has trailing bytes or invalid UTF-8
+    MalformedBody,
+
+    /// Underlying stream failed
+    Io(String),
+}
+
+impl From<std::io::Error> for WireError {
+    fn from(e: std::io::Error) -> Self {
+        WireError::Io(e.to_string())
+    }
+}
+
+impl WireMessage {
+    fn kind(&self) -> u8 {
+        match self {
+            WireMessage::Submit(_) => KIND_SUBMIT,
+            WireMessage::Response(ProofSubmitResponse::Accepted(_)) => KIND_ACK,
+            WireMessage::Response(ProofSubmitResponse::Rejected { .. }) => KIND_REJECT,
+        }
+    }
+
+    fn encode_body(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        match self {
+            WireMessage::Submit(req) => {
+                put_str(&mut out, &req.idempotency_key);
+                put_str(&mut out, &req.vault_id);
+                put_str(&mut out, &req.token);
+                out.extend_from_slice(&req.size.to_le_bytes());
+                put_str(&mut out, &req.owner_hash);
+                put_str(&mut out, &req.circuit);
+                put_str(&mut out, &req.backend);
+                put_str(&mut out, &req.public_inputs_hash);
+                put_bytes(&mut out, &req.zk_payload);
+                out.extend_from_slice(&req.timestamp.to_le_bytes());
+            }
+            WireMessage::Response(ProofSubmitResponse::Accepted(ack)) => {
+                put_str(&mut out, &ack.idempotency_key);
+                out.extend_from_slice(&ack.proof_id);
+                out.push(ack.duplicate as u8);
+            }
+            WireMessage::Response(ProofSubmitResponse::Rejected { retryable, reason }) => {
+                out.push(*retryable as u8);
+                put_str(&mut out, reason);
+            }
+        }
+        out
+    }
+
+    fn decode_body(kind: u8, body: &[u8]) -> Result<Self, WireError> {
+        let mut reader = BodyReader { bytes: body, pos: 0 };
+        let message = match kind {
+            KIND_SUBMIT => WireMessage::Submit(ProofSubmitRequest {
+                idempotency_key: reader.string()?,
+                vault_id: reader.string()?,
+                token: reader.string()?,
+                size: reader.u64()?,
+                owner_hash: reader.string()?,
+                circuit: reader.string()?,
+                backend: reader.string()?,
+                public_inputs_hash: reader.string()?,
+                zk_payload: reader.blob()?.to_vec(),
+                timestamp: reader.u64()?,
+            }),
+            KIND_ACK => WireMessage::Response(ProofSubmitResponse::Accepted(ProofAck {
+                idempotency_key: reader.string()?,
+                proof_id: reader.take(32)?.try_into().expect("32-byte slice"),
+                duplicate: reader.take(1)?[0] != 0,
+            })),
+            KIND_REJECT => WireMessage::Response(ProofSubmitResponse::Rejected {
+                retryable: reader.take(1)?[0] != 0,
+                reason: reader.string()?,
+            }),
+            other => return Err(WireError::UnknownKind(other)),
+        };
+
+        if reader.pos != body.len() {
+            return Err(WireError::MalformedBody);
+        }
+        Ok(message)
+    }
+
+    /// Encodes the message as one frame
+    pub fn to_frame(&self) -> Vec<u8> {
+        let body = self.encode_body();
+        let mut frame = Vec::with_capacity(10 + body.len());
+        frame.extend_from_slice(&WIRE_MAGIC);
+        frame.push(WIRE_VERSION);
+        frame.push(self.kind());
+        frame.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        frame.extend_from_slice(&body);
+        frame
+    }
+}
+
+/// Writes one framed message
+pub fn write_frame<W: Write>(stream: &mut W, message: &WireMessage) -> Result<(), WireError> {
+    stream.write_all(&message.to_frame())?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Reads one framed message, rejecting bad magic, versions and oversized bodies
+pub fn read_frame<R: Read>(stream: &mut R) -> Result<WireMessage, WireError> {
+    let mut header = [0u8; 10];
+    stream.read_exact(&mut header)?;
+
+    if header[..4] != WIRE_MAGIC {
+        return Err(WireError::BadMagic);
+    }
+    if header[4] != WIRE_VERSION {
+        return Err(WireError::UnsupportedVersion(header[4]));
+    }
+
+    let len = u32::from_le_bytes(header[6..10].try_into().expect("4-byte slice")) as usize;
+    if len > MAX_FRAME_BYTES {
+        return Err(WireError::FrameTooLarge(len));
+    }
+
+    let mut body = vec![0u8; len];
+    stream.read_exact(&mut body)?;
+    WireMessage::decode_body(header[5], &body)
+}
+
+fn put_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    out.extend_from_slice(bytes);
+}
+
+fn put_str(out: &mut Vec<u8>, s: &str) {
+    put_bytes(out, s.as_bytes());
+}
+
+struct BodyReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BodyReader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], WireError> {
+        let end = self.pos.checked_add(n).ok_or(WireError::MalformedBody)?;
+        let slice = self.bytes.get(self.pos..end).ok_or(WireError::MalformedBody)?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u64(&mut self) -> Result<u64, WireError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().expect("8-byte slice")))
+    }
+
+    fn blob(&mut self) -> Result<&'a [u8], WireError> {
+        let len = u32::from_le_bytes(self.take(4)?.try_into().expect("4-byte slice")) as usize;
+        self.take(len)
+    }
+
+    fn string(&mut self) -> Result<String, WireError> {
+        String::from_utf8(self.blob()?.to_vec()).map_err(|_| WireError::MalformedBody)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> ProofSubmitRequest {
+        ProofSubmitRequest {
+            idempotency_key: "domex.circuit.trade.v1:abcd".into(),
+            vault_id: "vault-btc-usdt".into(),
+            token: "dBTC".into(),
+            size: 42,
+            owner_hash: "ff".repeat(32),
+            circuit: "domex.circuit.trade.v1".into(),
+            backend: "plonky2".into(),
+            public_inputs_hash: "abcd".into(),
+            zk_payload: vec![1, 2, 3, 4],
+            timestamp: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn test_frames_round_trip() {
+        let messages = [
+            WireMessage::Submit(request()),
+            WireMessage::Response(ProofSubmitResponse::Accepted(ProofAck {
+                idempotency_key: "k".into(),
+                proof_id: [7u8; 32],
+                duplicate: true,
+            })),
+            WireMessage::Response(ProofSubmitResponse::Rejected {
+                retryable: false,
+                reason: "Invalid vault ID".into(),
+            }),
+        ];
+
+        for message in messages {
+            let frame = message.to_frame();
+            assert_eq!(read_frame(&mut frame.as_slice()), Ok(message));
+        }
+    }
+
+    #[test]
+    fn test_rejects_bad_headers() {
+        let frame = WireMessage::Submit(request()).to_frame();
+
+        let mut bad_magic = frame.clone();
+        bad_magic[0] = b'X';
+        assert_eq!(read_frame(&mut bad_magic.as_slice()), Err(WireError::BadMagic));
+
+        let mut bad_version = frame.clone();
+        bad_version[4] = 9;
+        assert_eq!(read_frame(&mut bad_version.as_slice()), Err(WireError::UnsupportedVersion(9)));
+
+        let mut oversized = frame.clone();
+        oversized[6..10].copy_from_slice(&(MAX_FRAME_BYTES as u32 + 1).to_le_bytes());
+        assert_eq!(
+            read_frame(&mut oversized.as_slice()),
+            Err(WireError::FrameTooLarge(MAX_FRAME_BYTES + 1))
+        );
+    }
+}
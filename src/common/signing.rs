@@ -0,0 +1,100 @@
+// ==========================================================
+// signing.rs — secp256k1 ECDSA over 32-byte Poseidon digests
+// ==========================================================
+//
+// Validators sign with a secret key; everyone else verifies with the
+// public key alone, so nothing registered with the committee can be
+// used to forge a signature.
+//
+// - Public keys: compressed SEC1 (33 bytes), lower-case hex
+// - Signatures: compact r || s (64 bytes), hex; high-s is rejected, so
+//   a signature has exactly one valid encoding
+// - Messages: a domain-tagged Poseidon digest built by the caller
+//
+
+use bitcoin::secp256k1::ecdsa::Signature;
+use bitcoin::secp256k1::{Message, PublicKey, Secp256k1};
+
+pub use bitcoin::secp256k1::SecretKey as SigningKey;
+
+/// Message digest a signature covers
+pub type Digest = [u8; 32];
+
+/// Hex compressed public key of `key`
+pub fn public_key_hex(key: &SigningKey) -> String {
+    hex::encode(PublicKey::from_secret_key(&Secp256k1::signing_only(), key).serialize())
+}
+
+/// Re-encodes a hex public key in its canonical form (compressed, lower-case),
+/// or `None` if it is not a valid secp256k1 point
+pub fn canonical_public_key(public_key: &str) -> Option<String> {
+    parse_public_key(public_key).map(|key| hex::encode(key.serialize()))
+}
+
+/// Signs `digest`, returning the compact signature as hex
+pub fn sign(key: &SigningKey, digest: &Digest) -> String {
+    let message = Message::from_slice(digest).expect("digest is 32 bytes");
+    hex::encode(Secp256k1::signing_only().sign_ecdsa(&message, key).serialize_compact())
+}
+
+/// True if `signature` is `public_key`'s signature over `digest`.
+/// Malformed keys or signatures never verify.
+pub fn verify(public_key: &str, digest: &Digest, signature: &str) -> bool {
+    let Some(key) = parse_public_key(public_key) else {
+        return false;
+    };
+    let Some(signature) = hex::decode(signature).ok().and_then(|bytes| Signature::from_compact(&bytes).ok()) else {
+        return false;
+    };
+    let Ok(message) = Message::from_slice(digest) else {
+        return false;
+    };
+    Secp256k1::verification_only().verify_ecdsa(&message, &signature, &key).is_ok()
+}
+
+fn parse_public_key(public_key: &str) -> Option<PublicKey> {
+    let bytes = hex::decode(public_key.trim_start_matches("0x")).ok()?;
+    PublicKey::from_slice(&bytes).ok()
+}
+
+/// Deterministic keys and stubs shared by the validator tests
+#[cfg(test)]
+pub mod fixtures {
+    use super::SigningKey;
+    use crate::common::merkle::hash_leaf;
+
+    /// Signing key of validator `id`: the secret is `hash_leaf(id)`
+    pub fn key(id: &str) -> SigningKey {
+        SigningKey::from_slice(&hash_leaf(id.as_bytes())).unwrap()
+    }
+
+    /// Proof verifier that rejects everything
+    pub fn never_verifies(_: &[u8]) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_slice(&[seed; 32]).unwrap()
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let digest = [9u8; 32];
+        let public_key = public_key_hex(&key(1));
+        let signature = sign(&key(1), &digest);
+
+        assert!(verify(&public_key, &digest, &signature));
+        assert!(!verify(&public_key, &[8u8; 32], &signature));
+        assert!(!verify(&public_key_hex(&key(2)), &digest, &signature));
+        assert!(!verify("not-a-key", &digest, &signature));
+        assert!(!verify(&public_key, &digest, "00"));
+
+        assert_eq!(canonical_public_key(&public_key.to_uppercase()), Some(public_key));
+        assert_eq!(canonical_public_key("02"), None);
+    }
+}
@@ -21,9 +21,9 @@ pub const FIRST_VALIDATOR_MINT: u64 = 200_000_000 * DOMEX_DECIMALS;
 pub const UNMINTED_POOL: u64 = DOMEX_TOTAL_SUPPLY - FIRST_VALIDATOR_MINT;
 
 
-/// ==========================
-/// Validator Reward Constants
-/// ==========================
+// ==========================
+// Validator Reward Constants
+// ==========================
 
 /// Reward for 1 validator selected by global majority (per valid proof)
 pub const MAJORITY_SELECTED_VALIDATOR_REWARD: u64 = 6 * DOMEX_DECIMALS;
@@ -41,9 +41,9 @@ pub fn max_block_reward_budget() -> u64 {
 }
 
 
-/// ==========================
-/// Fuel Burn and Recycling
-/// ==========================
+// ==========================
+// Fuel Burn and Recycling
+// ==========================
 
 /// Minimum fuel burn required per ZK proof (0.00001 DOMEX)
 pub const MIN_PROOF_FUEL_BURN: u64 = 10 * DOMEX_DECIMALS / 1_000_000;
@@ -57,9 +57,9 @@ pub fn recycled_fuel(burned: u64) -> u64 {
 }
 
 
-/// ==========================
-/// DOMEX Supply Utilities
-/// ==========================
+// ==========================
+// DOMEX Supply Utilities
+// ==========================
 
 /// Total supply in decimal units (e.g. 1,000,000,000.000000)
 pub fn total_domex_human() -> f64 {
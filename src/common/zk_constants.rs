@@ -18,8 +18,9 @@ pub const MAX_VAULTS_PER_PROOF: usize = 64;
 /// Number of Poseidon inputs for standard hashing
 pub const POSEIDON_INPUT_WIDTH: usize = 3;
 
-/// Merkle tree height used for vault state roots
-pub const VAULT_MERKLE_TREE_HEIGHT: usize = 32;
+/// Merkle tree height used for vault state roots: one level per bit of a
+/// 32-byte balance key, so every key has its own slot
+pub const VAULT_MERKLE_TREE_HEIGHT: usize = 256;
 
 /// Merkle tree height for fuel burn registry
 pub const FUEL_MERKLE_TREE_HEIGHT: usize = 16;
@@ -7,8 +7,8 @@
 // Used by validators, proof builders, and batch verifiers.
 //
 
-use crate::poseidon_utils::poseidon_hash;
-use crate::common_types::{VaultId, MerkleRoot};
+use crate::matching::core::poseidon_utils::poseidon_hash;
+use crate::common::common_types::{VaultId, MerkleRoot};
 
 /// Formats ZK circuit inputs from Merkle roots and vault IDs
 pub fn format_zk_inputs(
@@ -7,9 +7,11 @@
 //    
     
 use std::collections::HashMap;    
-use std::time::{SystemTime, UNIX_EPOCH};    
+use std::sync::Arc;    
     
-use crate::validator::validator_identity::ValidatorId;    
+use crate::common::clock::{system_clock, Clock};    
+    
+use crate::validator::types::validator_identity::ValidatorId;    
     
 /// Governance proposal types    
 #[derive(Debug, Clone)]    
@@ -34,21 +36,27 @@ pub struct Proposal {
 }    
     
 /// Global DAO state    
-#[derive(Default)]    
 pub struct GovernanceDAO {    
     pub proposals: HashMap<u64, Proposal>,    
     pub votes: HashMap<u64, HashMap<ValidatorId, bool>>, // Proposal ID → Voter → Yes/No    
     pub next_id: u64,    
     pub voting_period_secs: u64,    
+    clock: Arc<dyn Clock>,    
 }    
     
 impl GovernanceDAO {    
     pub fn new(voting_period_secs: u64) -> Self {    
+        Self::with_clock(voting_period_secs, system_clock())    
+    }    
+    
+    /// DAO reading deadlines from an injected clock    
+    pub fn with_clock(voting_period_secs: u64, clock: Arc<dyn Clock>) -> Self {    
         Self {    
             proposals: HashMap::new(),    
             votes: HashMap::new(),    
             next_id: 0,    
             voting_period_secs,    
+            clock,    
         }    
     }    
     
@@ -59,7 +67,7 @@ impl GovernanceDAO {
         proposal_type: ProposalType,    
         description: String,    
     ) -> u64 {    
-        let now = current_timestamp();    
+        let now = self.clock.now_unix();    
         let id = self.next_id;    
         self.next_id += 1;    
     
@@ -82,7 +90,7 @@ impl GovernanceDAO {
     /// Cast vote for a proposal    
     pub fn vote(&mut self, validator: ValidatorId, proposal_id: u64, approve: bool) -> Result<(), String> {    
         if let Some(proposal) = self.proposals.get_mut(&proposal_id) {    
-            if current_timestamp() > proposal.vote_deadline {    
+            if self.clock.now_unix() > proposal.vote_deadline {    
                 return Err("Voting period expired".into());    
             }    
     
@@ -110,7 +118,7 @@ impl GovernanceDAO {
             if proposal.executed {    
                 return Err("Proposal already executed".into());    
             }    
-            if current_timestamp() < proposal.vote_deadline {    
+            if self.clock.now_unix() < proposal.vote_deadline {    
                 return Err("Voting still active".into());    
             }    
     
@@ -132,10 +140,8 @@ impl GovernanceDAO {
     }    
 }    
     
-/// Utility to get current timestamp in seconds    
-fn current_timestamp() -> u64 {    
-    SystemTime::now()    
-        .duration_since(UNIX_EPOCH)    
-        .unwrap()    
-        .as_secs()    
+impl Default for GovernanceDAO {    
+    fn default() -> Self {    
+        Self::new(0)    
+    }    
 }
@@ -2,7 +2,8 @@
 // balance_snapshot.rs — Merkle-Ready Delta Extractor
 // ==================================================
 
-use crate::types::{BalanceChange, MerkleDelta};
+use crate::matching::core::types::merkle::MerkleDelta;
+use crate::matching::core::types::order_book::BalanceChange;
 
 /// Converts a list of balance changes into a Merkle-compatible delta
 ///
@@ -18,7 +19,7 @@ pub fn generate_balance_delta(changes: &[BalanceChange]) -> Vec<MerkleDelta> {
     changes
         .iter()
         .map(|change| {
-            let identity = change.identity.clone(); //  Poseidon identity hash
+            let identity = hex::encode(change.identity); //  Poseidon identity hash (hex)
             let token = change.token.clone();       //  Token symbol (e.g., dBTC)
             let delta_value = change.delta;         //  Change in token balance
 
@@ -13,13 +13,90 @@ pub fn check_price_delta(order_price: u64, liquidity_price: u64, max_delta_bps:
         return false;
     }
 
-    let delta = if order_price > liquidity_price {
-        order_price - liquidity_price
-    } else {
-        liquidity_price - order_price
-    };
+    let delta = order_price.abs_diff(liquidity_price);
 
     let allowed = (liquidity_price as u128 * max_delta_bps as u128) / 10_000;
 
     delta as u128 <= allowed
 }
+
+/// Detailed rejection for the liquidity-share entry limit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiquidityDeltaError {
+    /// Entering balance exceeds the vault's configured share of liquidity
+    ExceedsShare {
+        token: String,
+        balance: u64,
+        total_liquidity: u64,
+        allowed: u64,
+        max_share_bps: u64,
+    },
+}
+
+/// Enforces the entry limit: an entering balance may not exceed
+/// `max_share_bps` of the vault's current liquidity in that token.
+/// The first depositor into a vault with no liquidity in the token is
+/// always admitted, since there is no share to measure against.
+///
+/// # Arguments
+/// * `token` - Token being onboarded (for error reporting)
+/// * `balance` - Balance the user is entering with
+/// * `total_liquidity` - Vault liquidity for the token, taken from current vault state
+/// * `max_share_bps` - Allowed share in basis points (e.g., 200 = 2%); 10_000 or more disables the limit
+///
+/// # Returns
+/// `Ok(())` if the balance fits within the allowed share
+pub fn check_liquidity_delta(
+    token: &str,
+    balance: u64,
+    total_liquidity: u64,
+    max_share_bps: u64,
+) -> Result<(), LiquidityDeltaError> {
+    if max_share_bps >= 10_000 || total_liquidity == 0 {
+        // Limit disabled, or first deposit bootstrapping an empty vault
+        return Ok(());
+    }
+
+    let allowed = (total_liquidity as u128 * max_share_bps as u128) / 10_000;
+
+    if balance as u128 > allowed {
+        return Err(LiquidityDeltaError::ExceedsShare {
+            token: token.to_string(),
+            balance,
+            total_liquidity,
+            allowed: allowed as u64,
+            max_share_bps,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_liquidity_share_limit() {
+        // 2% of 1_000_000 = 20_000
+        assert!(check_liquidity_delta("dBTC", 20_000, 1_000_000, 200).is_ok());
+
+        assert_eq!(
+            check_liquidity_delta("dBTC", 20_001, 1_000_000, 200),
+            Err(LiquidityDeltaError::ExceedsShare {
+                token: "dBTC".into(),
+                balance: 20_001,
+                total_liquidity: 1_000_000,
+                allowed: 20_000,
+                max_share_bps: 200,
+            })
+        );
+    }
+
+    #[test]
+    fn test_liquidity_share_empty_vault() {
+        // First depositor is admitted whatever the share limit
+        assert!(check_liquidity_delta("dBTC", 1_000_000, 0, 200).is_ok());
+        assert!(check_liquidity_delta("dBTC", 1, 0, 10_000).is_ok());
+    }
+}
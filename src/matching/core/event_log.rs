@@ -2,20 +2,21 @@
 // event_log.rs — Trading Event Hooks
 // ===================================
 
-use crate::types::{OrderInstruction, BalanceChange};
-use crate::types::event_log::TradeEvent;
+use crate::matching::core::types::order_book::{OrderInstruction, BalanceChange};
+use crate::matching::core::types::event_log::{TradeEvent, FillEvent};
 use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
 
 /// Emits a trade event to stdout or optional subscriber
-pub fn emit_trade_event(order: &OrderInstruction, delta: &Vec<BalanceChange>) {
+pub fn emit_trade_event(order: &OrderInstruction, delta: &[BalanceChange]) {
     let event = TradeEvent {
         vault_id: order.vault_id.clone(),
-        buyer: order.counterparty_hash.clone(),
-        seller: order.owner_hash.clone(),
+        buyer: hex::encode(order.counterparty_hash),
+        seller: hex::encode(order.owner_hash),
         token: order.token.clone(),
         size: order.size,
         price: order.price,
-        balance_delta: delta.clone(),
+        balance_delta: delta.to_vec(),
         timestamp: current_unix_timestamp(),
     };
 
@@ -31,8 +32,31 @@ pub fn emit_trade_event(order: &OrderInstruction, delta: &Vec<BalanceChange>) {
     );
 }
 
+/// Fan-out bus for fills. Slow subscribers lag and skip, they never block matching.
+#[derive(Clone)]
+pub struct EventBus {
+    tx: broadcast::Sender<FillEvent>,
+}
+
+impl EventBus {
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(capacity);
+        Self { tx }
+    }
+
+    /// New receiver for all fills published from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<FillEvent> {
+        self.tx.subscribe()
+    }
+
+    /// Publish a fill; returns how many subscribers received it
+    pub fn publish(&self, event: FillEvent) -> usize {
+        self.tx.send(event).unwrap_or(0)
+    }
+}
+
 /// Returns the current Unix timestamp
-fn current_unix_timestamp() -> u64 {
+pub(crate) fn current_unix_timestamp() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or_default()
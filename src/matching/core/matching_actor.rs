@@ -0,0 +1,384 @@
+// ================================================
+// matching_actor.rs — Per-Vault Async Matching Actor
+// ================================================
+
+//! Runs one vault's `OrderBook` and `VaultState` as a single-writer tokio task.
+//!
+//! - Callers talk to the task through a cloneable `VaultMatcherHandle`
+//! - Commands go over a bounded mpsc channel; a full channel is reported as
+//!   `ActorError::Backpressure` instead of queueing without limit
+//! - Every command carries a oneshot sender for its reply
+//! - Fills are published on the `EventBus` as `FillEvent`s
+//! - Time comes from the injected `Clock`, never from callers: it stamps order
+//!   expiry checks and fills, and a periodic tick drops expired resting orders
+//! - Vault rules and activations are read from the shared `VaultRegistry` at
+//!   the actor's epoch, which only moves forward through `AdvanceEpoch`
+
+use crate::matching::core::types::order_book::{OrderInstruction, VaultState};
+use crate::matching::core::types::event_log::FillEvent;
+use crate::matching::core::types::matching_actor::{ActorError, BookQuery, BookSnapshot, QueryReply};
+use crate::matching::core::order_book::{OrderBook, SubmitOutcome};
+use crate::matching::core::order_arena::{ArenaOrder, OrderId};
+use crate::matching::core::vault_registry::VaultRegistry;
+use crate::matching::core::event_log::EventBus;
+use crate::common::clock::{system_clock, Clock};
+
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+/// How often the actor drops expired resting orders
+pub const EXPIRY_TICK_SECS: u64 = 1;
+
+/// Commands accepted by the vault matching actor
+#[derive(Debug)]
+pub enum MatchCommand {
+    Submit {
+        order: OrderInstruction,
+        reply: oneshot::Sender<SubmitOutcome>,
+    },
+    Cancel {
+        order_id: OrderId,
+        reply: oneshot::Sender<Result<ArenaOrder, &'static str>>,
+    },
+    Snapshot {
+        reply: oneshot::Sender<BookSnapshot>,
+    },
+    Query {
+        query: BookQuery,
+        reply: oneshot::Sender<QueryReply>,
+    },
+    /// Drop resting orders expired at the actor's clock; replies with the count
+    ExpireOrders {
+        reply: oneshot::Sender<usize>,
+    },
+    /// Committed epoch boundary; replies with the actor's epoch afterwards
+    AdvanceEpoch {
+        epoch: u64,
+        reply: oneshot::Sender<u64>,
+    },
+}
+
+/// Cloneable sender side of a vault matching actor
+#[derive(Clone)]
+pub struct VaultMatcherHandle {
+    vault_id: String,
+    tx: mpsc::Sender<MatchCommand>,
+}
+
+impl VaultMatcherHandle {
+    pub fn vault_id(&self) -> &str {
+        &self.vault_id
+    }
+
+    /// Submit an order and wait for the matcher's outcome.
+    /// Expiry is checked against the actor's clock.
+    pub async fn submit(&self, order: OrderInstruction) -> Result<SubmitOutcome, ActorError> {
+        let (reply, rx) = oneshot::channel();
+        self.dispatch(MatchCommand::Submit { order, reply })?;
+        rx.await.map_err(|_| ActorError::NoReply)
+    }
+
+    /// Cancel a resting order by ID
+    pub async fn cancel(&self, order_id: OrderId) -> Result<Result<ArenaOrder, &'static str>, ActorError> {
+        let (reply, rx) = oneshot::channel();
+        self.dispatch(MatchCommand::Cancel { order_id, reply })?;
+        rx.await.map_err(|_| ActorError::NoReply)
+    }
+
+    /// Copy of the current book and balances
+    pub async fn snapshot(&self) -> Result<BookSnapshot, ActorError> {
+        let (reply, rx) = oneshot::channel();
+        self.dispatch(MatchCommand::Snapshot { reply })?;
+        rx.await.map_err(|_| ActorError::NoReply)
+    }
+
+    /// Read-only lookup against the book
+    pub async fn query(&self, query: BookQuery) -> Result<QueryReply, ActorError> {
+        let (reply, rx) = oneshot::channel();
+        self.dispatch(MatchCommand::Query { query, reply })?;
+        rx.await.map_err(|_| ActorError::NoReply)
+    }
+
+    /// Expire resting orders now instead of waiting for the next tick
+    pub async fn expire_orders(&self) -> Result<usize, ActorError> {
+        let (reply, rx) = oneshot::channel();
+        self.dispatch(MatchCommand::ExpireOrders { reply })?;
+        rx.await.map_err(|_| ActorError::NoReply)
+    }
+
+    /// Move the actor to a committed epoch; older epochs are ignored
+    pub async fn advance_epoch(&self, epoch: u64) -> Result<u64, ActorError> {
+        let (reply, rx) = oneshot::channel();
+        self.dispatch(MatchCommand::AdvanceEpoch { epoch, reply })?;
+        rx.await.map_err(|_| ActorError::NoReply)
+    }
+
+    /// Enqueue without waiting for channel capacity
+    fn dispatch(&self, command: MatchCommand) -> Result<(), ActorError> {
+        self.tx.try_send(command).map_err(|e| match e {
+            mpsc::error::TrySendError::Full(_) => ActorError::Backpressure,
+            mpsc::error::TrySendError::Closed(_) => ActorError::Closed,
+        })
+    }
+}
+
+/// Single-writer owner of a vault's book and state
+pub struct VaultMatcher {
+    book: OrderBook,
+    state: VaultState,
+    registry: Arc<RwLock<VaultRegistry>>,
+    epoch: u64,
+    events: EventBus,
+    fill_sequence: u64,
+    clock: Arc<dyn Clock>,
+    rx: mpsc::Receiver<MatchCommand>,
+}
+
+impl VaultMatcher {
+    /// Build the actor and its handle. `capacity` bounds the command queue.
+    /// Orders are checked against `registry` at `epoch` until it advances.
+    pub fn new(
+        book: OrderBook,
+        state: VaultState,
+        registry: Arc<RwLock<VaultRegistry>>,
+        epoch: u64,
+        events: EventBus,
+        capacity: usize,
+    ) -> (Self, VaultMatcherHandle) {
+        Self::with_clock(book, state, registry, epoch, events, capacity, system_clock())
+    }
+
+    /// Same as `new`, reading time from `clock`
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_clock(
+        book: OrderBook,
+        state: VaultState,
+        registry: Arc<RwLock<VaultRegistry>>,
+        epoch: u64,
+        events: EventBus,
+        capacity: usize,
+        clock: Arc<dyn Clock>,
+    ) -> (Self, VaultMatcherHandle) {
+        let (tx, rx) = mpsc::channel(capacity);
+        let handle = VaultMatcherHandle {
+            vault_id: state.vault_id.clone(),
+            tx,
+        };
+        let actor = Self {
+            book,
+            state,
+            registry,
+            epoch,
+            events,
+            fill_sequence: 0,
+            clock,
+            rx,
+        };
+        (actor, handle)
+    }
+
+    /// Build the actor and run it on the current tokio runtime
+    pub fn spawn(
+        book: OrderBook,
+        state: VaultState,
+        registry: Arc<RwLock<VaultRegistry>>,
+        epoch: u64,
+        events: EventBus,
+        capacity: usize,
+    ) -> (VaultMatcherHandle, JoinHandle<()>) {
+        let (actor, handle) = Self::new(book, state, registry, epoch, events, capacity);
+        (handle, tokio::spawn(actor.run()))
+    }
+
+    /// Process commands until every handle is dropped, expiring resting
+    /// orders every `EXPIRY_TICK_SECS`
+    pub async fn run(mut self) {
+        let mut expiry_tick = tokio::time::interval(Duration::from_secs(EXPIRY_TICK_SECS));
+        loop {
+            tokio::select! {
+                command = self.rx.recv() => match command {
+                    Some(command) => self.handle(command),
+                    None => break,
+                },
+                _ = expiry_tick.tick() => {
+                    self.expire_orders();
+                }
+            }
+        }
+        println!("[ACTOR] Vault {} matcher stopped", self.state.vault_id);
+    }
+
+    fn handle(&mut self, command: MatchCommand) {
+        // A dropped reply receiver just means the caller gave up; ignore send errors
+        match command {
+            MatchCommand::Submit { order, reply } => {
+                let now = self.clock.now_unix();
+                let outcome = {
+                    let registry = self.registry.read().unwrap_or_else(|e| e.into_inner());
+                    self.book.submit_order(&mut self.state, order, &registry, self.epoch, now)
+                };
+                if let SubmitOutcome::Matched(proposal) = &outcome {
+                    self.fill_sequence += 1;
+                    self.events.publish(FillEvent {
+                        vault_id: proposal.vault_id.clone(),
+                        sequence: self.fill_sequence,
+                        trade: proposal.trade.clone(),
+                        timestamp: now,
+                    });
+                }
+                let _ = reply.send(outcome);
+            }
+            MatchCommand::Cancel { order_id, reply } => {
+                let _ = reply.send(self.book.cancel_order(&mut self.state, order_id));
+            }
+            MatchCommand::Snapshot { reply } => {
+                let _ = reply.send(self.snapshot());
+            }
+            MatchCommand::Query { query, reply } => {
+                let _ = reply.send(self.query(query));
+            }
+            MatchCommand::ExpireOrders { reply } => {
+                let _ = reply.send(self.expire_orders());
+            }
+            MatchCommand::AdvanceEpoch { epoch, reply } => {
+                self.epoch = self.epoch.max(epoch);
+                let _ = reply.send(self.epoch);
+            }
+        }
+    }
+
+    /// Drop resting orders whose expiry is at or before the clock's now
+    fn expire_orders(&mut self) -> usize {
+        let expired = self.book.expire_orders(&mut self.state, self.clock.now_unix());
+        if expired > 0 {
+            println!("[ACTOR] Vault {} expired {} resting orders", self.state.vault_id, expired);
+        }
+        expired
+    }
+
+    fn snapshot(&self) -> BookSnapshot {
+        let (best_bid, best_ask) = self.book.best_prices();
+        BookSnapshot {
+            vault_id: self.state.vault_id.clone(),
+            balances: self.state.balances.clone(),
+            reserved: self.state.reserved.clone(),
+            best_bid,
+            best_ask,
+            resting_orders: self.book.resting_count(),
+            last_price: self.book.get_last_price(),
+        }
+    }
+
+    fn query(&self, query: BookQuery) -> QueryReply {
+        match query {
+            BookQuery::Order(id) => QueryReply::Order(self.book.get_order(id).copied()),
+            BookQuery::BestPrices => {
+                let (bid, ask) = self.book.best_prices();
+                QueryReply::BestPrices { bid, ask }
+            }
+            BookQuery::LastPrice => QueryReply::LastPrice(self.book.get_last_price()),
+            BookQuery::AvailableBalance { identity, token } => {
+                QueryReply::AvailableBalance(self.state.available_balance(&identity, &token))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matching::core::vault_registry::{VaultMetadata, VaultPair, VaultStatus};
+
+    fn meta() -> VaultMetadata {
+        VaultMetadata {
+            tick_size: 1,
+            lot_size: 1,
+            max_delta_bps: 200,
+            base_token: "dBTC".into(),
+            quote_token: "dUSDT".into(),
+            base_decimals: 8,
+            quote_decimals: 2,
+            liquidity_price: 6_000_000,
+            status: VaultStatus::Active,
+            activation_ttl_epochs: 10,
+            max_entry_share_bps: 200,
+        }
+    }
+
+    fn registry() -> Arc<RwLock<VaultRegistry>> {
+        let mut registry = VaultRegistry::new();
+        registry.register_vault(VaultPair("vault-btc-usdt".into()), meta());
+        Arc::new(RwLock::new(registry))
+    }
+
+    fn matcher(capacity: usize) -> (VaultMatcher, VaultMatcherHandle) {
+        let mut state = VaultState::new("vault-btc-usdt");
+        state.balances.insert(([1u8; 32], "dUSDT".into()), 500);
+        VaultMatcher::new(OrderBook::new(&meta()), state, registry(), 0, EventBus::new(16), capacity)
+    }
+
+    #[test]
+    fn test_full_channel_reports_backpressure() {
+        let (_actor, handle) = matcher(1);
+
+        let (reply, _rx1) = oneshot::channel();
+        assert_eq!(handle.dispatch(MatchCommand::Snapshot { reply }), Ok(()));
+
+        let (reply, _rx2) = oneshot::channel();
+        assert_eq!(handle.dispatch(MatchCommand::Snapshot { reply }), Err(ActorError::Backpressure));
+    }
+
+    #[tokio::test]
+    async fn test_actor_serves_snapshot_query_and_cancel() {
+        let (actor, handle) = matcher(8);
+        let task = tokio::spawn(actor.run());
+
+        let snapshot = handle.snapshot().await.unwrap();
+        assert_eq!(snapshot.vault_id, "vault-btc-usdt");
+        assert_eq!(snapshot.resting_orders, 0);
+
+        match handle
+            .query(BookQuery::AvailableBalance { identity: [1u8; 32], token: "dUSDT".into() })
+            .await
+            .unwrap()
+        {
+            QueryReply::AvailableBalance(amount) => assert_eq!(amount, 500),
+            other => panic!("unexpected reply {:?}", other),
+        }
+
+        assert_eq!(handle.cancel(OrderId(42)).await.unwrap(), Err("Order not found"));
+
+        drop(handle);
+        task.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_expiry_reads_injected_clock() {
+        use crate::common::clock::ManualClock;
+
+        let clock = Arc::new(ManualClock::new(1_000));
+        let (actor, handle) = VaultMatcher::with_clock(
+            OrderBook::new(&meta()),
+            VaultState::new("vault-btc-usdt"),
+            registry(),
+            0,
+            EventBus::new(16),
+            8,
+            clock.clone(),
+        );
+        let task = tokio::spawn(actor.run());
+
+        clock.advance(60);
+        assert_eq!(handle.expire_orders().await, Ok(0));
+
+        // Epochs only move forward
+        assert_eq!(handle.advance_epoch(3).await, Ok(3));
+        assert_eq!(handle.advance_epoch(2).await, Ok(3));
+
+        drop(handle);
+        task.await.unwrap();
+    }
+}
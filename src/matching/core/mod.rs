@@ -1,3 +1,4 @@
+pub mod types;
 pub mod vault_logic;
 pub mod order_book;
 pub mod vault_registry;
@@ -7,3 +8,9 @@ pub mod vault_raft_adapter;
 pub mod delta_checker;
 pub mod balance_snapshot;
 pub mod event_log;
+pub mod order_intake;
+pub mod order_auth;
+pub mod order_arena;
+pub mod matching_actor;
+pub mod unboarding_verifier;
+pub mod poseidon_utils;
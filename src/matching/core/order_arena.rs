@@ -0,0 +1,427 @@
+// ==========================================================
+// order_arena.rs — Arena-Allocated Order Storage for Matching
+// ==========================================================
+
+//! Storage layer behind `OrderBook`, built for microsecond matching.
+//!
+//! - Orders live in a slab (`Vec<Slot>`) with a free list; slots are reused.
+//! - Each price level is an intrusive doubly-linked FIFO threaded through
+//!   the slots (`prev` / `next`), so unlinking any order is O(1).
+//! - `OrderId → slot` index gives O(1) cancel.
+//! - Owners are stored as `IdentityHandle` (u32) instead of hash strings.
+
+use crate::matching::core::types::order_book::{OrderIntent, PoseidonHash};
+
+use std::collections::{BTreeMap, HashMap};
+
+/// Slot index inside the arena
+pub type SlotIndex = u32;
+
+/// Sentinel for "no slot" in intrusive links
+const NIL: SlotIndex = SlotIndex::MAX;
+
+/// Book-assigned order identifier (monotonic per book)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct OrderId(pub u64);
+
+/// Compact handle for an interned Poseidon identity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IdentityHandle(pub u32);
+
+/// Maps Poseidon identities to compact integer handles (never shrinks)
+#[derive(Debug, Default)]
+pub struct IdentityInterner {
+    handles: HashMap<PoseidonHash, IdentityHandle>,
+    hashes: Vec<PoseidonHash>,
+}
+
+impl IdentityInterner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the handle for `hash`, allocating one on first sight
+    pub fn intern(&mut self, hash: &PoseidonHash) -> IdentityHandle {
+        if let Some(handle) = self.handles.get(hash) {
+            return *handle;
+        }
+        let handle = IdentityHandle(self.hashes.len() as u32);
+        self.hashes.push(*hash);
+        self.handles.insert(*hash, handle);
+        handle
+    }
+
+    /// Looks up an existing handle without allocating
+    pub fn get(&self, hash: &PoseidonHash) -> Option<IdentityHandle> {
+        self.handles.get(hash).copied()
+    }
+
+    /// Resolves a handle back to its Poseidon identity
+    pub fn resolve(&self, handle: IdentityHandle) -> &PoseidonHash {
+        &self.hashes[handle.0 as usize]
+    }
+}
+
+/// A resting order as stored in the arena (no heap data)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArenaOrder {
+    pub id: OrderId,
+    pub owner: IdentityHandle,
+    pub intent: OrderIntent,
+    pub price: u64,
+    pub size: u64,
+    pub nonce: u64,
+    pub expiry: u64,
+    pub reserved_amount: u64, // Base for sells, quote for buys
+}
+
+#[derive(Debug)]
+struct Slot {
+    order: Option<ArenaOrder>,
+    prev: SlotIndex,
+    next: SlotIndex, // Doubles as free-list link when `order` is None
+}
+
+/// One price level: head/tail of its intrusive FIFO plus aggregates
+#[derive(Debug, Clone, Copy)]
+struct PriceLevel {
+    head: SlotIndex,
+    tail: SlotIndex,
+    count: u32,
+    total_size: u64,
+}
+
+impl PriceLevel {
+    fn empty() -> Self {
+        Self {
+            head: NIL,
+            tail: NIL,
+            count: 0,
+            total_size: 0,
+        }
+    }
+}
+
+/// Arena-backed two-sided book for a single vault
+#[derive(Debug)]
+pub struct OrderArena {
+    slots: Vec<Slot>,
+    free_head: SlotIndex,
+    bids: BTreeMap<u64, PriceLevel>,
+    asks: BTreeMap<u64, PriceLevel>,
+    index: HashMap<OrderId, SlotIndex>,
+    next_id: u64,
+}
+
+impl Default for OrderArena {
+    fn default() -> Self {
+        Self::with_capacity(0)
+    }
+}
+
+impl OrderArena {
+    /// Create an arena with room for `capacity` resting orders before growing
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            free_head: NIL,
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            index: HashMap::with_capacity(capacity),
+            next_id: 0,
+        }
+    }
+
+    /// Number of resting orders
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Rest a new order at the tail of its price level. O(log levels).
+    #[allow(clippy::too_many_arguments)]
+    pub fn insert(
+        &mut self,
+        owner: IdentityHandle,
+        intent: OrderIntent,
+        price: u64,
+        size: u64,
+        nonce: u64,
+        expiry: u64,
+        reserved_amount: u64,
+    ) -> OrderId {
+        let id = OrderId(self.next_id);
+        self.next_id += 1;
+
+        let order = ArenaOrder {
+            id,
+            owner,
+            intent,
+            price,
+            size,
+            nonce,
+            expiry,
+            reserved_amount,
+        };
+
+        let slot = self.alloc_slot(order);
+        let level = self
+            .side_mut(intent)
+            .entry(price)
+            .or_insert_with(PriceLevel::empty);
+
+        let old_tail = level.tail;
+        level.tail = slot;
+        if old_tail == NIL {
+            level.head = slot;
+        }
+        level.count += 1;
+        level.total_size += size;
+
+        self.slots[slot as usize].prev = old_tail;
+        if old_tail != NIL {
+            self.slots[old_tail as usize].next = slot;
+        }
+
+        self.index.insert(id, slot);
+        id
+    }
+
+    /// Fetch a resting order by id
+    pub fn get(&self, id: OrderId) -> Option<&ArenaOrder> {
+        self.index
+            .get(&id)
+            .and_then(|slot| self.slots[*slot as usize].order.as_ref())
+    }
+
+    /// Remove a resting order by id. O(1) unlink (plus level cleanup).
+    pub fn cancel(&mut self, id: OrderId) -> Option<ArenaOrder> {
+        let slot = self.index.remove(&id)?;
+        Some(self.unlink(slot))
+    }
+
+    /// Pop the oldest order at the best opposite level that crosses `limit_price`.
+    /// A buy taker hits the lowest ask ≤ limit; a sell taker hits the highest bid ≥ limit.
+    pub fn pop_best(&mut self, taker: OrderIntent, limit_price: u64) -> Option<ArenaOrder> {
+        let head = self.best_head(taker, limit_price)?;
+        let order = self.unlink(head);
+        self.index.remove(&order.id);
+        Some(order)
+    }
+
+    /// The order `pop_best` would return, left on the book
+    pub fn peek_best(&self, taker: OrderIntent, limit_price: u64) -> Option<ArenaOrder> {
+        let head = self.best_head(taker, limit_price)?;
+        self.slots[head as usize].order
+    }
+
+    /// Head slot of the best opposite level crossing `limit_price`
+    fn best_head(&self, taker: OrderIntent, limit_price: u64) -> Option<SlotIndex> {
+        match taker {
+            OrderIntent::Buy => self
+                .asks
+                .iter()
+                .next()
+                .filter(|(price, _)| **price <= limit_price)
+                .map(|(_, level)| level.head),
+            OrderIntent::Sell => self
+                .bids
+                .iter()
+                .next_back()
+                .filter(|(price, _)| **price >= limit_price)
+                .map(|(_, level)| level.head),
+        }
+    }
+
+    /// Highest resting bid price
+    pub fn best_bid(&self) -> Option<u64> {
+        self.bids.keys().next_back().copied()
+    }
+
+    /// Lowest resting ask price
+    pub fn best_ask(&self) -> Option<u64> {
+        self.asks.keys().next().copied()
+    }
+
+    /// (order count, total size) resting at a price on one side
+    pub fn level_depth(&self, side: OrderIntent, price: u64) -> Option<(u32, u64)> {
+        self.side(side)
+            .get(&price)
+            .map(|level| (level.count, level.total_size))
+    }
+
+    /// Orders at a price level in FIFO order
+    pub fn level_orders(&self, side: OrderIntent, price: u64) -> Vec<ArenaOrder> {
+        let mut out = Vec::new();
+        let mut cursor = self.side(side).get(&price).map(|l| l.head).unwrap_or(NIL);
+        while cursor != NIL {
+            let slot = &self.slots[cursor as usize];
+            if let Some(order) = slot.order {
+                out.push(order);
+            }
+            cursor = slot.next;
+        }
+        out
+    }
+
+    /// Remove every order with `expiry <= now`, returning them
+    pub fn expire(&mut self, now: u64) -> Vec<ArenaOrder> {
+        let expired_ids: Vec<OrderId> = self
+            .slots
+            .iter()
+            .filter_map(|slot| slot.order)
+            .filter(|order| order.expiry <= now)
+            .map(|order| order.id)
+            .collect();
+
+        expired_ids
+            .into_iter()
+            .filter_map(|id| self.cancel(id))
+            .collect()
+    }
+
+    fn side(&self, intent: OrderIntent) -> &BTreeMap<u64, PriceLevel> {
+        match intent {
+            OrderIntent::Buy => &self.bids,
+            OrderIntent::Sell => &self.asks,
+        }
+    }
+
+    fn side_mut(&mut self, intent: OrderIntent) -> &mut BTreeMap<u64, PriceLevel> {
+        match intent {
+            OrderIntent::Buy => &mut self.bids,
+            OrderIntent::Sell => &mut self.asks,
+        }
+    }
+
+    fn alloc_slot(&mut self, order: ArenaOrder) -> SlotIndex {
+        if self.free_head != NIL {
+            let slot = self.free_head;
+            let entry = &mut self.slots[slot as usize];
+            self.free_head = entry.next;
+            entry.order = Some(order);
+            entry.prev = NIL;
+            entry.next = NIL;
+            slot
+        } else {
+            self.slots.push(Slot {
+                order: Some(order),
+                prev: NIL,
+                next: NIL,
+            });
+            (self.slots.len() - 1) as SlotIndex
+        }
+    }
+
+    /// Unlinks a slot from its level, frees it, and returns the order
+    fn unlink(&mut self, slot: SlotIndex) -> ArenaOrder {
+        let (order, prev, next) = {
+            let entry = &mut self.slots[slot as usize];
+            let order = entry.order.take().expect("unlink of free slot");
+            (order, entry.prev, entry.next)
+        };
+
+        if prev != NIL {
+            self.slots[prev as usize].next = next;
+        }
+        if next != NIL {
+            self.slots[next as usize].prev = prev;
+        }
+
+        let book = self.side_mut(order.intent);
+        let mut remove_level = false;
+        if let Some(level) = book.get_mut(&order.price) {
+            if level.head == slot {
+                level.head = next;
+            }
+            if level.tail == slot {
+                level.tail = prev;
+            }
+            level.count -= 1;
+            level.total_size -= order.size;
+            remove_level = level.count == 0;
+        }
+        if remove_level {
+            book.remove(&order.price);
+        }
+
+        let entry = &mut self.slots[slot as usize];
+        entry.prev = NIL;
+        entry.next = self.free_head;
+        self.free_head = slot;
+
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rest(arena: &mut OrderArena, intent: OrderIntent, price: u64, nonce: u64) -> OrderId {
+        arena.insert(IdentityHandle(0), intent, price, 10, nonce, u64::MAX, 10)
+    }
+
+    #[test]
+    fn test_fifo_and_best_price() {
+        let mut arena = OrderArena::with_capacity(8);
+        let a = rest(&mut arena, OrderIntent::Sell, 101, 1);
+        let b = rest(&mut arena, OrderIntent::Sell, 100, 2);
+        let c = rest(&mut arena, OrderIntent::Sell, 100, 3);
+
+        assert_eq!(arena.best_ask(), Some(100));
+        assert_eq!(arena.pop_best(OrderIntent::Buy, 105).map(|o| o.id), Some(b));
+        assert_eq!(arena.pop_best(OrderIntent::Buy, 105).map(|o| o.id), Some(c));
+        assert_eq!(arena.pop_best(OrderIntent::Buy, 100), None);
+        assert_eq!(arena.pop_best(OrderIntent::Buy, 101).map(|o| o.id), Some(a));
+        assert!(arena.is_empty());
+    }
+
+    #[test]
+    fn test_peek_best_leaves_order_queued() {
+        let mut arena = OrderArena::with_capacity(4);
+        let a = rest(&mut arena, OrderIntent::Buy, 50, 1);
+        let b = rest(&mut arena, OrderIntent::Buy, 50, 2);
+
+        assert_eq!(arena.peek_best(OrderIntent::Sell, 50).map(|o| o.id), Some(a));
+        assert_eq!(arena.peek_best(OrderIntent::Sell, 51), None);
+        assert_eq!(arena.len(), 2);
+
+        // Still at the head after peeking
+        assert_eq!(arena.pop_best(OrderIntent::Sell, 50).map(|o| o.id), Some(a));
+        assert_eq!(arena.peek_best(OrderIntent::Sell, 50).map(|o| o.id), Some(b));
+    }
+
+    #[test]
+    fn test_cancel_middle_and_slot_reuse() {
+        let mut arena = OrderArena::with_capacity(4);
+        let a = rest(&mut arena, OrderIntent::Buy, 50, 1);
+        let b = rest(&mut arena, OrderIntent::Buy, 50, 2);
+        let c = rest(&mut arena, OrderIntent::Buy, 50, 3);
+
+        assert_eq!(arena.cancel(b).map(|o| o.nonce), Some(2));
+        assert_eq!(arena.cancel(b), None);
+        assert_eq!(arena.level_depth(OrderIntent::Buy, 50), Some((2, 20)));
+
+        let ids: Vec<OrderId> = arena.level_orders(OrderIntent::Buy, 50).iter().map(|o| o.id).collect();
+        assert_eq!(ids, vec![a, c]);
+
+        // Freed slot is reused, arena does not grow
+        rest(&mut arena, OrderIntent::Buy, 49, 4);
+        assert_eq!(arena.slots.len(), 3);
+        assert_eq!(arena.best_bid(), Some(50));
+    }
+
+    #[test]
+    fn test_interner_round_trip() {
+        let mut interner = IdentityInterner::new();
+        let h1 = interner.intern(&[1u8; 32]);
+        let h2 = interner.intern(&[2u8; 32]);
+        assert_eq!(interner.intern(&[1u8; 32]), h1);
+        assert_ne!(h1, h2);
+        assert_eq!(interner.resolve(h2), &[2u8; 32]);
+    }
+}
@@ -0,0 +1,226 @@
+// ===================================================
+// order_auth.rs — Poseidon Order Authorization (ORDER domain)
+// ===================================================
+
+//! Binds every order to the submitter's identity secret so that nobody can
+//! trade under another user's `owner_hash`.
+//!
+//! Scheme (no signatures, Poseidon only):
+//! - Client derives `auth_key = Poseidon(ORDER || sk || owner_hash)` and hands it
+//!   to the vault once, at entry time.
+//! - The key is registered only by `verify_user_entry`, after the entry leaf
+//!   `entry_leaf(owner_hash, token, balance, auth_key)` is proven under the
+//!   vault's Merkle root. Only the owner's ZK onboarding produces that leaf, so
+//!   nobody else can install or rotate a key for `owner_hash`; rotating means
+//!   entering again with a fresh proof.
+//! - Each order carries `commitment = Poseidon(ORDER || auth_key || vault_id ||
+//!   token || intent || order_type || size || price || nonce || expiry)` as
+//!   `vault_signature`.
+//! - The matcher recomputes the commitment with the registered key and rejects
+//!   any order that does not match or has expired.
+//! - Strings and hashes are hashed as length-prefixed limbs (`string_to_limbs`),
+//!   never truncated.
+
+use pasta_curves::group::ff::PrimeField;
+use pasta_curves::Fp;
+use crate::common::poseidon_hasher::PoseidonHasher;
+
+use crate::common::merkle::{hash_leaf, MerkleHash};
+use crate::matching::core::poseidon_utils::{bytes_to_limbs, string_to_limbs, u64_to_fp, RAW_BYTES_TAG};
+use crate::validator::circuit_constants::domains;
+use crate::matching::core::types::order_book::{OrderInstruction, OrderIntent, OrderType, PoseidonHash};
+
+use std::collections::HashMap;
+
+/// Reasons an order fails authorization
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderAuthError {
+    /// No order auth key registered for this identity
+    UnknownIdentity,
+
+    /// Order carries no commitment (`vault_signature`)
+    MissingCommitment,
+
+    /// Recomputed commitment differs from the submitted one
+    CommitmentMismatch,
+
+    /// Order expiry is at or before the current time
+    Expired { expiry: u64, now: u64 },
+}
+
+/// Field encoding of the order intent (0 is reserved for "unset")
+fn intent_to_fp(intent: &OrderIntent) -> Fp {
+    match intent {
+        OrderIntent::Buy => u64_to_fp(1),
+        OrderIntent::Sell => u64_to_fp(2),
+    }
+}
+
+/// Field encoding of the order type (0 is reserved for "unset")
+fn order_type_to_fp(order_type: &OrderType) -> Fp {
+    match order_type {
+        OrderType::Limit => u64_to_fp(1),
+        OrderType::Market => u64_to_fp(2),
+    }
+}
+
+/// Derives the per-identity order auth key: Poseidon(ORDER || sk || owner_hash).
+/// Computed client-side; the secret never leaves the client.
+pub fn derive_order_auth_key(sk_fp: Fp, owner_hash: &PoseidonHash) -> Fp {
+    let mut inputs = string_to_limbs(domains::ORDER);
+    inputs.push(sk_fp);
+    inputs.extend(bytes_to_limbs(RAW_BYTES_TAG, owner_hash));
+
+    let mut hasher = PoseidonHasher::new();
+    hasher.hash(&inputs)
+}
+
+/// Parses a hex-encoded auth key; None unless it is a canonical field element
+pub fn auth_key_from_hex(hex_str: &str) -> Option<Fp> {
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(hex_str.trim_start_matches("0x"), &mut bytes).ok()?;
+    Option::from(Fp::from_repr(bytes))
+}
+
+/// Vault entry leaf binding an identity's proven balance to its order auth key:
+/// leaf hash of (owner_hash || auth_key || balance LE || token)
+pub fn entry_leaf(owner_hash: &PoseidonHash, token: &str, balance: u64, auth_key: Fp) -> MerkleHash {
+    let mut data = Vec::with_capacity(72 + token.len());
+    data.extend_from_slice(owner_hash);
+    data.extend_from_slice(&auth_key.to_repr());
+    data.extend_from_slice(&balance.to_le_bytes());
+    data.extend_from_slice(token.as_bytes());
+    hash_leaf(&data)
+}
+
+/// Computes the ORDER-domain commitment over
+/// (vault_id, token, intent, order_type, size, price, nonce, expiry), keyed by `auth_key`.
+pub fn compute_order_commitment(auth_key: Fp, order: &OrderInstruction) -> String {
+    let mut inputs = string_to_limbs(domains::ORDER);
+    inputs.push(auth_key);
+    inputs.extend(string_to_limbs(&order.vault_id));
+    inputs.extend(string_to_limbs(&order.token));
+    inputs.extend([
+        intent_to_fp(&order.intent),
+        order_type_to_fp(&order.order_type),
+        u64_to_fp(order.size),
+        u64_to_fp(order.price),
+        u64_to_fp(order.nonce),
+        u64_to_fp(order.expiry),
+    ]);
+
+    let mut hasher = PoseidonHasher::new();
+    hex::encode(hasher.hash(&inputs).to_repr())
+}
+
+/// Per-vault registry of order auth keys, indexed by Poseidon identity
+#[derive(Clone, Default)]
+pub struct OrderAuthRegistry {
+    keys: HashMap<PoseidonHash, Fp>,
+}
+
+impl OrderAuthRegistry {
+    pub fn new() -> Self {
+        Self {
+            keys: HashMap::new(),
+        }
+    }
+
+    /// Installs the auth key proven by a vault entry. Only `verify_user_entry`
+    /// calls this, once the entry leaf binding the key is proven under the
+    /// vault root; a fresh entry is the only way to rotate.
+    pub(crate) fn register_auth_key(&mut self, owner_hash: PoseidonHash, auth_key: Fp) {
+        self.keys.insert(owner_hash, auth_key);
+    }
+
+    /// Drop the auth key on offboarding
+    pub fn remove_auth_key(&mut self, owner_hash: &PoseidonHash) {
+        self.keys.remove(owner_hash);
+    }
+
+    /// Verifies that `order` was committed by the holder of `owner_hash`'s secret
+    /// and has not expired at `now` (unix seconds).
+    pub fn verify_order(&self, order: &OrderInstruction, now: u64) -> Result<(), OrderAuthError> {
+        let auth_key = self
+            .keys
+            .get(&order.owner_hash)
+            .ok_or(OrderAuthError::UnknownIdentity)?;
+
+        let submitted = order
+            .commitment
+            .as_deref()
+            .ok_or(OrderAuthError::MissingCommitment)?;
+
+        if order.expiry <= now {
+            return Err(OrderAuthError::Expired {
+                expiry: order.expiry,
+                now,
+            });
+        }
+
+        let expected = compute_order_commitment(*auth_key, order);
+        if !submitted.trim_start_matches("0x").eq_ignore_ascii_case(&expected) {
+            return Err(OrderAuthError::CommitmentMismatch);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(owner_hash: PoseidonHash) -> OrderInstruction {
+        OrderInstruction {
+            vault_id: "vault-btc-usdt".to_string(),
+            token: "dBTC".to_string(),
+            intent: OrderIntent::Buy,
+            order_type: OrderType::Limit,
+            size: 50_000_000,
+            price: 6_000_025,
+            nonce: 1,
+            expiry: 1_900_000_000,
+            commitment: None,
+            owner_hash,
+            counterparty_hash: [0u8; 32],
+        }
+    }
+
+    #[test]
+    fn test_commitment_binds_order_type() {
+        let owner = [0xffu8; 32];
+        let key = derive_order_auth_key(u64_to_fp(42), &owner);
+        let limit = order(owner);
+        let market = OrderInstruction { order_type: OrderType::Market, ..limit.clone() };
+        assert_ne!(compute_order_commitment(key, &limit), compute_order_commitment(key, &market));
+    }
+
+    #[test]
+    fn test_verify_order_requires_registered_key() {
+        let owner = [0xffu8; 32];
+        let key = derive_order_auth_key(u64_to_fp(42), &owner);
+        let mut signed = order(owner);
+        signed.commitment = Some(compute_order_commitment(key, &signed));
+
+        let mut registry = OrderAuthRegistry::new();
+        assert_eq!(registry.verify_order(&signed, 1_000), Err(OrderAuthError::UnknownIdentity));
+
+        registry.register_auth_key(owner, key);
+        assert_eq!(registry.verify_order(&signed, 1_000), Ok(()));
+
+        let other_key = derive_order_auth_key(u64_to_fp(43), &owner);
+        registry.register_auth_key(owner, other_key);
+        assert_eq!(registry.verify_order(&signed, 1_000), Err(OrderAuthError::CommitmentMismatch));
+    }
+
+    #[test]
+    fn test_entry_leaf_binds_auth_key() {
+        let owner = [0xabu8; 32];
+        let key = derive_order_auth_key(u64_to_fp(42), &owner);
+        let other = derive_order_auth_key(u64_to_fp(43), &owner);
+        assert_ne!(entry_leaf(&owner, "dBTC", 10, key), entry_leaf(&owner, "dBTC", 10, other));
+        assert_eq!(auth_key_from_hex(&hex::encode(key.to_repr())), Some(key));
+        assert_eq!(auth_key_from_hex(&"ff".repeat(32)), None);
+    }
+}
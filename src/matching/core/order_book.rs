@@ -4,110 +4,205 @@
 
 //! Matching engine for limit/market orders inside a vault.
 //! Applies skip logic (only one node matches), and emits raft proposals.
+//!
+//! Resting orders are stored in an `OrderArena` (slab + intrusive FIFO per
+//! price level), owners are interned to integer handles, and cancels are O(1)
+//! through the order ID index.
 
-use crate::types::{OrderInstruction, TradeResult, VaultState, RaftProposal};
-use crate::vault_registry::{VaultMetadata, is_vault_active};
-use crate::vault_logic::execute_trade;
+use crate::matching::core::types::order_book::{OrderInstruction, OrderIntent, PoseidonHash, VaultState, RaftProposal};
+use crate::matching::core::vault_registry::{VaultMetadata, VaultPair, VaultRegistry};
+use crate::matching::core::vault_logic::execute_trade;
+use crate::matching::core::order_auth::OrderAuthRegistry;
+use crate::matching::core::order_arena::{OrderArena, OrderId, ArenaOrder, IdentityHandle, IdentityInterner};
 
-use std::collections::{BTreeMap, VecDeque};
+use std::collections::HashMap;
+
+/// Outcome of submitting an order to the matcher
+#[derive(Debug, Clone)]
+pub enum SubmitOutcome {
+    Matched(RaftProposal),   // Crossed a resting order, trade executed
+    Rested(OrderId),         // Queued on the book under this ID
+    Rejected(&'static str),  // Failed activation, auth, nonce, balance or execution
+}
 
 /// OrderBook stores limit orders for a single token pair.
 pub struct OrderBook {
-    bids: BTreeMap<u64, VecDeque<OrderInstruction>>, // price -> FIFO queue
-    asks: BTreeMap<u64, VecDeque<OrderInstruction>>, // price -> FIFO queue
+    arena: OrderArena,
+    identities: IdentityInterner,
+    nonce_index: HashMap<(IdentityHandle, u64), OrderId>, // (owner, nonce) → order
+    base_token: String,
+    quote_token: String,
+    base_decimals: u8,
     last_price: u64,
+    pub order_auth: OrderAuthRegistry, // ORDER-domain auth keys per identity
 }
 
 impl OrderBook {
-    pub fn new() -> Self {
+    pub fn new(vault_meta: &VaultMetadata) -> Self {
+        Self::with_capacity(vault_meta, 0)
+    }
+
+    /// Pre-size the arena for `capacity` resting orders
+    pub fn with_capacity(vault_meta: &VaultMetadata, capacity: usize) -> Self {
         Self {
-            bids: BTreeMap::new(),
-            asks: BTreeMap::new(),
+            arena: OrderArena::with_capacity(capacity),
+            identities: IdentityInterner::new(),
+            nonce_index: HashMap::with_capacity(capacity),
+            base_token: vault_meta.base_token.clone(),
+            quote_token: vault_meta.quote_token.clone(),
+            base_decimals: vault_meta.base_decimals,
             last_price: 0,
+            order_auth: OrderAuthRegistry::new(),
         }
     }
 
-    /// Submit a new order to the matcher. Returns Raft proposal if matched.
+    /// Submit a new order to the matcher.
+    /// Vault rules and activations come from `registry` at `epoch`;
+    /// `now` is unix seconds, used for order expiry.
     pub fn submit_order(
         &mut self,
         state: &mut VaultState,
         order: OrderInstruction,
-        vault_meta: &VaultMetadata,
-    ) -> Option<RaftProposal> {
-        // Phase 2 entry validation — must be ZK-verified
-        if !is_vault_active(&state.vault_id, &order.owner_hash) {
-            println!("[MATCH] Rejected: vault not ZK-activated for {}", &order.owner_hash);
-            return None;
+        registry: &VaultRegistry,
+        epoch: u64,
+        now: u64,
+    ) -> SubmitOutcome {
+        let pair = VaultPair(state.vault_id.clone());
+        let Some(vault_meta) = registry.get_metadata(&pair) else {
+            return SubmitOutcome::Rejected("Unknown vault");
+        };
+
+        // Phase 2 entry validation — must be ZK-verified and not lapsed
+        if !registry.is_vault_active(&pair, &order.owner_hash, epoch) {
+            println!("[MATCH] Rejected: vault not ZK-activated for {}", hex::encode(order.owner_hash));
+            return SubmitOutcome::Rejected("Vault not ZK-activated for identity");
+        }
+
+        // Order must be committed under the submitter's identity secret
+        if let Err(e) = self.order_auth.verify_order(&order, now) {
+            println!("[MATCH] Rejected: order authorization failed ({:?})", e);
+            return SubmitOutcome::Rejected("Order authorization failed");
+        }
+
+        // A resting order already holds this (owner, nonce); never overwrite it
+        if let Some(owner) = self.identities.get(&order.owner_hash) {
+            if self.nonce_index.contains_key(&(owner, order.nonce)) {
+                println!("[MATCH] Rejected: duplicate nonce {} for {}", order.nonce, hex::encode(order.owner_hash));
+                return SubmitOutcome::Rejected("Duplicate order nonce");
+            }
+        }
+
+        // Balance this order would lock if it rests
+        let reserve_amount = match reservation_amount(&order, self.base_decimals) {
+            Some(amount) => amount,
+            None => return SubmitOutcome::Rejected("Order notional overflows"),
+        };
+        {
+            let reserve_token = self.reserved_token(order.intent);
+            if state.available_balance(&order.owner_hash, reserve_token) < reserve_amount {
+                println!("[MATCH] Rejected: insufficient available {} for order", reserve_token);
+                return SubmitOutcome::Rejected("Insufficient available balance");
+            }
         }
 
-        // Skip logic: this node is responsible for matching
-        if let Some(counterparty) = self.match_order(&order) {
+        // Skip logic: this node is responsible for matching.
+        // The maker stays on the book until the trade succeeds, so a failed
+        // execution leaves its queue position and reservation untouched.
+        if let Some(counterparty) = self.peek_live_maker(state, order.intent, order.price, now) {
             let matched_price = order.price;
+            let counterparty_hash = *self.identities.resolve(counterparty.owner);
 
             let filled_order = OrderInstruction {
-                counterparty_hash: counterparty.owner_hash.clone(),
-                ..order.clone()
+                counterparty_hash,
+                ..order
             };
 
             //  Core vault execution
-            match execute_trade(state, filled_order.clone(), vault_meta, self.last_price) {
+            match execute_trade(state, filled_order, vault_meta) {
                 Ok(result) => {
+                    // Resting side is settled: take it off the book and unlock its funds
+                    self.arena.cancel(counterparty.id);
+                    self.nonce_index.remove(&(counterparty.owner, counterparty.nonce));
+                    self.release(state, &counterparty);
+
                     self.last_price = matched_price;
-                    Some(RaftProposal {
+                    SubmitOutcome::Matched(RaftProposal {
                         vault_id: state.vault_id.clone(),
                         trade: result,
                     })
                 }
                 Err(e) => {
                     println!("[MATCH] Trade execution failed: {}", e);
-                    None
+                    SubmitOutcome::Rejected(e)
                 }
             }
         } else {
-            // No match: queue as limit order
-            self.enqueue_order(order);
-            None
+            // No match: lock the funds and queue as limit order
+            let reserve_token = self.reserved_token(order.intent);
+            if let Err(e) = state.reserve_balance(&order.owner_hash, reserve_token, reserve_amount) {
+                println!("[MATCH] Rejected: {}", e);
+                return SubmitOutcome::Rejected(e);
+            }
+
+            let owner = self.identities.intern(&order.owner_hash);
+            let id = self.arena.insert(
+                owner,
+                order.intent,
+                order.price,
+                order.size,
+                order.nonce,
+                order.expiry,
+                reserve_amount,
+            );
+            self.nonce_index.insert((owner, order.nonce), id);
+            SubmitOutcome::Rested(id)
         }
     }
 
-    /// Match incoming order against opposite side of the book
-    fn match_order(&mut self, order: &OrderInstruction) -> Option<OrderInstruction> {
-        let side = order.intent.as_str();
-        let book = if side == "buy" {
-            &mut self.asks
-        } else {
-            &mut self.bids
-        };
+    /// Cancel a resting order by ID and release its reservation. O(1).
+    pub fn cancel_order(&mut self, state: &mut VaultState, order_id: OrderId) -> Result<ArenaOrder, &'static str> {
+        let order = self.arena.cancel(order_id).ok_or("Order not found")?;
+        self.nonce_index.remove(&(order.owner, order.nonce));
+        self.release(state, &order);
+        Ok(order)
+    }
 
-        for (price, queue) in book.iter_mut() {
-            let acceptable = if side == "buy" {
-                order.price >= *price
-            } else {
-                order.price <= *price
-            };
+    /// Cancel a resting order by (owner, nonce), as clients know it
+    pub fn cancel_by_nonce(
+        &mut self,
+        state: &mut VaultState,
+        owner_hash: &PoseidonHash,
+        nonce: u64,
+    ) -> Result<ArenaOrder, &'static str> {
+        let owner = self.identities.get(owner_hash).ok_or("Order not found")?;
+        let order_id = *self.nonce_index.get(&(owner, nonce)).ok_or("Order not found")?;
+        self.cancel_order(state, order_id)
+    }
 
-            if acceptable && !queue.is_empty() {
-                let matched = queue.pop_front();
-                return matched;
-            }
+    /// Drop every resting order whose expiry is at or before `now`,
+    /// releasing their reservations. Returns the number of orders expired.
+    pub fn expire_orders(&mut self, state: &mut VaultState, now: u64) -> usize {
+        let expired = self.arena.expire(now);
+        for order in &expired {
+            self.nonce_index.remove(&(order.owner, order.nonce));
+            self.release(state, order);
         }
-        None
+        expired.len()
     }
 
-    /// Queue a limit order if not matched
-    fn enqueue_order(&mut self, order: OrderInstruction) {
-        let book = if order.intent == "buy" {
-            &mut self.bids
-        } else {
-            &mut self.asks
-        };
-        book.entry(order.price).or_default().push_back(order);
+    /// Look up a resting order
+    pub fn get_order(&self, order_id: OrderId) -> Option<&ArenaOrder> {
+        self.arena.get(order_id)
+    }
+
+    /// Number of resting orders
+    pub fn resting_count(&self) -> usize {
+        self.arena.len()
     }
 
-    /// Remove stale or empty price levels
-    pub fn prune_book(&mut self) {
-        self.bids.retain(|_, q| !q.is_empty());
-        self.asks.retain(|_, q| !q.is_empty());
+    /// Best bid / best ask
+    pub fn best_prices(&self) -> (Option<u64>, Option<u64>) {
+        (self.arena.best_bid(), self.arena.best_ask())
     }
 
     /// Sync last matched price externally
@@ -119,4 +214,50 @@ impl OrderBook {
     pub fn get_last_price(&self) -> u64 {
         self.last_price
     }
+
+    /// Best crossing maker that has not expired at `now`.
+    /// Expired makers met on the way are dropped and their funds released.
+    fn peek_live_maker(
+        &mut self,
+        state: &mut VaultState,
+        taker: OrderIntent,
+        limit_price: u64,
+        now: u64,
+    ) -> Option<ArenaOrder> {
+        while let Some(maker) = self.arena.peek_best(taker, limit_price) {
+            if maker.expiry > now {
+                return Some(maker);
+            }
+            self.arena.cancel(maker.id);
+            self.nonce_index.remove(&(maker.owner, maker.nonce));
+            self.release(state, &maker);
+        }
+        None
+    }
+
+    /// Token an order of this side locks: base for sells, quote for buys
+    fn reserved_token(&self, intent: OrderIntent) -> &str {
+        match intent {
+            OrderIntent::Sell => &self.base_token,
+            OrderIntent::Buy => &self.quote_token,
+        }
+    }
+
+    fn release(&self, state: &mut VaultState, order: &ArenaOrder) {
+        let owner_hash = *self.identities.resolve(order.owner);
+        state.release_balance(&owner_hash, self.reserved_token(order.intent), order.reserved_amount);
+    }
+}
+
+/// Amount an order locks while resting.
+/// Sells lock base size; buys lock quote notional (size × price), rounded up.
+pub fn reservation_amount(order: &OrderInstruction, base_decimals: u8) -> Option<u64> {
+    match order.intent {
+        OrderIntent::Sell => Some(order.size),
+        OrderIntent::Buy => {
+            let scale = 10u128.checked_pow(base_decimals as u32)?;
+            let notional = (order.size as u128 * order.price as u128).div_ceil(scale);
+            u64::try_from(notional).ok()
+        }
+    }
 }
@@ -0,0 +1,364 @@
+// ==============================================
+// order_intake.rs — Domex JSON Order Intake
+// ==============================================
+
+//! Parses and validates client orders submitted as JSON against
+//! `schema/order_format.json`, converting them into `OrderInstruction`s.
+//!
+//! It is responsible for:
+//! 1. Rejecting unknown or missing schema fields
+//! 2. Validating intent / order_type enums
+//! 3. Converting decimal size and price to fixed-point with vault decimals
+//! 4. Enforcing tick and lot size
+//! 5. Per-identity nonce replay protection
+//!
+//! Commitment verification is left to `order_auth`, run by the matcher.
+
+use crate::matching::core::types::order_intake::{RawOrder, OrderIntakeError, ORDER_FIELDS, REQUIRED_ORDER_FIELDS};
+use crate::matching::core::types::order_book::{OrderInstruction, OrderIntent, OrderType, PoseidonHash};
+use crate::matching::core::vault_registry::VaultMetadata;
+
+use std::collections::HashMap;
+
+/// Largest decimal shift we attempt before declaring overflow (10^20 > u64::MAX)
+const MAX_DECIMAL_SHIFT: i64 = 20;
+
+/// Order intake for a single vault gateway.
+/// Tracks the last accepted nonce per identity for replay protection.
+#[derive(Default)]
+pub struct OrderIntake {
+    last_nonce: HashMap<PoseidonHash, u64>,
+}
+
+impl OrderIntake {
+    pub fn new() -> Self {
+        Self {
+            last_nonce: HashMap::new(),
+        }
+    }
+
+    /// Parse, validate and convert a JSON order submitted by `owner_hash`.
+    /// The nonce is only consumed if the whole order is accepted.
+    pub fn parse_order(
+        &mut self,
+        json: &str,
+        owner_hash: PoseidonHash,
+        vault_id: &str,
+        vault_meta: &VaultMetadata,
+    ) -> Result<OrderInstruction, OrderIntakeError> {
+        let raw = parse_raw_order(json)?;
+        self.accept_raw_order(&raw, owner_hash, vault_id, vault_meta)
+    }
+
+    /// Validate an already-parsed raw order and record its nonce
+    pub fn accept_raw_order(
+        &mut self,
+        raw: &RawOrder,
+        owner_hash: PoseidonHash,
+        vault_id: &str,
+        vault_meta: &VaultMetadata,
+    ) -> Result<OrderInstruction, OrderIntakeError> {
+        let order = validate_raw_order(raw, owner_hash, vault_id, vault_meta)?;
+        self.check_nonce(&order.owner_hash, order.nonce)?;
+        self.last_nonce.insert(order.owner_hash, order.nonce);
+        Ok(order)
+    }
+
+    /// Ensure `nonce` is strictly greater than the last accepted nonce
+    pub fn check_nonce(&self, owner_hash: &PoseidonHash, nonce: u64) -> Result<(), OrderIntakeError> {
+        match self.last_nonce.get(owner_hash) {
+            Some(&last) if nonce <= last => Err(OrderIntakeError::ReplayedNonce {
+                nonce,
+                last_accepted: last,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Last accepted nonce for an identity, if any
+    pub fn last_nonce(&self, owner_hash: &PoseidonHash) -> Option<u64> {
+        self.last_nonce.get(owner_hash).copied()
+    }
+}
+
+/// Parses a JSON body into a `RawOrder`, rejecting unknown and missing fields
+pub fn parse_raw_order(json: &str) -> Result<RawOrder, OrderIntakeError> {
+    let value: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| OrderIntakeError::MalformedJson(e.to_string()))?;
+
+    let object = value
+        .as_object()
+        .ok_or_else(|| OrderIntakeError::MalformedJson("order must be a JSON object".into()))?;
+
+    if let Some(unknown) = object.keys().find(|k| !ORDER_FIELDS.contains(&k.as_str())) {
+        return Err(OrderIntakeError::UnknownField(unknown.clone()));
+    }
+
+    if let Some(missing) = REQUIRED_ORDER_FIELDS.iter().find(|f| !object.contains_key(**f)) {
+        return Err(OrderIntakeError::MissingField(missing));
+    }
+
+    serde_json::from_value(value).map_err(|e| OrderIntakeError::MalformedJson(e.to_string()))
+}
+
+/// Validates a raw order against vault rules and converts it to fixed-point.
+/// Does not touch replay state.
+pub fn validate_raw_order(
+    raw: &RawOrder,
+    owner_hash: PoseidonHash,
+    vault_id: &str,
+    vault_meta: &VaultMetadata,
+) -> Result<OrderInstruction, OrderIntakeError> {
+    if raw.vault_id != vault_id {
+        return Err(OrderIntakeError::VaultMismatch {
+            expected: vault_id.to_string(),
+            got: raw.vault_id.clone(),
+        });
+    }
+
+    if raw.token != vault_meta.base_token {
+        return Err(OrderIntakeError::TokenMismatch {
+            expected: vault_meta.base_token.clone(),
+            got: raw.token.clone(),
+        });
+    }
+
+    let intent = match raw.intent.as_str() {
+        "buy" => OrderIntent::Buy,
+        "sell" => OrderIntent::Sell,
+        other => return Err(OrderIntakeError::InvalidIntent(other.to_string())),
+    };
+
+    let order_type = match raw.order_type.as_str() {
+        "limit" => OrderType::Limit,
+        "market" => OrderType::Market,
+        other => return Err(OrderIntakeError::InvalidOrderType(other.to_string())),
+    };
+
+    let size = decimal_to_fixed("size", &raw.size, vault_meta.base_decimals)?;
+    let price = decimal_to_fixed("price", &raw.price, vault_meta.quote_decimals)?;
+
+    if vault_meta.tick_size > 0 && price % vault_meta.tick_size != 0 {
+        return Err(OrderIntakeError::PriceOffTick {
+            price,
+            tick_size: vault_meta.tick_size,
+        });
+    }
+
+    if vault_meta.lot_size > 0 && size % vault_meta.lot_size != 0 {
+        return Err(OrderIntakeError::SizeOffLot {
+            size,
+            lot_size: vault_meta.lot_size,
+        });
+    }
+
+    let nonce_text = raw.nonce.as_deref().ok_or(OrderIntakeError::MissingNonce)?;
+    let nonce = nonce_text
+        .parse::<u64>()
+        .map_err(|_| OrderIntakeError::InvalidNonce(nonce_text.to_string()))?;
+
+    let expiry = raw.expiry.ok_or(OrderIntakeError::MissingExpiry)?;
+
+    Ok(OrderInstruction {
+        vault_id: raw.vault_id.clone(),
+        token: raw.token.clone(),
+        intent,
+        order_type,
+        size,
+        price,
+        nonce,
+        expiry,
+        commitment: raw.vault_signature.clone(),
+        owner_hash,
+        counterparty_hash: PoseidonHash::default(),
+    })
+}
+
+/// Converts a JSON decimal (plain or exponent form) into a fixed-point u64
+/// with `decimals` fractional digits. Works on the number exactly as written
+/// (serde_json `arbitrary_precision`), so it never rounds: extra precision is
+/// an error.
+pub fn decimal_to_fixed(
+    field: &'static str,
+    number: &serde_json::Number,
+    decimals: u8,
+) -> Result<u64, OrderIntakeError> {
+    let text = number.to_string();
+    let invalid = || OrderIntakeError::InvalidDecimal {
+        field,
+        value: text.clone(),
+    };
+
+    if text.starts_with('-') {
+        return Err(OrderIntakeError::NonPositive { field });
+    }
+
+    // Split off exponent (clients may send e.g. "1e-8")
+    let (body, exponent) = match text.find(['e', 'E']) {
+        Some(pos) => {
+            let exp = text[pos + 1..].parse::<i64>().map_err(|_| invalid())?;
+            (&text[..pos], exp)
+        }
+        None => (text.as_str(), 0),
+    };
+
+    let (int_part, frac_part) = match body.split_once('.') {
+        Some((i, f)) => (i, f),
+        None => (body, ""),
+    };
+
+    if int_part.is_empty() && frac_part.is_empty() {
+        return Err(invalid());
+    }
+    if !int_part.bytes().chain(frac_part.bytes()).all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    let mut digits = format!("{}{}", int_part, frac_part);
+    if digits.bytes().all(|b| b == b'0') {
+        return Err(OrderIntakeError::NonPositive { field });
+    }
+
+    // Exponents come from the client, so the shift itself must not wrap
+    let shift = i64::try_from(frac_part.len())
+        .ok()
+        .and_then(|frac_len| exponent.checked_add(decimals as i64)?.checked_sub(frac_len))
+        .ok_or(OrderIntakeError::Overflow { field })?;
+    if shift < 0 {
+        let drop = usize::try_from(shift.unsigned_abs()).unwrap_or(usize::MAX);
+        if drop > digits.len() || digits[digits.len() - drop..].bytes().any(|b| b != b'0') {
+            return Err(OrderIntakeError::PrecisionExceeded {
+                field,
+                value: text.clone(),
+                decimals,
+            });
+        }
+        digits.truncate(digits.len() - drop);
+    } else {
+        if shift > MAX_DECIMAL_SHIFT {
+            return Err(OrderIntakeError::Overflow { field });
+        }
+        digits.extend(std::iter::repeat_n('0', shift as usize));
+    }
+
+    digits
+        .trim_start_matches('0')
+        .parse::<u64>()
+        .map_err(|_| OrderIntakeError::Overflow { field })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matching::core::vault_registry::VaultStatus;
+
+    fn meta() -> VaultMetadata {
+        VaultMetadata {
+            tick_size: 1,
+            lot_size: 1_000,
+            max_delta_bps: 200,
+            base_token: "dBTC".into(),
+            quote_token: "dUSDT".into(),
+            base_decimals: 8,
+            quote_decimals: 2,
+            liquidity_price: 6_000_000,
+            status: VaultStatus::Active,
+            activation_ttl_epochs: 10,
+            max_entry_share_bps: 200,
+        }
+    }
+
+    fn order_json(extra: &str) -> String {
+        format!(
+            r#"{{"vault_id":"vault-btc-usdt","token":"dBTC","intent":"buy","size":0.5,"price":60000.25,"order_type":"limit","nonce":"1","expiry":1900000000,"vault_signature":"00"{}}}"#,
+            extra
+        )
+    }
+
+    #[test]
+    fn test_parse_valid_order() {
+        let mut intake = OrderIntake::new();
+        let order = intake
+            .parse_order(&order_json(""), [1u8; 32], "vault-btc-usdt", &meta())
+            .unwrap();
+
+        assert_eq!(order.intent, OrderIntent::Buy);
+        assert_eq!(order.order_type, OrderType::Limit);
+        assert_eq!(order.size, 50_000_000);
+        assert_eq!(order.price, 6_000_025);
+        assert_eq!(intake.last_nonce(&[1u8; 32]), Some(1));
+    }
+
+    #[test]
+    fn test_rejects_unknown_field_and_replay() {
+        let mut intake = OrderIntake::new();
+        assert_eq!(
+            intake.parse_order(&order_json(r#","leverage":10"#), [1u8; 32], "vault-btc-usdt", &meta()),
+            Err(OrderIntakeError::UnknownField("leverage".into()))
+        );
+
+        intake.parse_order(&order_json(""), [1u8; 32], "vault-btc-usdt", &meta()).unwrap();
+        assert_eq!(
+            intake.parse_order(&order_json(""), [1u8; 32], "vault-btc-usdt", &meta()),
+            Err(OrderIntakeError::ReplayedNonce { nonce: 1, last_accepted: 1 })
+        );
+
+        // Same nonce from a different identity is fine
+        assert!(intake.parse_order(&order_json(""), [2u8; 32], "vault-btc-usdt", &meta()).is_ok());
+    }
+
+    #[test]
+    fn test_decimal_conversion() {
+        let n = |s: &str| serde_json::from_str::<serde_json::Number>(s).unwrap();
+
+        assert_eq!(decimal_to_fixed("size", &n("1e-8"), 8), Ok(1));
+        assert_eq!(decimal_to_fixed("size", &n("12"), 8), Ok(1_200_000_000));
+        assert_eq!(
+            decimal_to_fixed("price", &n("0.001"), 2),
+            Err(OrderIntakeError::PrecisionExceeded { field: "price", value: "0.001".into(), decimals: 2 })
+        );
+        assert_eq!(decimal_to_fixed("size", &n("0"), 8), Err(OrderIntakeError::NonPositive { field: "size" }));
+        assert_eq!(decimal_to_fixed("size", &n("-1"), 8), Err(OrderIntakeError::NonPositive { field: "size" }));
+
+        // More significant digits than an f64 holds: kept exactly, not rounded
+        assert_eq!(decimal_to_fixed("size", &n("123456789.12345678"), 8), Ok(12_345_678_912_345_678));
+        assert_eq!(decimal_to_fixed("size", &n("0.0000001"), 8), Ok(10));
+        assert_eq!(
+            decimal_to_fixed("price", &n("0.10000000000000000001"), 2),
+            Err(OrderIntakeError::PrecisionExceeded {
+                field: "price",
+                value: "0.10000000000000000001".into(),
+                decimals: 2
+            })
+        );
+
+        // Extreme exponents are rejected, never wrapped
+        assert_eq!(
+            decimal_to_fixed("size", &n("1e9223372036854775807"), 8),
+            Err(OrderIntakeError::Overflow { field: "size" })
+        );
+        assert_eq!(
+            decimal_to_fixed("size", &n("1e-9223372036854775808"), 8),
+            Err(OrderIntakeError::PrecisionExceeded {
+                field: "size",
+                value: "1e-9223372036854775808".into(),
+                decimals: 8
+            })
+        );
+        assert_eq!(
+            decimal_to_fixed("size", &n("1e99999999999999999999"), 8),
+            Err(OrderIntakeError::InvalidDecimal { field: "size", value: "1e+99999999999999999999".into() })
+        );
+    }
+
+    #[test]
+    fn test_size_must_be_whole_lots() {
+        let mut intake = OrderIntake::new();
+        // 0.500005 dBTC = 50_000_500 units, lot size 1_000
+        let json = order_json("").replace(r#""size":0.5"#, r#""size":0.500005"#);
+        assert_eq!(
+            intake.parse_order(&json, [1u8; 32], "vault-btc-usdt", &meta()),
+            Err(OrderIntakeError::SizeOffLot { size: 50_000_500, lot_size: 1_000 })
+        );
+    }
+}
@@ -2,12 +2,13 @@
 // poseidon_utils.rs : Domex Poseidon Hash Utilities (Plonky2 + Pasta Fp)
 // ===============================
 
+use pasta_curves::group::ff::PrimeField;
 use pasta_curves::Fp;
-use plonky2_poseidon::PoseidonHasher;
+use crate::common::poseidon_hasher::PoseidonHasher;
 
 /// Converts a 32-byte input to Pasta Fp (used as base field in Plonky2 circuits)
 pub fn bytes_to_fp(input: &[u8; 32]) -> Fp {
-    Fp::from_bytes(input).expect("Invalid bytes: not a valid Pasta field element")
+    Fp::from_repr(*input).expect("Invalid bytes: not a valid Pasta field element")
 }
 
 /// Converts a u64 (e.g., vault ID or token amount) to Pasta Fp
@@ -21,7 +22,37 @@ pub fn string_to_fp(input: &str) -> Fp {
     let bytes = input.as_bytes();
     let len = bytes.len().min(32);
     buf[..len].copy_from_slice(&bytes[..len]);
-    Fp::from_bytes(&buf).expect("Invalid Pasta field element from string")
+    Fp::from_repr(buf).expect("Invalid Pasta field element from string")
+}
+
+/// Limb-encoding tag for raw bytes (UTF-8 strings, binary hashes)
+pub const RAW_BYTES_TAG: u64 = 1;
+
+/// Limb-encoding tag for hex strings, hashed as their decoded bytes
+pub const HEX_BYTES_TAG: u64 = 2;
+
+/// Encodes arbitrary bytes as `tag || len || 8-byte little-endian limbs`.
+/// Every limb is a canonical Fp, so nothing is truncated and nothing panics;
+/// the length keeps concatenated encodings unambiguous.
+pub fn bytes_to_limbs(tag: u64, bytes: &[u8]) -> Vec<Fp> {
+    let mut limbs = Vec::with_capacity(2 + bytes.len().div_ceil(8));
+    limbs.push(u64_to_fp(tag));
+    limbs.push(u64_to_fp(bytes.len() as u64));
+    limbs.extend(bytes.chunks(8).map(|chunk| {
+        let mut limb = [0u8; 8];
+        limb[..chunk.len()].copy_from_slice(chunk);
+        u64_to_fp(u64::from_le_bytes(limb))
+    }));
+    limbs
+}
+
+/// Encodes an ID or hash string for hashing: hex (optionally `0x`-prefixed)
+/// as its decoded bytes, anything else as UTF-8. The tag keeps the two apart.
+pub fn string_to_limbs(input: &str) -> Vec<Fp> {
+    match hex::decode(input.trim_start_matches("0x")) {
+        Ok(bytes) if !input.is_empty() => bytes_to_limbs(HEX_BYTES_TAG, &bytes),
+        _ => bytes_to_limbs(RAW_BYTES_TAG, input.as_bytes()),
+    }
 }
 
 /// Computes Poseidon(sk || vault_id || zk_node_id) — identity hash for onboarding
@@ -36,7 +67,7 @@ pub fn recompute_delegation_hash(vault_id: &str, delegate_pubkey: &str) -> Strin
     let fp2 = string_to_fp(delegate_pubkey);
     let mut hasher = PoseidonHasher::new();
     let hash = hasher.hash(&[fp1, fp2]);
-    hex::encode(hash.to_bytes())
+    hex::encode(hash.to_repr())
 }
 
 /// Verifies that identity hash matches Poseidon(sk || vault_id || zk_node_id)
@@ -2,7 +2,7 @@
 // types/event_log.rs — Shared Trade Event Types
 // ===============================
 
-use crate::types::BalanceChange;
+use crate::matching::core::types::order_book::BalanceChange;
 
 /// Emitted after every successful trade
 #[derive(Debug, Clone)]
@@ -16,3 +16,12 @@ pub struct TradeEvent {
     pub balance_delta: Vec<BalanceChange>,
     pub timestamp: u64,
 }
+
+/// Fill published on the event bus by a vault matching actor
+#[derive(Debug, Clone)]
+pub struct FillEvent {
+    pub vault_id: String,
+    pub sequence: u64,        // Per-vault fill counter, gaps mean a lagging subscriber
+    pub trade: crate::matching::core::types::order_book::TradeResult,
+    pub timestamp: u64,
+}
@@ -5,8 +5,8 @@
 /// Unique vault identifier (usually UUID or vault Merkle root)
 pub type VaultId = String;
 
-/// Poseidon hash representing vault ownership identity (hex-encoded)
-pub type PoseidonHash = String;
+/// Poseidon identity hash, the same key type the order book and registry use
+pub use crate::matching::core::types::order_book::PoseidonHash;
 
 /// Poseidon hash representing delegated authority for trading or exit rights
 pub type DelegationHash = String;
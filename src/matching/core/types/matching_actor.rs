@@ -0,0 +1,51 @@
+// ================================================
+// types/matching_actor.rs — Vault Matching Actor Types
+// ================================================
+
+use crate::matching::core::order_arena::{ArenaOrder, OrderId};
+use crate::matching::core::types::order_book::PoseidonHash;
+
+use std::collections::HashMap;
+
+/// Errors returned to callers of a vault matching actor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActorError {
+    /// Command channel is full, caller should back off and retry
+    Backpressure,
+
+    /// Actor task has stopped
+    Closed,
+
+    /// Actor dropped the reply without answering
+    NoReply,
+}
+
+/// Point-in-time copy of a vault's book and balances
+#[derive(Debug, Clone)]
+pub struct BookSnapshot {
+    pub vault_id: String,
+    pub balances: HashMap<(PoseidonHash, String), u64>,
+    pub reserved: HashMap<(PoseidonHash, String), u64>,
+    pub best_bid: Option<u64>,
+    pub best_ask: Option<u64>,
+    pub resting_orders: usize,
+    pub last_price: u64,
+}
+
+/// Read-only lookups served by the actor
+#[derive(Debug, Clone)]
+pub enum BookQuery {
+    Order(OrderId),
+    BestPrices,
+    LastPrice,
+    AvailableBalance { identity: PoseidonHash, token: String },
+}
+
+/// Answer to a `BookQuery`
+#[derive(Debug, Clone)]
+pub enum QueryReply {
+    Order(Option<ArenaOrder>),
+    BestPrices { bid: Option<u64>, ask: Option<u64> },
+    LastPrice(u64),
+    AvailableBalance(u64),
+}
@@ -5,3 +5,6 @@ pub mod order_book;
 pub mod ownership;
 pub mod register;
 pub mod merkle;
+pub mod order_intake;
+pub mod matching_actor;
+pub mod unboarding_verifier;
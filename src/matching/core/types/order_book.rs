@@ -8,20 +8,41 @@ use std::collections::HashMap;
 pub type PoseidonHash = [u8; 32];
 
 /// Order intent type (buy or sell)  
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OrderIntent {
     Buy,
     Sell,
 }
 
+impl OrderIntent {
+    /// Schema string for this intent ("buy" / "sell")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderIntent::Buy => "buy",
+            OrderIntent::Sell => "sell",
+        }
+    }
+}
+
+/// Order type (limit is price-bound, market matches best available)  
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderType {
+    Limit,
+    Market,
+}
+
 /// A single order instruction submitted by a user  
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OrderInstruction {
     pub vault_id: String,
     pub token: String,
     pub intent: OrderIntent,
-    pub size: u64,
-    pub price: u64,
+    pub order_type: OrderType,
+    pub size: u64,                       // Fixed-point, base token decimals
+    pub price: u64,                      // Fixed-point, quote token decimals
+    pub nonce: u64,                      // Per-identity replay counter
+    pub expiry: u64,                     // Unix seconds after which the order is void
+    pub commitment: Option<String>,      // ORDER-domain Poseidon commitment (hex)
     pub owner_hash: PoseidonHash,
     pub counterparty_hash: PoseidonHash, // Filled by matching engine
 }
@@ -50,25 +71,84 @@ pub struct BalanceChange {
 #[derive(Debug)]
 pub struct VaultState {
     pub vault_id: String,
-    pub balances: HashMap<(PoseidonHash, String), u64>, // (identity, token) → balance
+    pub balances: HashMap<(PoseidonHash, String), u64>, // (identity, token) → total balance
+    pub reserved: HashMap<(PoseidonHash, String), u64>, // (identity, token) → locked by resting orders
 }
 
 impl VaultState {
+    pub fn new(vault_id: impl Into<String>) -> Self {
+        Self {
+            vault_id: vault_id.into(),
+            balances: HashMap::new(),
+            reserved: HashMap::new(),
+        }
+    }
+
+    /// Total balance, including any amount reserved by resting orders
     pub fn get_balance(&self, identity: &PoseidonHash, token: &str) -> u64 {
-        *self.balances.get(&(identity.clone(), token.to_string())).unwrap_or(&0)
+        *self.balances.get(&(*identity, token.to_string())).unwrap_or(&0)
+    }
+
+    /// Amount locked by the identity's resting orders
+    pub fn get_reserved(&self, identity: &PoseidonHash, token: &str) -> u64 {
+        *self.reserved.get(&(*identity, token.to_string())).unwrap_or(&0)
+    }
+
+    /// Balance free for new orders and trades (total minus reserved)
+    pub fn available_balance(&self, identity: &PoseidonHash, token: &str) -> u64 {
+        self.get_balance(identity, token)
+            .saturating_sub(self.get_reserved(identity, token))
+    }
+
+    /// Lock `amount` of available balance for a resting order
+    pub fn reserve_balance(&mut self, identity: &PoseidonHash, token: &str, amount: u64) -> Result<(), &'static str> {
+        if self.available_balance(identity, token) < amount {
+            return Err("Insufficient available balance to reserve");
+        }
+        *self.reserved.entry((*identity, token.to_string())).or_insert(0) += amount;
+        Ok(())
+    }
+
+    /// Unlock a previous reservation (fill, cancel or expiry)
+    pub fn release_balance(&mut self, identity: &PoseidonHash, token: &str, amount: u64) {
+        let key = (*identity, token.to_string());
+        if let Some(entry) = self.reserved.get_mut(&key) {
+            *entry = entry.saturating_sub(amount);
+            if *entry == 0 {
+                self.reserved.remove(&key);
+            }
+        }
     }
 
     pub fn decrease_balance(&mut self, identity: &PoseidonHash, token: &str, amount: u64) {
-        let key = (identity.clone(), token.to_string());
+        let key = (*identity, token.to_string());
         let entry = self.balances.entry(key).or_insert(0);
         *entry = entry.saturating_sub(amount);
     }
 
     pub fn increase_balance(&mut self, identity: &PoseidonHash, token: &str, amount: u64) {
-        let key = (identity.clone(), token.to_string());
+        let key = (*identity, token.to_string());
         let entry = self.balances.entry(key).or_insert(0);
         *entry += amount;
     }
+
+    /// Sum of all token balances held by an identity in this vault
+    pub fn total_balance_of(&self, identity: &PoseidonHash) -> u64 {
+        self.balances
+            .iter()
+            .filter(|((id, _), _)| id == identity)
+            .map(|(_, balance)| *balance)
+            .sum()
+    }
+
+    /// Total liquidity the vault holds in a token (sum over all identities)
+    pub fn total_liquidity(&self, token: &str) -> u64 {
+        self.balances
+            .iter()
+            .filter(|((_, t), _)| t == token)
+            .map(|(_, balance)| *balance)
+            .sum()
+    }
 }
 
 /// Proposal to be committed via Raft consensus  
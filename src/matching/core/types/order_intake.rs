@@ -0,0 +1,104 @@
+// ===============================================
+// types/order_intake.rs — JSON Order Intake Types
+// ===============================================
+
+use serde::{Serialize, Deserialize};
+
+/// Fields allowed by schema/order_format.json
+pub const ORDER_FIELDS: &[&str] = &[
+    "vault_id",
+    "token",
+    "intent",
+    "size",
+    "price",
+    "order_type",
+    "timestamp",
+    "nonce",
+    "expiry",
+    "vault_signature",
+];
+
+/// Fields the schema marks as required
+pub const REQUIRED_ORDER_FIELDS: &[&str] = &[
+    "vault_id",
+    "token",
+    "intent",
+    "size",
+    "price",
+    "order_type",
+    "nonce",
+    "expiry",
+    "vault_signature",
+];
+
+/// Raw order exactly as submitted against schema/order_format.json.
+/// Size and price stay as JSON numbers until converted with vault decimals.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RawOrder {
+    pub vault_id: String,
+    pub token: String,
+    pub intent: String,                  // "buy" | "sell"
+    pub size: serde_json::Number,        // Decimal token units
+    pub price: serde_json::Number,       // Decimal quote units per token
+    pub order_type: String,              // "limit" | "market"
+    pub timestamp: Option<u64>,          // Client-side issue time
+    pub nonce: Option<String>,           // Replay counter (decimal string)
+    pub expiry: Option<u64>,             // Unix seconds after which the order is void
+    pub vault_signature: Option<String>, // ORDER-domain Poseidon commitment (hex)
+}
+
+/// Typed rejection reasons for order intake
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderIntakeError {
+    /// Body is not valid JSON or a field has the wrong JSON type
+    MalformedJson(String),
+
+    /// Field not present in the order schema
+    UnknownField(String),
+
+    /// Required schema field is absent
+    MissingField(&'static str),
+
+    /// `intent` is not "buy" or "sell"
+    InvalidIntent(String),
+
+    /// `order_type` is not "limit" or "market"
+    InvalidOrderType(String),
+
+    /// Order addressed to a different vault than the one receiving it
+    VaultMismatch { expected: String, got: String },
+
+    /// Token is not the vault's base token
+    TokenMismatch { expected: String, got: String },
+
+    /// Size or price is not a well-formed decimal
+    InvalidDecimal { field: &'static str, value: String },
+
+    /// Size or price is zero or negative
+    NonPositive { field: &'static str },
+
+    /// More fractional digits than the vault's decimals allow
+    PrecisionExceeded { field: &'static str, value: String, decimals: u8 },
+
+    /// Fixed-point value does not fit in u64
+    Overflow { field: &'static str },
+
+    /// Price is not a multiple of the vault tick size
+    PriceOffTick { price: u64, tick_size: u64 },
+
+    /// Size is not a whole number of vault lots
+    SizeOffLot { size: u64, lot_size: u64 },
+
+    /// Order carries no nonce, so replay cannot be checked
+    MissingNonce,
+
+    /// Nonce is not a decimal u64
+    InvalidNonce(String),
+
+    /// Nonce not greater than the last accepted nonce for this identity
+    ReplayedNonce { nonce: u64, last_accepted: u64 },
+
+    /// Order carries no expiry, so its commitment cannot be bound
+    MissingExpiry,
+}
@@ -2,6 +2,7 @@
 // types/unboarding_verifier.rs : ZK Entry Proof for Vault Activation
 // ===================================================================
 
+use crate::matching::core::types::order_book::PoseidonHash;
 use serde::{Deserialize, Serialize};
 
 /// A zero-knowledge proof input submitted by the user
@@ -10,9 +11,12 @@ use serde::{Deserialize, Serialize};
 pub struct UserZkEntryProof {
     pub vault_id: String,        // Vault the user wants to enter (e.g., BTC/USDT)
     pub token: String,           // Token being onboarded (e.g., dBTC)
-    pub poseidon_hash: String,   // Identity hash bound to vault entry
+    pub poseidon_hash: PoseidonHash, // Identity hash bound to vault entry
     pub balance: u64,            // User's verified token balance
     pub total_liquidity: u64,    // Snapshot of vault liquidity at time of proof
-    pub merkle_leaf: String,     // ZK-verified leaf (Poseidon hash + balance)
-    pub merkle_path: Vec<String> // Merkle path to verify inclusion
+    pub order_auth_key: String,  // ORDER-domain auth key (hex Fp), bound into the leaf
+    pub merkle_leaf: String,     // ZK-verified leaf, `order_auth::entry_leaf` of the fields above
+    pub merkle_index: u64,       // Leaf position in the vault entry tree
+    pub merkle_leaf_count: u64,  // Leaves in that tree, fixes which levels have no sibling
+    pub merkle_path: Vec<String> // Hex sibling hashes bottom-up (promoted levels skipped)
 }
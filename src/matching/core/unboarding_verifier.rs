@@ -2,43 +2,141 @@
 // core/unboarding_verifier.rs : Domex Vault Entry Validation (Phase 2)
 // ===============================
 
-use crate::types::onboarding::UserZkEntryProof;
-use crate::identity::verify_poseidon_auth;
-use crate::zk::merkle::verify_merkle_proof;
-use crate::delta_checker::check_liquidity_delta;
-use crate::vault_registry::VaultMetadata;
+use crate::matching::core::types::unboarding_verifier::UserZkEntryProof;
+use crate::matching::zk::merkle::verify_merkle_proof;
+use crate::matching::zk::types::merkle::MerkleProof;
+use crate::matching::core::delta_checker::{check_liquidity_delta, LiquidityDeltaError};
+use crate::matching::core::vault_registry::{VaultRegistry, VaultPair, ActivationError};
+use crate::matching::core::order_auth::{auth_key_from_hex, entry_leaf, OrderAuthRegistry};
+use crate::common::merkle::hash_from_hex;
+use crate::validator::sparse_merkle::{balance_key, verify_non_inclusion, SparseMerkleTree};
+use crate::validator::types::sparse_merkle::SmtProof;
+use crate::matching::core::types::order_book::{PoseidonHash, VaultState};
 
 /// Verifies a user's ZK proof of ownership and liquidity-limited entry.
-/// Activates vault locally if successful.
+/// Activates vault locally if successful, anchored to `expected_merkle_root`,
+/// and registers the order auth key the proven entry leaf commits to.
+///
+/// Vault liquidity is read from `state`; `proof.total_liquidity` is not trusted.
+/// The entering balance is credited to `state`, so it counts towards the
+/// liquidity the next entrant is measured against.
 pub fn verify_user_entry(
     proof: &UserZkEntryProof,
-    vault_meta: &VaultMetadata,
+    state: &mut VaultState,
+    registry: &mut VaultRegistry,
+    order_auth: &mut OrderAuthRegistry,
     expected_merkle_root: &str,
-) -> Result<(), &'static str> {
-    // Step 1: Verify Poseidon identity matches vault ID
-    if !verify_poseidon_auth(&proof.poseidon_hash, &proof.vault_id) {
-        return Err("Invalid Poseidon identity for vault");
+    current_epoch: u64,
+) -> Result<(), String> {
+    let pair = VaultPair(proof.vault_id.clone());
+    let vault_meta = registry
+        .get_metadata(&pair)
+        .ok_or("Unknown vault")?;
+    let max_entry_share_bps = vault_meta.max_entry_share_bps;
+
+    if proof.vault_id != state.vault_id {
+        return Err("Entry proof is for another vault".into());
+    }
+
+    // Step 1: The entry leaf must bind identity, balance and order auth key
+    let owner_hash = proof.poseidon_hash;
+    let auth_key = auth_key_from_hex(&proof.order_auth_key).ok_or("Malformed order auth key")?;
+    let expected_leaf = entry_leaf(&owner_hash, &proof.token, proof.balance, auth_key);
+    if hash_from_hex(&proof.merkle_leaf) != Some(expected_leaf) {
+        return Err("Entry leaf does not bind identity, balance and order auth key".into());
     }
 
     // Step 2: Verify Merkle inclusion proof (ZK-proved balance)
-    let verified = verify_merkle_proof(
-        &proof.merkle_leaf,
-        &proof.merkle_path,
-        expected_merkle_root,
-    );
+    let inclusion = MerkleProof {
+        leaf: proof.merkle_leaf.clone(),
+        index: proof.merkle_index,
+        leaf_count: proof.merkle_leaf_count,
+        siblings: proof.merkle_path.clone(),
+        expected_root: expected_merkle_root.to_string(),
+    };
+    let verified = verify_merkle_proof(&proof.merkle_leaf, &inclusion, expected_merkle_root);
     if !verified {
-        return Err("Merkle proof verification failed");
+        return Err("Merkle proof verification failed".into());
     }
 
-    // Step 3: Enforce 2% Delta Law based on liquidity snapshot
-    if !check_liquidity_delta(proof.balance, proof.total_liquidity, vault_meta.max_delta_bps) {
-        return Err("Delta violation: balance exceeds 2% of vault liquidity");
+    // Step 3: Enforce the liquidity-share entry limit against current vault state
+    let total_liquidity = state.total_liquidity(&proof.token);
+    check_liquidity_delta(&proof.token, proof.balance, total_liquidity, max_entry_share_bps)
+        .map_err(|e| match e {
+            LiquidityDeltaError::ExceedsShare { token, balance, total_liquidity, allowed, max_share_bps } => format!(
+                "Delta violation: entering {} {} exceeds {} bps of vault liquidity {} (max {})",
+                balance, token, max_share_bps, total_liquidity, allowed
+            ),
+        })?;
+
+    // Step 4: Passed all checks, activate the vault for this user
+    let period = registry
+        .activate_vault(&pair, &owner_hash, expected_merkle_root, current_epoch)
+        .map_err(|e| match e {
+            ActivationError::AlreadyActive => "Vault already active for this identity".to_string(),
+            ActivationError::ProofRootReused => "Re-activation requires a fresh entry proof".to_string(),
+            ActivationError::VaultNotTradable => "Vault is not accepting entries".to_string(),
+            _ => "Vault activation failed".to_string(),
+        })?;
+
+    // Step 5: The proven balance enters the vault
+    state.increase_balance(&owner_hash, &proof.token, proof.balance);
+
+    // Step 6: Orders are accepted only under the key this entry proved
+    order_auth.register_auth_key(owner_hash, auth_key);
+
+    println!(
+        "[ENTRY] User {} approved to enter vault {} with {} {} (active until epoch {})",
+        hex::encode(owner_hash), proof.vault_id, proof.balance, proof.token, period.expires_epoch
+    );
+
+    Ok(())
+}
+
+/// Offboards a user from a vault once all of their balances are zero or withdrawn.
+///
+/// Zero balances are proven, not looked up: for each of the vault's base and
+/// quote tokens, `exit_proofs` must hold a sparse-tree non-inclusion proof of the
+/// identity's balance key under the root of `vault_tree`, the vault's committed
+/// balance tree.
+/// Closes the live activation period and drops the order auth key; re-entry
+/// requires a fresh entry proof.
+#[allow(clippy::too_many_arguments)]
+pub fn offboard_user_exit(
+    vault_id: &str,
+    vault_tree: &SparseMerkleTree,
+    exit_proofs: &[SmtProof],
+    registry: &mut VaultRegistry,
+    order_auth: &mut OrderAuthRegistry,
+    identity_hash: &PoseidonHash,
+    current_epoch: u64,
+) -> Result<(), &'static str> {
+    let pair = VaultPair(vault_id.to_string());
+    let meta = registry.get_metadata(&pair).ok_or("Unknown vault")?;
+    let balance_root = vault_tree.root();
+
+    let zero_everywhere = [&meta.base_token, &meta.quote_token].iter().all(|token| {
+        let key = balance_key(identity_hash, token);
+        exit_proofs
+            .iter()
+            .any(|proof| verify_non_inclusion(&balance_root, &key, proof))
+    });
+    if !zero_everywhere {
+        return Err("Cannot offboard: zero balance not proven for every vault token");
     }
 
-    //  Passed all checks: Vault can now be activated for this user
+    registry
+        .offboard_user(&pair, identity_hash, 0, current_epoch)
+        .map_err(|e| match e {
+            ActivationError::NotActive => "Vault not active for this identity",
+            _ => "Vault offboarding failed",
+        })?;
+
+    order_auth.remove_auth_key(identity_hash);
+
     println!(
-        "[ENTRY] User {:?} approved to enter vault {} with {} {}",
-        proof.poseidon_hash, proof.vault_id, proof.balance, proof.token
+        "[EXIT] User {} offboarded from vault {} at epoch {}",
+        hex::encode(identity_hash), vault_id, current_epoch
     );
 
     Ok(())
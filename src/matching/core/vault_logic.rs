@@ -8,18 +8,16 @@
 //! 1. Validating order instructions
 //! 2. Enforcing the 2% delta rule (vs global liquidity price)
 //! 3. Updating account balances
-//! 4. Verifying Poseidon-bound identity rights
-//! 5. Preparing Merkle deltas for Raft proposal
-//! 6. Emitting post-trade ownership transitions
+//! 4. Emitting the trade event and balance changes for the Raft proposal
+//!
+//! Identity rights are checked before this point: `OrderBook::submit_order`
+//! requires a live vault activation and a valid ORDER-domain authorization.
 
-use crate::vault_registry::VaultMetadata;
-use crate::identity::verify_poseidon_auth;
-use crate::ownership::transfer_ownership;
-use crate::delta_checker::check_price_delta;
-use crate::balance_snapshot::generate_balance_delta;
-use crate::event_log::emit_trade_event;
+use crate::matching::core::vault_registry::VaultMetadata;
+use crate::matching::core::delta_checker::check_price_delta;
+use crate::matching::core::event_log::emit_trade_event;
 
-use crate::types::{OrderInstruction, TradeResult, VaultState, BalanceChange};
+use crate::matching::core::types::order_book::{OrderInstruction, TradeResult, VaultState, BalanceChange};
 
 /// Executes a trade within a vault given a validated order instruction.
 pub fn execute_trade(
@@ -27,42 +25,32 @@ pub fn execute_trade(
     order: OrderInstruction,
     vault_meta: &VaultMetadata,
 ) -> Result<TradeResult, &'static str> {
-    // Step 1: Verify the Poseidon identity matches vault ownership
-    if !verify_poseidon_auth(&order.owner_hash, &state.vault_id) {
-        return Err("Invalid Poseidon identity for this vault");
-    }
-
-    // Step 2: Enforce the 2% delta rule based on global liquidity price
+    // Step 1: Enforce the 2% delta rule based on global liquidity price
     if !check_price_delta(order.price, vault_meta.liquidity_price, vault_meta.max_delta_bps) {
         return Err("Order violates global liquidity delta rule");
     }
 
-    // Step 3: Check for sufficient balance (basic pre-trade risk check)
-    let balance = state.get_balance(&order.owner_hash, &order.token);
+    // Step 2: Check for sufficient balance (basic pre-trade risk check).
+    // Balance locked by the owner's own resting orders is not spendable.
+    let balance = state.available_balance(&order.owner_hash, &order.token);
     if balance < order.size {
         return Err("Insufficient balance");
     }
 
-    // Step 4: Apply balance mutation (debit from seller, credit to buyer)
+    // Step 3: Apply balance mutation (debit from seller, credit to buyer)
     let balance_changes = apply_balance_mutation(state, &order)?;
 
-    // Step 5: Transfer ownership if trade is successful
-    transfer_ownership(state, &order.owner_hash, &order.counterparty_hash, &order.token, order.size);
-
-    // Step 6: Generate Merkle-compatible delta
-    let delta = generate_balance_delta(&balance_changes);
-
-    // Step 7: Emit trade event for Raft trace
-    emit_trade_event(&order, &delta);
+    // Step 4: Emit trade event for Raft trace
+    emit_trade_event(&order, &balance_changes);
 
     Ok(TradeResult {
         vault_id: state.vault_id.clone(),
         executed_price: order.price,
-        buyer: order.counterparty_hash.clone(),
-        seller: order.owner_hash.clone(),
+        buyer: order.counterparty_hash,
+        seller: order.owner_hash,
         token: order.token.clone(),
         size: order.size,
-        balance_delta: delta,
+        balance_delta: balance_changes,
     })
 }
 
@@ -86,12 +74,12 @@ fn apply_balance_mutation(
 
     Ok(vec![
         BalanceChange {
-            identity: from.clone(),
+            identity: *from,
             token: order.token.clone(),
             delta: -(order.size as i64),
         },
         BalanceChange {
-            identity: to.clone(),
+            identity: *to,
             token: order.token.clone(),
             delta: order.size as i64,
         },
@@ -1,25 +1,47 @@
 // ==========================================================
-// vault_raft_adapter.rs : Raft Commit Hook for Vault Trades
+// vault_raft_adapter.rs : Raft Commit Hooks for Vault Trades
 // ==========================================================
 
-use crate::vault_logic::execute_trade;
-use crate::zk::proof_dispatch::dispatch_zk_proof;
-use crate::types::{OrderInstruction, TradeResult, VaultState, VaultMetadata};
+use crate::matching::core::vault_logic::execute_trade;
+use crate::matching::core::vault_registry::VaultRegistry;
+use crate::matching::zk::proof_dispatch::ProofPipeline;
+use crate::matching::core::types::order_book::{OrderInstruction, TradeResult, VaultState};
+use crate::matching::core::vault_registry::VaultMetadata;
+use crate::validator::merkle_state::VaultMerkleCache;
 
-/// Called by Raft when a vault trade is committed (3-of-5 agreement)
+/// Called by Raft when a vault trade is committed (3-of-5 agreement).
+/// The batch's pre/post roots are read from `vault_tree` around the trade,
+/// so the proof chains exactly the balances this entry changed.
 pub fn apply_committed_trade(
     state: &mut VaultState,
+    vault_tree: &mut VaultMerkleCache,
     order: OrderInstruction,
     meta: &VaultMetadata,
-    last_price: u64,
+    pipeline: &mut ProofPipeline,
+    epoch: u64,
 ) -> Result<TradeResult, &'static str> {
     // Step 1: Execute trade on committed vault state
-    let result = execute_trade(state, order, meta, last_price)?;
+    let pre_root = vault_tree.root();
+    let result = execute_trade(state, order, meta)?;
 
-    // Step 2: Trigger ZK proof generation (only Raft leader will submit)
-    dispatch_zk_proof(result.clone());
+    // Step 2: Rehash only the balances the trade touched
+    let changed: Vec<_> = result
+        .balance_delta
+        .iter()
+        .map(|change| (change.identity, change.token.clone()))
+        .collect();
+    let post_root = vault_tree.apply_changes(state, &changed);
 
-    // Step 3: Log for audit trace
+    // Step 3: Batch the trade for ZK proving (only the Raft leader proves)
+    let total_liquidity = state.total_liquidity(&result.token);
+    pipeline
+        .dispatch(result.clone(), total_liquidity, epoch, &pre_root.0, &post_root.0)
+        .map_err(|e| {
+            println!("[RAFT] Trade rejected by proof batcher: {:?}", e);
+            "Trade rejected by proof batcher"
+        })?;
+
+    // Step 4: Log for audit trace
     println!(
         "[RAFT] Trade committed in vault {} @ price {}",
         result.vault_id,
@@ -28,3 +50,16 @@ pub fn apply_committed_trade(
 
     Ok(result)
 }
+
+/// Called by Raft when an epoch-boundary entry is committed.
+/// Lapses expired activations and proves every batch the finished epoch left open.
+pub fn apply_epoch_boundary(pipeline: &mut ProofPipeline, registry: &mut VaultRegistry, new_epoch: u64) {
+    let expired = registry.expire_activations(new_epoch);
+    pipeline.flush_epoch(new_epoch);
+
+    println!(
+        "[RAFT] Epoch {} committed: {} activations expired",
+        new_epoch,
+        expired.len()
+    );
+}
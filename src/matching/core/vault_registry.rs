@@ -2,6 +2,8 @@
 // vault_registry.rs — Domex Vault Metadata + Activation
 // =======================================================
 
+use crate::matching::core::types::order_book::PoseidonHash;
+
 use std::collections::HashMap;
 
 /// Unique identifier for a trading pair vault (e.g. "BTC/USDT")
@@ -20,19 +22,59 @@ pub enum VaultStatus {
 #[derive(Clone, Debug)]
 pub struct VaultMetadata {
     pub tick_size: u64,         // Minimum price increment (e.g. 100 = $1.00)
-    pub lot_size: u64,          // Minimum order size (e.g. 10_000 = 0.01 BTC)
+    pub lot_size: u64,          // Order size granularity (e.g. 10_000 = 0.01 BTC)
     pub max_delta_bps: u64,     // Max allowed deviation in BPS (200 = 2%)
     pub base_token: String,     // e.g. "BTC"
     pub quote_token: String,    // e.g. "USDT"
+    pub base_decimals: u8,      // Fixed-point decimals for order size (e.g. 8)
+    pub quote_decimals: u8,     // Fixed-point decimals for order price (e.g. 2)
     pub liquidity_price: u64,   // Global VWAP or oracle anchor
     pub status: VaultStatus,    // Active, Paused, Deprecated
+    pub activation_ttl_epochs: u64, // How long an entry proof keeps a user active
+    pub max_entry_share_bps: u64, // Max entering balance vs vault liquidity (200 = 2%)
+}
+
+/// Why an activation period was closed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeactivationReason {
+    Expired,    // Expiry epoch reached without a fresh proof
+    Offboarded, // User exited with zero / withdrawn balances
+    Revoked,    // Operator or governance action
+}
+
+/// One contiguous period during which an identity was active in a vault
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActivationPeriod {
+    pub proof_root: String,           // Merkle root the entry proof was verified against
+    pub activated_epoch: u64,         // Epoch the entry proof was accepted
+    pub expires_epoch: u64,           // First epoch the activation is no longer valid
+    pub deactivated_epoch: Option<u64>, // Set once the period is closed
+    pub reason: Option<DeactivationReason>,
+}
+
+impl ActivationPeriod {
+    /// True if the period is open and not past its expiry at `epoch`
+    pub fn is_live(&self, epoch: u64) -> bool {
+        self.deactivated_epoch.is_none() && epoch < self.expires_epoch
+    }
+}
+
+/// Activation lifecycle errors
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ActivationError {
+    UnknownVault,
+    VaultNotTradable,
+    AlreadyActive,
+    NotActive,
+    ProofRootReused,
+    OutstandingBalance(u64),
 }
 
 /// Holds vault-level configs + user activations
 #[derive(Default)]
 pub struct VaultRegistry {
     pub metadata_map: HashMap<VaultPair, VaultMetadata>,
-    pub activation_map: HashMap<(VaultPair, String), bool>, // (vault_id, user_hash) → active
+    pub activation_map: HashMap<(VaultPair, PoseidonHash), Vec<ActivationPeriod>>, // (vault_id, user_hash) → periods, oldest first
 }
 
 impl VaultRegistry {
@@ -49,18 +91,133 @@ impl VaultRegistry {
         self.metadata_map.insert(pair, metadata);
     }
 
-    /// Activate a vault for a user after ZK onboarding proof
-    pub fn activate_vault(&mut self, pair: &VaultPair, identity_hash: &str) {
+    /// Activate a vault for a user after a verified ZK entry proof.
+    ///
+    /// Opens a new activation period anchored to `proof_root` that expires
+    /// `activation_ttl_epochs` after `current_epoch`. A lapsed or offboarded
+    /// identity may re-activate, but only with a proof against a root it has
+    /// never used before.
+    pub fn activate_vault(
+        &mut self,
+        pair: &VaultPair,
+        identity_hash: &PoseidonHash,
+        proof_root: &str,
+        current_epoch: u64,
+    ) -> Result<&ActivationPeriod, ActivationError> {
+        let meta = self.metadata_map.get(pair).ok_or(ActivationError::UnknownVault)?;
+        if meta.status != VaultStatus::Active {
+            return Err(ActivationError::VaultNotTradable);
+        }
+        let ttl = meta.activation_ttl_epochs;
+
+        let periods = self
+            .activation_map
+            .entry((pair.clone(), *identity_hash))
+            .or_default();
+
+        // Every earlier root is stale, not just the last one (A -> B -> A)
+        if periods.iter().any(|p| p.proof_root == proof_root) {
+            return Err(ActivationError::ProofRootReused);
+        }
+
+        if let Some(last) = periods.last_mut() {
+            if last.is_live(current_epoch) {
+                return Err(ActivationError::AlreadyActive);
+            }
+            // Close a period that lapsed without an explicit sweep
+            if last.deactivated_epoch.is_none() {
+                last.deactivated_epoch = Some(last.expires_epoch);
+                last.reason = Some(DeactivationReason::Expired);
+            }
+        }
+
+        periods.push(ActivationPeriod {
+            proof_root: proof_root.to_string(),
+            activated_epoch: current_epoch,
+            expires_epoch: current_epoch.saturating_add(ttl),
+            deactivated_epoch: None,
+            reason: None,
+        });
+
+        Ok(periods.last().expect("period just pushed"))
+    }
+
+    /// Check if a vault is active for a given identity at `current_epoch`
+    pub fn is_vault_active(&self, pair: &VaultPair, identity_hash: &PoseidonHash, current_epoch: u64) -> bool {
+        self.current_activation(pair, identity_hash)
+            .map(|p| p.is_live(current_epoch))
+            .unwrap_or(false)
+    }
+
+    /// Most recent activation period for an identity, open or closed
+    pub fn current_activation(&self, pair: &VaultPair, identity_hash: &PoseidonHash) -> Option<&ActivationPeriod> {
         self.activation_map
-            .insert((pair.clone(), identity_hash.to_string()), true);
+            .get(&(pair.clone(), *identity_hash))
+            .and_then(|periods| periods.last())
     }
 
-    /// Check if a vault is active for a given identity
-    pub fn is_vault_active(&self, pair: &VaultPair, identity_hash: &str) -> bool {
+    /// Offboard a user from a vault.
+    ///
+    /// Only allowed once every balance the identity holds in the vault is zero
+    /// (traded out or withdrawn). `outstanding_balance` is the caller's sum of
+    /// those balances from the current vault state.
+    pub fn offboard_user(
+        &mut self,
+        pair: &VaultPair,
+        identity_hash: &PoseidonHash,
+        outstanding_balance: u64,
+        current_epoch: u64,
+    ) -> Result<(), ActivationError> {
+        if outstanding_balance > 0 {
+            return Err(ActivationError::OutstandingBalance(outstanding_balance));
+        }
+        self.deactivate_vault(pair, identity_hash, current_epoch, DeactivationReason::Offboarded)
+    }
+
+    /// Close the live activation period for an identity
+    pub fn deactivate_vault(
+        &mut self,
+        pair: &VaultPair,
+        identity_hash: &PoseidonHash,
+        current_epoch: u64,
+        reason: DeactivationReason,
+    ) -> Result<(), ActivationError> {
+        let period = self
+            .activation_map
+            .get_mut(&(pair.clone(), *identity_hash))
+            .and_then(|periods| periods.last_mut())
+            .filter(|p| p.is_live(current_epoch))
+            .ok_or(ActivationError::NotActive)?;
+
+        period.deactivated_epoch = Some(current_epoch);
+        period.reason = Some(reason);
+        Ok(())
+    }
+
+    /// Close every open period whose expiry epoch has passed.
+    /// Returns the (vault, identity) pairs that were expired.
+    pub fn expire_activations(&mut self, current_epoch: u64) -> Vec<(VaultPair, PoseidonHash)> {
+        let mut expired = Vec::new();
+
+        for (key, periods) in self.activation_map.iter_mut() {
+            if let Some(last) = periods.last_mut() {
+                if last.deactivated_epoch.is_none() && current_epoch >= last.expires_epoch {
+                    last.deactivated_epoch = Some(last.expires_epoch);
+                    last.reason = Some(DeactivationReason::Expired);
+                    expired.push(key.clone());
+                }
+            }
+        }
+
+        expired
+    }
+
+    /// Full activation history for an identity (compliance / audit trail)
+    pub fn activation_history(&self, pair: &VaultPair, identity_hash: &PoseidonHash) -> &[ActivationPeriod] {
         self.activation_map
-            .get(&(pair.clone(), identity_hash.to_string()))
-            .copied()
-            .unwrap_or(false)
+            .get(&(pair.clone(), *identity_hash))
+            .map(|periods| periods.as_slice())
+            .unwrap_or(&[])
     }
 
     /// Retrieve vault trading rules
@@ -80,3 +237,82 @@ impl VaultRegistry {
         self.metadata_map.get(pair).map(|meta| meta.liquidity_price)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: PoseidonHash = [1u8; 32];
+    const BOB: PoseidonHash = [2u8; 32];
+
+    fn registry_with_vault() -> (VaultRegistry, VaultPair) {
+        let pair = VaultPair("BTC/USDT".into());
+        let mut registry = VaultRegistry::new();
+        registry.register_vault(
+            pair.clone(),
+            VaultMetadata {
+                tick_size: 100,
+                lot_size: 10_000,
+                max_delta_bps: 200,
+                base_token: "BTC".into(),
+                quote_token: "USDT".into(),
+                base_decimals: 8,
+                quote_decimals: 2,
+                liquidity_price: 60_000,
+                status: VaultStatus::Active,
+                activation_ttl_epochs: 10,
+                max_entry_share_bps: 200,
+            },
+        );
+        (registry, pair)
+    }
+
+    #[test]
+    fn test_activation_expiry_and_reactivation() {
+        let (mut registry, pair) = registry_with_vault();
+
+        registry.activate_vault(&pair, &ALICE, "root_a", 5).unwrap();
+        assert!(registry.is_vault_active(&pair, &ALICE, 14));
+        assert!(!registry.is_vault_active(&pair, &ALICE, 15));
+
+        assert_eq!(registry.expire_activations(15).len(), 1);
+        assert_eq!(
+            registry.activate_vault(&pair, &ALICE, "root_a", 16).unwrap_err(),
+            ActivationError::ProofRootReused
+        );
+
+        registry.activate_vault(&pair, &ALICE, "root_b", 16).unwrap();
+        let history = registry.activation_history(&pair, &ALICE);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].deactivated_epoch, Some(15));
+        assert_eq!(history[0].reason, Some(DeactivationReason::Expired));
+
+        // A -> B -> A: the first root stays spent
+        registry.expire_activations(26);
+        assert_eq!(
+            registry.activate_vault(&pair, &ALICE, "root_a", 27).unwrap_err(),
+            ActivationError::ProofRootReused
+        );
+        let history = registry.activation_history(&pair, &ALICE);
+        assert_eq!(history[0].deactivated_epoch, Some(15));
+        assert_eq!(history[0].reason, Some(DeactivationReason::Expired));
+    }
+
+    #[test]
+    fn test_offboarding_requires_zero_balance() {
+        let (mut registry, pair) = registry_with_vault();
+        registry.activate_vault(&pair, &BOB, "root_a", 1).unwrap();
+
+        assert_eq!(
+            registry.offboard_user(&pair, &BOB, 500, 2),
+            Err(ActivationError::OutstandingBalance(500))
+        );
+
+        registry.offboard_user(&pair, &BOB, 0, 3).unwrap();
+        assert!(!registry.is_vault_active(&pair, &BOB, 3));
+        assert_eq!(
+            registry.current_activation(&pair, &BOB).unwrap().reason,
+            Some(DeactivationReason::Offboarded)
+        );
+    }
+}
@@ -2,10 +2,15 @@
 // zk/merkle.rs — Domex Merkle Tree Verification using Ponkey2 + Pasta Poseidon
 // ===============================================================
 
+//! Hex-facing wrappers over the canonical tree in `common/merkle.rs`.
+
+use pasta_curves::group::ff::PrimeField;
 use pasta_curves::Fp;
-use ponkey2_poseidon::PoseidonHasher;
 use serde::{Deserialize, Serialize};
 
+use crate::common::merkle::{hash_from_hex, verify_path, MerklePath};
+use crate::matching::zk::types::merkle::MerkleProof;
+
 /// Merkle root delta (used in ZK circuit context)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MerkleDelta {
@@ -14,45 +19,27 @@ pub struct MerkleDelta {
     pub affected_leaf: String,
 }
 
-/// Verifies that a given leaf belongs to a Merkle root using a Poseidon-based Merkle proof.
-/// All inputs are hex strings encoded from Pasta Fp field elements.
-pub fn verify_merkle_proof(
-    leaf_hex: &str,
-    root_hex: &str,
-    path: &[String],
-    mut index: usize,
-) -> bool {
-    let mut current = hex_to_fp(leaf_hex);
-
-    for sibling_hex in path {
-        let sibling = hex_to_fp(sibling_hex);
-        let combined = if index % 2 == 0 {
-            hash_pair(current, sibling)
-        } else {
-            hash_pair(sibling, current)
-        };
-        current = combined;
-        index /= 2;
-    }
-
-    current == hex_to_fp(root_hex)
-}
-
-/// Hashes two Pasta Fp values using Poseidon (Ponkey2-compatible)
-fn hash_pair(left: Fp, right: Fp) -> Fp {
-    let mut hasher = PoseidonHasher::new();
-    hasher.hash(&[left, right])
-}
-
-/// Converts a hex string into a Pasta field element (Fp)
-fn hex_to_fp(hex_str: &str) -> Fp {
-    let cleaned = hex_str.trim_start_matches("0x");
-    let mut bytes = [0u8; 32];
-    hex::decode_to_slice(cleaned, &mut bytes[..]).expect("Invalid hex input");
-    Fp::from_bytes(&bytes).expect("Invalid Pasta field element")
+/// Verifies that a given leaf hash belongs to a Merkle root.
+/// Leaf, siblings and root are hex-encoded canonical Merkle hashes.
+pub fn verify_merkle_proof(leaf_hex: &str, proof: &MerkleProof, root_hex: &str) -> bool {
+    let (Some(leaf), Some(root)) = (hash_from_hex(leaf_hex), hash_from_hex(root_hex)) else {
+        return false;
+    };
+
+    let siblings: Option<Vec<_>> = proof.siblings.iter().map(|s| hash_from_hex(s)).collect();
+    let Some(siblings) = siblings else {
+        return false;
+    };
+
+    let path = MerklePath {
+        index: proof.index,
+        leaf_count: proof.leaf_count,
+        siblings,
+    };
+    verify_path(&leaf, &path, &root)
 }
 
 /// Converts Fp field element into hex string
 pub fn fp_to_hex(fp: &Fp) -> String {
-    hex::encode(fp.to_bytes())
+    hex::encode(fp.to_repr())
 }
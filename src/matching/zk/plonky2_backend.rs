@@ -0,0 +1,381 @@
+// ================================================
+// zk/plonky2_backend.rs — Plonky2 Proving Backend (feature = "plonky2")
+// ================================================
+
+//! Real prover for the trade circuits over Goldilocks + Poseidon.
+//!
+//! Each circuit takes a fixed-width vector of public inputs (amounts and
+//! Poseidon digests of the string fields), hashes the vector in-circuit and
+//! exposes the digest as extra public inputs. Circuits are built once per
+//! backend and reused for every proof.
+//!
+//! The trade circuit constrains `proof_backend::check_trade_relations`:
+//! - price, size, liquidity and delta magnitudes are range-checked to 60 bits
+//! - price and size are non-zero (they have inverses); liquidity − size is in range
+//! - each delta is a boolean sign plus magnitude; per token, the signed deltas
+//!   sum to zero (tokens compared by digest)
+//! - signed deltas of (traded token, buyer) sum to `size`, of
+//!   (traded token, seller) to −`size`, so buyer and seller differ
+//!
+//! The batch circuit binds (vault, epoch, pre/post root, trade count) and a
+//! digest over every trade's encoded inputs; each trade's relations are
+//! proven by its own trade proof.
+
+#![cfg(feature = "plonky2")]
+
+use plonky2::field::types::{Field, PrimeField64};
+use plonky2::hash::poseidon::PoseidonHash;
+use plonky2::iop::target::{BoolTarget, Target};
+use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::{CircuitConfig, CircuitData};
+use plonky2::plonk::config::{GenericConfig, Hasher, PoseidonGoldilocksConfig};
+use plonky2::plonk::proof::ProofWithPublicInputs;
+
+use crate::matching::zk::types::proof_input::ZkProofInput;
+use crate::matching::zk::types::proof_backend::{CircuitId, ProofBackendError, ZkProof};
+use crate::matching::zk::types::proof_batcher::ZkBatchProofInput;
+use crate::matching::zk::proof_backend::{check_trade_relations, ProofBackend, MAX_CIRCUIT_AMOUNT};
+
+const D: usize = 2;
+type C = PoseidonGoldilocksConfig;
+type F = <C as GenericConfig<D>>::F;
+
+/// Balance changes per trade the circuit has room for (buyer/seller × base/quote)
+pub const MAX_TRADE_DELTAS: usize = 4;
+
+/// Field elements in a Poseidon digest
+const DIGEST_WIDTH: usize = 4;
+
+/// Bits in a range-checked amount (`MAX_CIRCUIT_AMOUNT`)
+const AMOUNT_BITS: usize = 60;
+
+/// Trade input layout: circuit tag, vault_id, token, buyer, seller digests;
+/// price, size, liquidity; delta count; per delta identity + token digests,
+/// sign bit and magnitude
+const TOKEN_AT: usize = 2 * DIGEST_WIDTH;
+const BUYER_AT: usize = 3 * DIGEST_WIDTH;
+const SELLER_AT: usize = 4 * DIGEST_WIDTH;
+const PRICE_AT: usize = 5 * DIGEST_WIDTH;
+const SIZE_AT: usize = PRICE_AT + 1;
+const LIQUIDITY_AT: usize = PRICE_AT + 2;
+const COUNT_AT: usize = PRICE_AT + 3;
+const DELTAS_AT: usize = PRICE_AT + 4;
+const DELTA_STRIDE: usize = 2 * DIGEST_WIDTH + 2;
+
+/// Width of the encoded trade inputs
+const INPUT_WIDTH: usize = DELTAS_AT + MAX_TRADE_DELTAS * DELTA_STRIDE;
+
+/// Width of the encoded batch inputs:
+/// circuit tag, vault_id digests; epoch limbs; pre/post root digests;
+/// trade count limbs; digest over every trade's encoded inputs
+const BATCH_INPUT_WIDTH: usize = 2 * DIGEST_WIDTH + 2 + 2 * DIGEST_WIDTH + 2 + DIGEST_WIDTH;
+
+/// u32 limb positions in the batch inputs (epoch, trade count)
+const BATCH_LIMBS: [usize; 4] = [8, 9, 18, 19];
+
+struct HashBindingCircuit {
+    data: CircuitData<F, C, D>,
+    inputs: Vec<Target>,
+    width: usize,
+}
+
+/// Plonky2 prover holding one built circuit per `CircuitId`
+pub struct Plonky2Backend {
+    trade: HashBindingCircuit,
+    batch: HashBindingCircuit,
+}
+
+impl Plonky2Backend {
+    pub fn new() -> Self {
+        Self {
+            trade: build_circuit(INPUT_WIDTH, constrain_trade),
+            batch: build_circuit(BATCH_INPUT_WIDTH, |builder, inputs| {
+                for &i in &BATCH_LIMBS {
+                    builder.range_check(inputs[i], 32);
+                }
+            }),
+        }
+    }
+
+    fn circuit(&self, circuit: CircuitId) -> &HashBindingCircuit {
+        match circuit {
+            CircuitId::TradeExecution => &self.trade,
+            CircuitId::TradeBatch => &self.batch,
+        }
+    }
+
+    fn check_backend(&self, proof: &ZkProof) -> Result<(), ProofBackendError> {
+        if proof.backend != self.name() {
+            return Err(ProofBackendError::BackendMismatch {
+                expected: self.name().to_string(),
+                got: proof.backend.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    fn prove_encoded(&self, circuit: CircuitId, encoded: &[F]) -> Result<ZkProof, ProofBackendError> {
+        let target = self.circuit(circuit);
+
+        let mut witness = PartialWitness::new();
+        for (t, value) in target.inputs.iter().zip(encoded) {
+            witness.set_target(*t, *value);
+        }
+
+        let proof = target
+            .data
+            .prove(witness)
+            .map_err(|e| ProofBackendError::ProvingFailed(e.to_string()))?;
+
+        Ok(ZkProof {
+            circuit,
+            backend: self.name().to_string(),
+            public_inputs_hash: digest_hex(&proof.public_inputs[target.width..]),
+            proof_bytes: proof.to_bytes(),
+        })
+    }
+
+    fn verify_encoded(&self, proof: &ZkProof, expected: &[F]) -> Result<bool, ProofBackendError> {
+        let target = self.circuit(proof.circuit);
+        let decoded = ProofWithPublicInputs::<F, C, D>::from_bytes(proof.proof_bytes.clone(), &target.data.common)
+            .map_err(|e| ProofBackendError::MalformedProof(e.to_string()))?;
+
+        if decoded.public_inputs[..target.width] != *expected
+            || digest_hex(&decoded.public_inputs[target.width..]) != proof.public_inputs_hash
+        {
+            return Ok(false);
+        }
+
+        Ok(target.data.verify(decoded).is_ok())
+    }
+}
+
+impl Default for Plonky2Backend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProofBackend for Plonky2Backend {
+    fn name(&self) -> &'static str {
+        "plonky2"
+    }
+
+    fn circuits(&self) -> &[CircuitId] {
+        &[CircuitId::TradeExecution, CircuitId::TradeBatch]
+    }
+
+    fn prove(&self, circuit: CircuitId, input: &ZkProofInput) -> Result<ZkProof, ProofBackendError> {
+        if circuit != CircuitId::TradeExecution {
+            return Err(ProofBackendError::UnsupportedCircuit(circuit));
+        }
+        // Same check the circuit enforces, for a readable error instead of a failed proof
+        check_trade_relations(input)?;
+        self.prove_encoded(circuit, &encode_public_inputs(circuit, input)?)
+    }
+
+    fn verify(&self, proof: &ZkProof, input: &ZkProofInput) -> Result<bool, ProofBackendError> {
+        self.check_backend(proof)?;
+        if proof.circuit != CircuitId::TradeExecution {
+            return Ok(false);
+        }
+        self.verify_encoded(proof, &encode_public_inputs(proof.circuit, input)?)
+    }
+
+    fn prove_batch(&self, input: &ZkBatchProofInput) -> Result<ZkProof, ProofBackendError> {
+        for trade in &input.trades {
+            check_trade_relations(trade)?;
+        }
+        self.prove_encoded(CircuitId::TradeBatch, &encode_batch_inputs(input)?)
+    }
+
+    fn verify_batch(&self, proof: &ZkProof, input: &ZkBatchProofInput) -> Result<bool, ProofBackendError> {
+        self.check_backend(proof)?;
+        if proof.circuit != CircuitId::TradeBatch {
+            return Ok(false);
+        }
+        self.verify_encoded(proof, &encode_batch_inputs(input)?)
+    }
+}
+
+/// Builds a fixed-width circuit: `constrain` adds the circuit's relations over
+/// the inputs, which are then hashed and exposed with their digest
+fn build_circuit(width: usize, constrain: impl FnOnce(&mut CircuitBuilder<F, D>, &[Target])) -> HashBindingCircuit {
+    let mut builder = CircuitBuilder::<F, D>::new(CircuitConfig::standard_recursion_config());
+
+    let inputs = builder.add_virtual_targets(width);
+    constrain(&mut builder, &inputs);
+
+    builder.register_public_inputs(&inputs);
+    let digest = builder.hash_n_to_hash_no_pad::<PoseidonHash>(inputs.clone());
+    builder.register_public_inputs(&digest.elements);
+
+    HashBindingCircuit {
+        data: builder.build::<C>(),
+        inputs,
+        width,
+    }
+}
+
+/// True iff two digests are equal element-wise
+fn digests_equal(builder: &mut CircuitBuilder<F, D>, a: &[Target], b: &[Target]) -> BoolTarget {
+    let mut all = builder._true();
+    for (x, y) in a.iter().zip(b) {
+        let eq = builder.is_equal(*x, *y);
+        all = builder.and(all, eq);
+    }
+    all
+}
+
+/// Trade relations, see the module docs
+fn constrain_trade(builder: &mut CircuitBuilder<F, D>, inputs: &[Target]) {
+    let digest = |at: usize| inputs[at..at + DIGEST_WIDTH].to_vec();
+    let (price, size, liquidity) = (inputs[PRICE_AT], inputs[SIZE_AT], inputs[LIQUIDITY_AT]);
+
+    for amount in [price, size, liquidity] {
+        builder.range_check(amount, AMOUNT_BITS);
+    }
+    builder.inverse(price);
+    builder.inverse(size);
+    let headroom = builder.sub(liquidity, size);
+    builder.range_check(headroom, AMOUNT_BITS);
+    builder.range_check(inputs[COUNT_AT], 32);
+
+    // Signed delta per slot: magnitude × (1 − 2·sign)
+    let mut slots = Vec::with_capacity(MAX_TRADE_DELTAS);
+    for slot in 0..MAX_TRADE_DELTAS {
+        let at = DELTAS_AT + slot * DELTA_STRIDE;
+        let sign = inputs[at + 2 * DIGEST_WIDTH];
+        let magnitude = inputs[at + 2 * DIGEST_WIDTH + 1];
+        builder.assert_bool(BoolTarget::new_unsafe(sign));
+        builder.range_check(magnitude, AMOUNT_BITS);
+
+        let signed_part = builder.mul(sign, magnitude);
+        let twice = builder.mul_const(F::TWO, signed_part);
+        let signed = builder.sub(magnitude, twice);
+        slots.push((digest(at), digest(at + DIGEST_WIDTH), signed));
+    }
+
+    // Conservation per token
+    for (_, token_i, _) in &slots {
+        let mut sum = builder.zero();
+        for (_, token_j, signed_j) in &slots {
+            let same = digests_equal(builder, token_i, token_j);
+            sum = builder.mul_add(same.target, *signed_j, sum);
+        }
+        builder.assert_zero(sum);
+    }
+
+    // Base leg: buyer gains `size`, seller loses it
+    let (token, buyer, seller) = (digest(TOKEN_AT), digest(BUYER_AT), digest(SELLER_AT));
+    let mut bought = builder.zero();
+    let mut sold = builder.zero();
+    for (identity, token_j, signed_j) in &slots {
+        let traded = digests_equal(builder, token_j, &token);
+        let is_buyer = digests_equal(builder, identity, &buyer);
+        let is_seller = digests_equal(builder, identity, &seller);
+
+        let to_buyer = builder.and(traded, is_buyer);
+        bought = builder.mul_add(to_buyer.target, *signed_j, bought);
+        let from_seller = builder.and(traded, is_seller);
+        sold = builder.mul_add(from_seller.target, *signed_j, sold);
+    }
+    builder.connect(bought, size);
+    let lost = builder.neg(size);
+    builder.connect(sold, lost);
+}
+
+/// Encodes a proof input into the circuit's fixed-width public input vector
+fn encode_public_inputs(circuit: CircuitId, input: &ZkProofInput) -> Result<Vec<F>, ProofBackendError> {
+    if input.delta.len() > MAX_TRADE_DELTAS {
+        return Err(ProofBackendError::InvalidInput(format!(
+            "{} balance changes, circuit supports {}",
+            input.delta.len(),
+            MAX_TRADE_DELTAS
+        )));
+    }
+
+    let mut out = Vec::with_capacity(INPUT_WIDTH);
+    for text in [circuit.as_str(), input.vault_id.as_str(), input.token.as_str(), input.buyer.as_str(), input.seller.as_str()] {
+        out.extend(string_digest(text));
+    }
+    for value in [input.executed_price, input.size, input.total_liquidity] {
+        out.push(amount(value)?);
+    }
+    out.push(F::from_canonical_u64(input.delta.len() as u64));
+
+    for slot in 0..MAX_TRADE_DELTAS {
+        match input.delta.get(slot) {
+            Some(change) => {
+                out.extend(string_digest(&change.identity));
+                out.extend(string_digest(&change.token));
+                out.push(F::from_bool(change.delta < 0));
+                out.push(amount(change.delta.unsigned_abs())?);
+            }
+            None => out.extend(std::iter::repeat(F::ZERO).take(DELTA_STRIDE)),
+        }
+    }
+
+    debug_assert_eq!(out.len(), INPUT_WIDTH);
+    Ok(out)
+}
+
+/// An amount as one field element, within the circuit's range check
+fn amount(value: u64) -> Result<F, ProofBackendError> {
+    if value > MAX_CIRCUIT_AMOUNT {
+        return Err(ProofBackendError::InvalidInput(format!("amount {} exceeds circuit bound", value)));
+    }
+    Ok(F::from_canonical_u64(value))
+}
+
+/// Encodes a batch into the batch circuit's public input vector.
+/// Individual trades are folded into one digest over their encoded inputs.
+fn encode_batch_inputs(input: &ZkBatchProofInput) -> Result<Vec<F>, ProofBackendError> {
+    let mut trades = Vec::with_capacity(input.trades.len() * INPUT_WIDTH);
+    for trade in &input.trades {
+        trades.extend(encode_public_inputs(CircuitId::TradeExecution, trade)?);
+    }
+
+    let mut out = Vec::with_capacity(BATCH_INPUT_WIDTH);
+    out.extend(string_digest(CircuitId::TradeBatch.as_str()));
+    out.extend(string_digest(&input.vault_id));
+    out.extend(u64_limbs(input.epoch));
+    out.extend(string_digest(&input.pre_batch_root));
+    out.extend(string_digest(&input.post_batch_root));
+    out.extend(u64_limbs(input.trades.len() as u64));
+    out.extend(PoseidonHash::hash_no_pad(&trades).elements);
+
+    debug_assert_eq!(out.len(), BATCH_INPUT_WIDTH);
+    Ok(out)
+}
+
+/// Off-circuit Poseidon digest of a string, packed as u32 limbs
+fn string_digest(text: &str) -> [F; DIGEST_WIDTH] {
+    let limbs: Vec<F> = text
+        .as_bytes()
+        .chunks(4)
+        .map(|chunk| {
+            let mut word = [0u8; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            F::from_canonical_u32(u32::from_le_bytes(word))
+        })
+        .chain(std::iter::once(F::from_canonical_u64(text.len() as u64)))
+        .collect();
+    PoseidonHash::hash_no_pad(&limbs).elements
+}
+
+fn u64_limbs(value: u64) -> [F; 2] {
+    [
+        F::from_canonical_u32(value as u32),
+        F::from_canonical_u32((value >> 32) as u32),
+    ]
+}
+
+fn digest_hex(elements: &[F]) -> String {
+    let bytes: Vec<u8> = elements
+        .iter()
+        .flat_map(|e| e.to_canonical_u64().to_le_bytes())
+        .collect();
+    hex::encode(bytes)
+}
@@ -0,0 +1,348 @@
+// ================================================
+// zk/proof_backend.rs — Pluggable ZK Proving Backends
+// ================================================
+
+//! Proving sits behind the `ProofBackend` trait so the trade-proof pipeline
+//! does not depend on a particular prover.
+//!
+//! - `MockProofBackend` (tests, or cargo feature `mock-prover`) hashes the public
+//!   inputs with Poseidon. It is deterministic and proves nothing, but lets the
+//!   pipeline run end to end in CI. It refuses trades that break
+//!   `check_trade_relations`, like a real prover would. Validator intake rejects
+//!   its proofs outside tests.
+//! - `Plonky2Backend` (cargo feature `plonky2`) proves those relations in-circuit,
+//!   see `zk/plonky2_backend.rs`.
+
+#[cfg(any(test, feature = "mock-prover"))]
+use pasta_curves::group::ff::PrimeField;
+use pasta_curves::Fp;
+use crate::common::poseidon_hasher::PoseidonHasher;
+
+use crate::matching::core::poseidon_utils::{string_to_limbs, u64_to_fp};
+use crate::matching::zk::types::proof_input::ZkProofInput;
+use crate::matching::zk::types::proof_backend::{CircuitId, ProofBackendError, ZkProof};
+use crate::matching::zk::types::proof_batcher::ZkBatchProofInput;
+
+/// Backend name carried by mock proofs
+pub const MOCK_BACKEND: &str = "mock";
+
+/// Domain tag for mock proofs, so they can never collide with a real commitment
+#[cfg(any(test, feature = "mock-prover"))]
+const MOCK_PROOF_DOMAIN: &str = "domex.mock.proof";
+
+/// Largest price, size, liquidity or balance change a trade circuit accepts (60 bits).
+/// Sums of a few such amounts stay far below the Goldilocks modulus, so the
+/// circuit's field equations on amounts hold over the integers.
+pub const MAX_CIRCUIT_AMOUNT: u64 = (1 << 60) - 1;
+
+/// Relations every provable trade satisfies:
+/// - price, size, liquidity and every |delta| are at most `MAX_CIRCUIT_AMOUNT`
+/// - price and size are non-zero, and size does not exceed vault liquidity
+/// - balance changes sum to zero per token
+/// - in the traded token the buyer gains exactly `size` and the seller loses it
+///
+/// Quote-side amounts are only held to conservation: the inputs carry no
+/// decimals to relate them to `executed_price`.
+pub fn check_trade_relations(input: &ZkProofInput) -> Result<(), ProofBackendError> {
+    let invalid = |reason: String| Err(ProofBackendError::InvalidInput(reason));
+
+    for (name, amount) in [("price", input.executed_price), ("size", input.size), ("liquidity", input.total_liquidity)] {
+        if amount > MAX_CIRCUIT_AMOUNT {
+            return invalid(format!("{} {} exceeds circuit bound", name, amount));
+        }
+    }
+    if input.executed_price == 0 || input.size == 0 {
+        return invalid("price and size must be non-zero".into());
+    }
+    if input.size > input.total_liquidity {
+        return invalid(format!("size {} exceeds vault liquidity {}", input.size, input.total_liquidity));
+    }
+
+    let mut per_token: std::collections::BTreeMap<&str, i128> = std::collections::BTreeMap::new();
+    let (mut bought, mut sold) = (0i128, 0i128);
+    for change in &input.delta {
+        if change.delta.unsigned_abs() > MAX_CIRCUIT_AMOUNT {
+            return invalid(format!("balance change {} exceeds circuit bound", change.delta));
+        }
+        *per_token.entry(change.token.as_str()).or_insert(0) += change.delta as i128;
+        if change.token == input.token && change.identity == input.buyer {
+            bought += change.delta as i128;
+        }
+        if change.token == input.token && change.identity == input.seller {
+            sold += change.delta as i128;
+        }
+    }
+    if let Some((token, sum)) = per_token.iter().find(|(_, sum)| **sum != 0) {
+        return invalid(format!("{} balance changes sum to {}, not zero", token, sum));
+    }
+    if bought != input.size as i128 || sold != -(input.size as i128) {
+        return invalid(format!("buyer gains {} and seller loses {}, trade size is {}", bought, -sold, input.size));
+    }
+    Ok(())
+}
+
+/// A prover/verifier for one or more Domex circuits
+pub trait ProofBackend: Send + Sync {
+    /// Short backend name, recorded in every proof it produces
+    fn name(&self) -> &'static str;
+
+    /// Circuits this backend can prove
+    fn circuits(&self) -> &[CircuitId];
+
+    /// Prove `input` under `circuit`
+    fn prove(&self, circuit: CircuitId, input: &ZkProofInput) -> Result<ZkProof, ProofBackendError>;
+
+    /// Check `proof` against the expected public inputs.
+    /// `Ok(false)` means a well-formed proof that does not verify.
+    fn verify(&self, proof: &ZkProof, input: &ZkProofInput) -> Result<bool, ProofBackendError>;
+
+    /// Prove a whole epoch batch under `CircuitId::TradeBatch`
+    fn prove_batch(&self, input: &ZkBatchProofInput) -> Result<ZkProof, ProofBackendError>;
+
+    /// Check a batch proof against the expected batch
+    fn verify_batch(&self, proof: &ZkProof, input: &ZkBatchProofInput) -> Result<bool, ProofBackendError>;
+
+    fn supports(&self, circuit: CircuitId) -> bool {
+        self.circuits().contains(&circuit)
+    }
+}
+
+/// Poseidon commitment to a circuit's public inputs:
+/// Poseidon(circuit || vault_id || token || price || size || liquidity || buyer || seller || deltas...)
+/// Strings are hashed as length-prefixed limbs, so long IDs are never truncated.
+pub fn public_inputs_commitment(circuit: CircuitId, input: &ZkProofInput) -> Fp {
+    let mut elements = string_to_limbs(circuit.as_str());
+    elements.extend(string_to_limbs(&input.vault_id));
+    elements.extend(string_to_limbs(&input.token));
+    elements.extend([
+        u64_to_fp(input.executed_price),
+        u64_to_fp(input.size),
+        u64_to_fp(input.total_liquidity),
+    ]);
+    elements.extend(string_to_limbs(&input.buyer));
+    elements.extend(string_to_limbs(&input.seller));
+    elements.push(u64_to_fp(input.delta.len() as u64));
+    for change in &input.delta {
+        elements.extend(string_to_limbs(&change.identity));
+        elements.extend(string_to_limbs(&change.token));
+        elements.push(u64_to_fp(change.delta as u64)); // two's complement
+    }
+
+    let mut hasher = PoseidonHasher::new();
+    hasher.hash(&elements)
+}
+
+/// Poseidon commitment to a batch:
+/// Poseidon(TradeBatch || vault_id || epoch || pre_root || post_root || count || trade commitments...)
+/// Roots are hex, hashed as their decoded bytes.
+pub fn batch_inputs_commitment(input: &ZkBatchProofInput) -> Fp {
+    let mut elements = string_to_limbs(CircuitId::TradeBatch.as_str());
+    elements.extend(string_to_limbs(&input.vault_id));
+    elements.push(u64_to_fp(input.epoch));
+    elements.extend(string_to_limbs(&input.pre_batch_root));
+    elements.extend(string_to_limbs(&input.post_batch_root));
+    elements.push(u64_to_fp(input.trades.len() as u64));
+    elements.extend(
+        input
+            .trades
+            .iter()
+            .map(|trade| public_inputs_commitment(CircuitId::TradeExecution, trade)),
+    );
+
+    let mut hasher = PoseidonHasher::new();
+    hasher.hash(&elements)
+}
+
+/// Deterministic stand-in prover.
+/// proof = Poseidon(MOCK_DOMAIN || public_inputs_commitment)
+#[cfg(any(test, feature = "mock-prover"))]
+#[derive(Debug, Default, Clone)]
+pub struct MockProofBackend;
+
+#[cfg(any(test, feature = "mock-prover"))]
+impl MockProofBackend {
+    pub fn new() -> Self {
+        MockProofBackend
+    }
+
+    fn check_backend(&self, proof: &ZkProof) -> Result<(), ProofBackendError> {
+        if proof.backend != self.name() {
+            return Err(ProofBackendError::BackendMismatch {
+                expected: self.name().to_string(),
+                got: proof.backend.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    fn matches(proof: &ZkProof, commitment: Fp) -> bool {
+        proof.public_inputs_hash == hex::encode(commitment.to_repr())
+            && proof.proof_bytes == Self::mock_proof_bytes(commitment)
+    }
+
+    fn mock_proof_bytes(commitment: Fp) -> Vec<u8> {
+        let mut elements = string_to_limbs(MOCK_PROOF_DOMAIN);
+        elements.push(commitment);
+
+        let mut hasher = PoseidonHasher::new();
+        hasher.hash(&elements).to_repr().to_vec()
+    }
+}
+
+#[cfg(any(test, feature = "mock-prover"))]
+impl ProofBackend for MockProofBackend {
+    fn name(&self) -> &'static str {
+        MOCK_BACKEND
+    }
+
+    fn circuits(&self) -> &[CircuitId] {
+        &[CircuitId::TradeExecution, CircuitId::TradeBatch]
+    }
+
+    fn prove(&self, circuit: CircuitId, input: &ZkProofInput) -> Result<ZkProof, ProofBackendError> {
+        check_trade_relations(input)?;
+        let commitment = public_inputs_commitment(circuit, input);
+        Ok(ZkProof {
+            circuit,
+            backend: self.name().to_string(),
+            public_inputs_hash: hex::encode(commitment.to_repr()),
+            proof_bytes: Self::mock_proof_bytes(commitment),
+        })
+    }
+
+    fn verify(&self, proof: &ZkProof, input: &ZkProofInput) -> Result<bool, ProofBackendError> {
+        self.check_backend(proof)?;
+        let commitment = public_inputs_commitment(proof.circuit, input);
+        Ok(Self::matches(proof, commitment))
+    }
+
+    fn prove_batch(&self, input: &ZkBatchProofInput) -> Result<ZkProof, ProofBackendError> {
+        for trade in &input.trades {
+            check_trade_relations(trade)?;
+        }
+        let commitment = batch_inputs_commitment(input);
+        Ok(ZkProof {
+            circuit: CircuitId::TradeBatch,
+            backend: self.name().to_string(),
+            public_inputs_hash: hex::encode(commitment.to_repr()),
+            proof_bytes: Self::mock_proof_bytes(commitment),
+        })
+    }
+
+    fn verify_batch(&self, proof: &ZkProof, input: &ZkBatchProofInput) -> Result<bool, ProofBackendError> {
+        self.check_backend(proof)?;
+        Ok(proof.circuit == CircuitId::TradeBatch && Self::matches(proof, batch_inputs_commitment(input)))
+    }
+}
+
+/// Backend selected at build time: Plonky2 with the `plonky2` feature, else the
+/// mock in tests or with `mock-prover`. A build with neither has no prover.
+pub fn default_backend() -> Option<Box<dyn ProofBackend>> {
+    #[cfg(feature = "plonky2")]
+    {
+        Some(Box::new(crate::matching::zk::plonky2_backend::Plonky2Backend::new()))
+    }
+    #[cfg(all(not(feature = "plonky2"), any(test, feature = "mock-prover")))]
+    {
+        Some(Box::new(MockProofBackend::new()))
+    }
+    #[cfg(all(not(feature = "plonky2"), not(any(test, feature = "mock-prover"))))]
+    {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matching::zk::types::proof_input::BalanceChange;
+
+    fn input() -> ZkProofInput {
+        ZkProofInput {
+            vault_id: "vault-btc-usdt".into(),
+            token: "dBTC".into(),
+            executed_price: 6_000_000,
+            size: 50_000_000,
+            buyer: "buyer".into(),
+            seller: "seller".into(),
+            delta: vec![
+                BalanceChange { identity: "buyer".into(), token: "dBTC".into(), delta: 50_000_000 },
+                BalanceChange { identity: "seller".into(), token: "dBTC".into(), delta: -50_000_000 },
+            ],
+            total_liquidity: 1_000_000_000,
+        }
+    }
+
+    #[test]
+    fn test_mock_proof_is_deterministic_and_verifies() {
+        let backend = MockProofBackend::new();
+        let a = backend.prove(CircuitId::TradeExecution, &input()).unwrap();
+        let b = backend.prove(CircuitId::TradeExecution, &input()).unwrap();
+
+        assert_eq!(a, b);
+        assert_eq!(backend.verify(&a, &input()), Ok(true));
+    }
+
+    #[test]
+    fn test_mock_proof_rejects_other_inputs_and_circuits() {
+        let backend = MockProofBackend::new();
+        let proof = backend.prove(CircuitId::TradeExecution, &input()).unwrap();
+
+        let mut tampered = input();
+        tampered.size += 1;
+        assert_eq!(backend.verify(&proof, &tampered), Ok(false));
+
+        let batch = backend.prove(CircuitId::TradeBatch, &input()).unwrap();
+        assert_ne!(batch.public_inputs_hash, proof.public_inputs_hash);
+    }
+
+    #[test]
+    fn test_long_hex_ids_hash_without_truncation() {
+        // 64 hex chars with a high final byte used to panic in string_to_fp
+        let mut a = input();
+        a.buyer = "ab".repeat(32);
+        a.delta[0].identity = a.buyer.clone();
+        let mut b = a.clone();
+        b.buyer = format!("{}{}", "ab".repeat(32), "cd");
+        b.delta[0].identity = b.buyer.clone();
+
+        assert_ne!(
+            public_inputs_commitment(CircuitId::TradeExecution, &a),
+            public_inputs_commitment(CircuitId::TradeExecution, &b)
+        );
+    }
+
+    #[test]
+    fn test_batch_commitment_hashes_full_roots() {
+        let batch = |post: &str| ZkBatchProofInput {
+            vault_id: "vault-btc-usdt".into(),
+            epoch: 3,
+            pre_batch_root: "ff".repeat(32),
+            post_batch_root: post.to_string(),
+            trades: vec![input()],
+        };
+
+        // Roots differing only in the last byte used to collide (or panic)
+        let a = batch_inputs_commitment(&batch(&format!("{}00", "ee".repeat(31))));
+        let b = batch_inputs_commitment(&batch(&format!("{}01", "ee".repeat(31))));
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_prove_rejects_broken_trade_relations() {
+        let backend = MockProofBackend::new();
+
+        let mut unbalanced = input();
+        unbalanced.delta[1].delta += 1;
+        assert!(matches!(backend.prove(CircuitId::TradeExecution, &unbalanced), Err(ProofBackendError::InvalidInput(_))));
+
+        let mut short_fill = input();
+        short_fill.delta[0].delta -= 1;
+        short_fill.delta[1].delta += 1;
+        assert!(matches!(backend.prove(CircuitId::TradeExecution, &short_fill), Err(ProofBackendError::InvalidInput(_))));
+
+        let mut oversized = input();
+        oversized.total_liquidity = oversized.size - 1;
+        assert!(matches!(backend.prove(CircuitId::TradeExecution, &oversized), Err(ProofBackendError::InvalidInput(_))));
+    }
+}
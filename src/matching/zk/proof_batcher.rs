@@ -0,0 +1,191 @@
+// ================================================
+// zk/proof_batcher.rs — Per-Vault Epoch Batching of Trade Proofs
+// ================================================
+
+//! The Raft leader collects committed trades per vault per epoch and proves
+//! each batch once, instead of one proof per trade.
+//!
+//! - A batch opens on the first trade of a vault in an epoch and records the
+//!   vault's Merkle root before that trade
+//! - Each following trade must start from the previous trade's post-root
+//! - A batch is flushed when it reaches the cap (`MAX_TRADES_PER_BATCH` by
+//!   default) or when the epoch advances
+
+use crate::common::zk_constants::MAX_TRADES_PER_BATCH;
+use crate::matching::zk::types::proof_input::ZkProofInput;
+use crate::matching::zk::types::proof_batcher::{BatchError, BatchFlushReason, FlushedBatch, ZkBatchProofInput};
+
+use std::collections::HashMap;
+
+/// Open batches for every vault this leader proves
+pub struct ProofBatcher {
+    current_epoch: u64,
+    max_trades: usize,
+    open: HashMap<String, ZkBatchProofInput>, // vault_id → open batch
+}
+
+impl ProofBatcher {
+    pub fn new(current_epoch: u64) -> Self {
+        Self::with_cap(current_epoch, MAX_TRADES_PER_BATCH)
+    }
+
+    /// Batcher with a custom trade cap (clamped to 1..=MAX_TRADES_PER_BATCH)
+    pub fn with_cap(current_epoch: u64, max_trades: usize) -> Self {
+        Self {
+            current_epoch,
+            max_trades: max_trades.clamp(1, MAX_TRADES_PER_BATCH),
+            open: HashMap::new(),
+        }
+    }
+
+    pub fn current_epoch(&self) -> u64 {
+        self.current_epoch
+    }
+
+    /// Add a committed trade that moved the vault root from `pre_root` to `post_root`.
+    ///
+    /// A trade from a later epoch advances the batcher first. Returns every batch
+    /// closed by this call: batches of the finished epoch, and this vault's batch
+    /// if it hit the cap.
+    pub fn push_trade(
+        &mut self,
+        epoch: u64,
+        trade: ZkProofInput,
+        pre_root: &str,
+        post_root: &str,
+    ) -> Result<Vec<FlushedBatch>, BatchError> {
+        if epoch < self.current_epoch {
+            return Err(BatchError::StaleEpoch {
+                epoch,
+                current: self.current_epoch,
+            });
+        }
+
+        let mut flushed = if epoch > self.current_epoch {
+            self.advance_epoch(epoch)
+        } else {
+            Vec::new()
+        };
+
+        if let Some(batch) = self.open.get(&trade.vault_id) {
+            if batch.post_batch_root != pre_root {
+                return Err(BatchError::RootDiscontinuity {
+                    expected: batch.post_batch_root.clone(),
+                    got: pre_root.to_string(),
+                });
+            }
+        }
+
+        let vault_id = trade.vault_id.clone();
+        let batch = self
+            .open
+            .entry(vault_id.clone())
+            .or_insert_with(|| ZkBatchProofInput {
+                vault_id: vault_id.clone(),
+                epoch,
+                pre_batch_root: pre_root.to_string(),
+                post_batch_root: pre_root.to_string(),
+                trades: Vec::new(),
+            });
+        batch.trades.push(trade);
+        batch.post_batch_root = post_root.to_string();
+
+        if batch.trades.len() >= self.max_trades {
+            if let Some(full) = self.open.remove(&vault_id) {
+                println!(
+                    "[ZK BATCH] Vault {} hit cap of {} trades in epoch {}",
+                    vault_id, self.max_trades, epoch
+                );
+                flushed.push(FlushedBatch {
+                    input: full,
+                    reason: BatchFlushReason::CapReached,
+                });
+            }
+        }
+
+        Ok(flushed)
+    }
+
+    /// Close every open batch and move to `new_epoch`.
+    /// Batches are returned ordered by vault ID.
+    pub fn advance_epoch(&mut self, new_epoch: u64) -> Vec<FlushedBatch> {
+        if new_epoch <= self.current_epoch {
+            return Vec::new();
+        }
+        self.current_epoch = new_epoch;
+
+        let mut flushed: Vec<FlushedBatch> = self
+            .open
+            .drain()
+            .map(|(_, input)| FlushedBatch {
+                input,
+                reason: BatchFlushReason::EpochBoundary,
+            })
+            .collect();
+        flushed.sort_by(|a, b| a.input.vault_id.cmp(&b.input.vault_id));
+
+        println!("[ZK BATCH] Epoch {} started, flushed {} batches", new_epoch, flushed.len());
+        flushed
+    }
+
+    /// Number of trades waiting in a vault's open batch
+    pub fn pending_trades(&self, vault_id: &str) -> usize {
+        self.open.get(vault_id).map_or(0, |batch| batch.trades.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(vault_id: &str, size: u64) -> ZkProofInput {
+        ZkProofInput {
+            vault_id: vault_id.into(),
+            token: "dBTC".into(),
+            executed_price: 6_000_000,
+            size,
+            buyer: "buyer".into(),
+            seller: "seller".into(),
+            delta: Vec::new(),
+            total_liquidity: 1_000_000,
+        }
+    }
+
+    #[test]
+    fn test_flushes_on_cap_with_pre_and_post_roots() {
+        let mut batcher = ProofBatcher::with_cap(7, 2);
+
+        assert!(batcher.push_trade(7, trade("v1", 1), "r0", "r1").unwrap().is_empty());
+        let flushed = batcher.push_trade(7, trade("v1", 2), "r1", "r2").unwrap();
+
+        assert_eq!(flushed.len(), 1);
+        assert_eq!(flushed[0].reason, BatchFlushReason::CapReached);
+        assert_eq!(flushed[0].input.pre_batch_root, "r0");
+        assert_eq!(flushed[0].input.post_batch_root, "r2");
+        assert_eq!(flushed[0].input.trades.len(), 2);
+        assert_eq!(batcher.pending_trades("v1"), 0);
+    }
+
+    #[test]
+    fn test_flushes_on_epoch_boundary_and_checks_continuity() {
+        let mut batcher = ProofBatcher::new(1);
+        batcher.push_trade(1, trade("v1", 1), "a0", "a1").unwrap();
+        batcher.push_trade(1, trade("v2", 1), "b0", "b1").unwrap();
+
+        assert_eq!(
+            batcher.push_trade(1, trade("v1", 2), "zz", "a2"),
+            Err(BatchError::RootDiscontinuity { expected: "a1".into(), got: "zz".into() })
+        );
+
+        // First trade of epoch 2 closes both epoch-1 batches
+        let flushed = batcher.push_trade(2, trade("v1", 3), "a1", "a2").unwrap();
+        assert_eq!(flushed.len(), 2);
+        assert!(flushed.iter().all(|b| b.reason == BatchFlushReason::EpochBoundary && b.input.epoch == 1));
+        assert_eq!(batcher.pending_trades("v1"), 1);
+
+        assert_eq!(
+            batcher.push_trade(1, trade("v2", 1), "b1", "b2"),
+            Err(BatchError::StaleEpoch { epoch: 1, current: 2 })
+        );
+    }
+}
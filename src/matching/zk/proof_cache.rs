@@ -3,10 +3,10 @@
 // ===============================
 
 use std::fs::{OpenOptions, File};
-use std::io::{Write, BufReader};
+use std::io::{Write, BufRead, BufReader};
 use std::path::Path;
 use std::sync::Mutex;
-use crate::types::zk::ZkProofInput;
+use crate::matching::zk::types::proof_input::ZkProofInput;
 use serde_json;
 
 /// Local path for cached proof inputs (JSON lines)
@@ -45,7 +45,7 @@ pub fn load_cached_inputs() -> Vec<ZkProofInput> {
     if Path::new(CACHE_FILE).exists() {
         if let Ok(file) = File::open(CACHE_FILE) {
             let reader = BufReader::new(file);
-            for line in reader.lines().flatten() {
+            for line in reader.lines().map_while(Result::ok) {
                 if let Ok(input) = serde_json::from_str::<ZkProofInput>(&line) {
                     inputs.push(input);
                 }
@@ -2,24 +2,195 @@
 // zk/proof_dispatch.rs : Domex ZK Proof Trigger (Post-Raft)
 // ============================================================
 
-use crate::zk::proof_generator::generate_and_submit_proof;
-use crate::zk::proof_cache::store_local_proof;
-use crate::infra::raft_context::is_local_leader;
-use crate::types::TradeResult;
-
-/// Dispatches ZK proof after a trade is committed via Raft.
-/// Raft leader: generates and submits proof
-/// Follower: caches proof locally in case of failover
-pub fn dispatch_zk_proof(trade: TradeResult) {
-    if is_local_leader() {
-        println!("[ZKP] I am Raft leader — generating proof for vault {}", trade.vault_id);
-        if let Err(e) = generate_and_submit_proof(&trade) {
-            eprintln!("[ZKP] Error submitting proof: {}", e);
+//! Batching runs on the Raft apply path; it is cheap and deterministic.
+//! Proving and the blocking validator submit (with its retries) run on a
+//! worker thread fed closed batches over a channel, so a slow prover or an
+//! unreachable validator never stalls trade application.
+
+use crate::matching::zk::proof_generator::generate_and_submit_batch_proof;
+use crate::matching::zk::proof_cache::store_proof_input;
+use crate::matching::zk::proof_backend::ProofBackend;
+use crate::matching::zk::proof_batcher::ProofBatcher;
+use crate::matching::zk::validator_client::ValidatorClient;
+use crate::matching::zk::proof_input::build_proof_input;
+use crate::matching::zk::types::proof_batcher::{BatchError, FlushedBatch};
+use crate::matching::core::types::order_book::TradeResult;
+
+use std::sync::mpsc::{self, Sender};
+use std::thread::{self, JoinHandle};
+
+/// Leader-side proving context for one vault node: the epoch batcher on the
+/// apply path, and the worker that proves and submits what it closes
+pub struct ProofPipeline {
+    pub is_leader: bool, // Set from Raft leadership changes
+    pub batcher: ProofBatcher,
+    jobs: Sender<FlushedBatch>,
+    worker: JoinHandle<()>,
+}
+
+impl ProofPipeline {
+    /// Starts the proving worker, which owns `backend` and `client`
+    pub fn new(current_epoch: u64, backend: Box<dyn ProofBackend>, client: ValidatorClient) -> Self {
+        let (jobs, queue) = mpsc::channel::<FlushedBatch>();
+        let worker = thread::spawn(move || {
+            // Runs until the pipeline drops its sender
+            for batch in queue {
+                prove_batch(backend.as_ref(), &client, &batch);
+            }
+        });
+
+        Self {
+            is_leader: false,
+            batcher: ProofBatcher::new(current_epoch),
+            jobs,
+            worker,
+        }
+    }
+
+    /// `dispatch_zk_proof` with this pipeline's batcher, queueing closed batches for the worker
+    pub fn dispatch(
+        &mut self,
+        trade: TradeResult,
+        total_liquidity: u64,
+        epoch: u64,
+        pre_root: &str,
+        post_root: &str,
+    ) -> Result<(), BatchError> {
+        let flushed = dispatch_zk_proof(
+            self.is_leader,
+            &mut self.batcher,
+            trade,
+            total_liquidity,
+            epoch,
+            pre_root,
+            post_root,
+        )?;
+        self.enqueue(flushed);
+        Ok(())
+    }
+
+    /// Closes the finished epoch's batches and queues them for the worker
+    pub fn flush_epoch(&mut self, new_epoch: u64) {
+        let flushed = self.batcher.advance_epoch(new_epoch);
+        self.enqueue(flushed);
+    }
+
+    /// Stops taking batches and waits for the worker to prove everything queued
+    pub fn shutdown(self) {
+        drop(self.jobs);
+        if self.worker.join().is_err() {
+            eprintln!("[ZKP] Proving worker panicked");
         }
+    }
+
+    fn enqueue(&self, batches: Vec<FlushedBatch>) {
+        for batch in batches {
+            let vault_id = batch.input.vault_id.clone();
+            if self.jobs.send(batch).is_err() {
+                eprintln!("[ZKP] Proving worker gone — batch for vault {} dropped", vault_id);
+            }
+        }
+    }
+}
+
+/// Batches a trade after it is committed via Raft.
+/// Raft leader: adds the trade to the vault's epoch batch and returns any
+/// batches it closes, to be proven off the apply path.
+/// Follower: caches the proof input locally in case of failover.
+pub fn dispatch_zk_proof(
+    is_leader: bool,
+    batcher: &mut ProofBatcher,
+    trade: TradeResult,
+    total_liquidity: u64,
+    epoch: u64,
+    pre_root: &str,
+    post_root: &str,
+) -> Result<Vec<FlushedBatch>, BatchError> {
+    if is_leader {
+        println!("[ZKP] I am Raft leader — batching trade for vault {} (epoch {})", trade.vault_id, epoch);
+        let input = build_proof_input(&trade, total_liquidity);
+        batcher.push_trade(epoch, input, pre_root, post_root)
     } else {
         println!("[ZKP] Not Raft leader — caching backup proof for vault {}", trade.vault_id);
-        if let Err(e) = store_local_proof(&trade) {
-            eprintln!("[ZKP] Error caching proof: {}", e);
+        store_proof_input(&build_proof_input(&trade, total_liquidity));
+        Ok(Vec::new())
+    }
+}
+
+/// Proves one closed batch and submits it, retrying per the client's transport config
+fn prove_batch(backend: &dyn ProofBackend, client: &ValidatorClient, batch: &FlushedBatch) {
+    if let Err(e) = generate_and_submit_batch_proof(backend, client, &batch.input) {
+        eprintln!("[ZKP] Error submitting batch proof for vault {}: {}", batch.input.vault_id, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matching::zk::proof_backend::MockProofBackend;
+    use crate::matching::zk::types::validator_client::TransportConfig;
+    use crate::validator::proof_intake_server::{spawn_intake_server, stub_check, ProofIntake};
+    use crate::matching::core::types::order_book::BalanceChange;
+
+    use std::time::Duration;
+
+    fn trade(size: u64) -> TradeResult {
+        TradeResult {
+            vault_id: "vault-btc-usdt".into(),
+            executed_price: 6_000_000,
+            buyer: [1u8; 32],
+            seller: [2u8; 32],
+            token: "dBTC".into(),
+            size,
+            balance_delta: vec![
+                BalanceChange { identity: [2u8; 32], token: "dBTC".into(), delta: -(size as i64) },
+                BalanceChange { identity: [1u8; 32], token: "dBTC".into(), delta: size as i64 },
+            ],
         }
     }
+
+    fn pipeline(addr: &str) -> ProofPipeline {
+        let config = TransportConfig {
+            connect_timeout: Duration::from_millis(500),
+            request_timeout: Duration::from_millis(500),
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(40),
+        };
+        let client = ValidatorClient::new(addr, config).unwrap();
+        let mut pipeline = ProofPipeline::new(1, Box::new(MockProofBackend::new()), client);
+        pipeline.is_leader = true;
+        pipeline
+    }
+
+    #[test]
+    fn test_batch_error_is_returned() {
+        let intake = ProofIntake::with_check(stub_check);
+        let (addr, _server) = spawn_intake_server("127.0.0.1:0", intake).unwrap();
+        let mut pipeline = pipeline(&addr.to_string());
+
+        let (a, b, c) = ("aa".repeat(32), "bb".repeat(32), "cc".repeat(32));
+        assert_eq!(pipeline.dispatch(trade(10), 1_000, 1, &a, &b), Ok(()));
+        assert!(matches!(
+            pipeline.dispatch(trade(10), 1_000, 1, &c, &a),
+            Err(BatchError::RootDiscontinuity { .. })
+        ));
+        pipeline.shutdown();
+    }
+
+    #[test]
+    fn test_worker_proves_flushed_batches() {
+        let intake = ProofIntake::with_check(stub_check);
+        let (addr, _server) = spawn_intake_server("127.0.0.1:0", intake.clone()).unwrap();
+        let mut pipeline = pipeline(&addr.to_string());
+
+        let (a, b) = ("aa".repeat(32), "bb".repeat(32));
+        pipeline.dispatch(trade(10), 1_000, 1, &a, &b).unwrap();
+        pipeline.flush_epoch(2);
+        pipeline.shutdown();
+
+        let accepted = intake.take_accepted();
+        assert_eq!(accepted.len(), 1);
+        assert_eq!(accepted[0].proof.vault_id, "vault-btc-usdt");
+    }
 }
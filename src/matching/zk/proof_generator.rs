@@ -2,39 +2,86 @@
 // zk/proof_generator.rs — Domex ZK Proof Builder & Submitter (Ponkey2: Plonky2 + Poseidon)
 // ===============================
 
-use crate::types::TradeResult;
-use crate::zk::proof_input::build_proof_input;
-use crate::types::zk::ZkProofInput;
-use crate::zk::merkle::MerkleDelta;
-use crate::zk::plonky2_backend::generate_plonky2_proof;
+use crate::matching::core::types::order_book::TradeResult;
+use crate::matching::zk::proof_input::build_proof_input;
+use crate::matching::zk::types::proof_input::ZkProofInput;
+use crate::matching::zk::types::proof_backend::{CircuitId, ZkProof};
+use crate::matching::zk::types::proof_batcher::ZkBatchProofInput;
+use crate::matching::zk::proof_backend::ProofBackend;
+use crate::matching::zk::validator_client::{proof_request, ValidatorClient};
+use crate::common::proof_wire::{ProofAck, ProofSubmitRequest};
 
-use std::fs::File;
-use std::io::Write;
-
-/// Generates a Plonky2-based ZK proof for a finalized trade result.
-/// This is triggered after Raft consensus by a local node and submitted to global validators.
-pub fn generate_and_submit_proof(trade: &TradeResult) -> Result<(), &'static str> {
+/// Generates a trade-execution proof for a finalized trade result with `backend`.
+pub fn generate_trade_proof(
+    backend: &dyn ProofBackend,
+    trade: &TradeResult,
+    total_liquidity: u64,
+) -> Result<ZkProof, &'static str> {
     // Step 1: Build ZK-compatible input from TradeResult (Merkle delta, vault info, etc.)
-    let zk_input: ZkProofInput = build_proof_input(trade)?;
+    let zk_input: ZkProofInput = build_proof_input(trade, total_liquidity);
 
-    // Step 2: Generate ZK proof using Ponkey2 = Plonky2 circuit backend
-    let proof_bytes = generate_plonky2_proof(&zk_input)
-        .map_err(|_| "Failed to generate Plonky2 proof")?;
+    // Step 2: Prove the trade circuit (Plonky2 in production, mock in CI)
+    let proof = backend.prove(CircuitId::TradeExecution, &zk_input).map_err(|e| {
+        println!("[ZK] {} backend failed to prove trade: {:?}", backend.name(), e);
+        "Failed to generate trade proof"
+    })?;
+
+    println!(
+        "[ZK] {} proof for vault {} (inputs {})",
+        proof.backend, trade.vault_id, proof.public_inputs_hash
+    );
+    Ok(proof)
+}
 
-    // Step 3: Submit proof (demo: write to disk; production: network broadcast to validators)
-    submit_to_validator(&proof_bytes)?;
+/// Generates a proof for a finalized trade and hands it to validators.
+/// This is triggered after Raft consensus by a local node and submitted to global validators.
+pub fn generate_and_submit_proof(
+    backend: &dyn ProofBackend,
+    client: &ValidatorClient,
+    trade: &TradeResult,
+    total_liquidity: u64,
+) -> Result<ProofAck, &'static str> {
+    let proof = generate_trade_proof(backend, trade, total_liquidity)?;
 
-    Ok(())
+    // Step 3: Submit proof to the validator intake (retried under one idempotency key)
+    let request = proof_request(&proof, &trade.vault_id, &trade.token, trade.size, &hex::encode(trade.buyer));
+    submit_to_validator(client, &request)
 }
 
-/// Writes the ZK proof bytes to disk for testing (temporary stub)
-fn submit_to_validator(proof: &[u8]) -> Result<(), &'static str> {
-    let mut file = File::create("/tmp/domex_last_proof.bin")
-        .map_err(|_| "Failed to create proof file")?;
+/// Proves a closed epoch batch as one root transition and hands it to validators.
+pub fn generate_and_submit_batch_proof(
+    backend: &dyn ProofBackend,
+    client: &ValidatorClient,
+    batch: &ZkBatchProofInput,
+) -> Result<(ZkProof, ProofAck), &'static str> {
+    let proof = backend.prove_batch(batch).map_err(|e| {
+        println!("[ZK] {} backend failed to prove batch: {:?}", backend.name(), e);
+        "Failed to generate batch proof"
+    })?;
+
+    println!(
+        "[ZK] {} batch proof for vault {} epoch {} ({} trades, {} → {})",
+        proof.backend,
+        batch.vault_id,
+        batch.epoch,
+        batch.trades.len(),
+        batch.pre_batch_root,
+        batch.post_batch_root
+    );
 
-    file.write_all(proof)
-        .map_err(|_| "Failed to write proof bytes")?;
+    // A batch has no single owner: it is identified by its public-input commitment
+    let token = batch.trades.first().map(|t| t.token.as_str()).unwrap_or_default();
+    let size = batch.trades.iter().map(|t| t.size).sum();
+    let request = proof_request(&proof, &batch.vault_id, token, size, &proof.public_inputs_hash);
+
+    let ack = submit_to_validator(client, &request)?;
+    Ok((proof, ack))
+}
 
-    println!("[ZK]  Plonky2 proof written to disk ({} bytes)", proof.len());
-    Ok(())
+/// Sends a proof to the validator intake and returns its acknowledgement
+fn submit_to_validator(client: &ValidatorClient, request: &ProofSubmitRequest) -> Result<ProofAck, &'static str> {
+    client.submit(request).map_err(|e| {
+        println!("[ZK] Submission to validator {} failed: {:?}", client.addr(), e);
+        "Failed to submit proof to validator"
+    })
 }
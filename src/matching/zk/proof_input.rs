@@ -2,7 +2,8 @@
 // zk/proof_input.rs : Domex ZK Circuit Input Builder (with liquidity delta)
 // ===============================
 
-use crate::types::{TradeResult, ZkProofInput, BalanceChange};
+use crate::matching::core::types::order_book::TradeResult;
+use crate::matching::zk::types::proof_input::{BalanceChange, ZkProofInput};
 
 /// Builds the ZK proof input struct from a confirmed trade.
 pub fn build_proof_input(trade: &TradeResult, total_liquidity: u64) -> ZkProofInput {
@@ -11,9 +12,17 @@ pub fn build_proof_input(trade: &TradeResult, total_liquidity: u64) -> ZkProofIn
         token: trade.token.clone(),
         executed_price: trade.executed_price,
         size: trade.size,
-        buyer: trade.buyer.clone(),
-        seller: trade.seller.clone(),
-        delta: trade.balance_delta.clone(),
+        buyer: hex::encode(trade.buyer),
+        seller: hex::encode(trade.seller),
+        delta: trade
+            .balance_delta
+            .iter()
+            .map(|change| BalanceChange {
+                identity: hex::encode(change.identity),
+                token: change.token.clone(),
+                delta: change.delta,
+            })
+            .collect(),
         total_liquidity, //  liquidity context for delta compliance
     }
 }
@@ -17,24 +17,21 @@ pub struct MerkleDelta {
     pub leaf_hash: Option<String>,
 }
 
-/// A single step in a Merkle proof path, from leaf to root.
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct MerklePathStep {
-    /// Hash of the sibling node at this level
-    pub sibling_hash: String,
-
-    /// Whether the current node is on the left or right (true = left, false = right)
-    pub is_left: bool,
-}
-
-/// A full Merkle proof from leaf to root, used for ZK onboarding verification
+/// A full Merkle proof from leaf to root, used for ZK onboarding verification.
+/// Follows the canonical tree in `common/merkle.rs` (odd node promoted).
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MerkleProof {
     /// The leaf hash (e.g., Poseidon hash of vault entry)
     pub leaf: String,
 
-    /// Path of sibling hashes to compute the root
-    pub path: Vec<MerklePathStep>,
+    /// Leaf position in the tree
+    pub index: u64,
+
+    /// Number of leaves in the tree, fixes which levels have no sibling
+    pub leaf_count: u64,
+
+    /// Sibling hashes bottom-up (promoted levels skipped)
+    pub siblings: Vec<String>,
 
     /// Claimed Merkle root (should match global state root)
     pub expected_root: String,
@@ -0,0 +1,53 @@
+// ================================================
+// types/proof_backend.rs — ZK Proving Backend Types
+// ================================================
+
+use serde::{Serialize, Deserialize};
+
+/// Circuits a proving backend can be asked to prove
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CircuitId {
+    /// Single trade execution + liquidity delta (post-Raft)
+    TradeExecution,
+
+    /// Aggregated batch of trade proofs for one vault epoch
+    TradeBatch,
+}
+
+impl CircuitId {
+    /// Versioned label, also used as the circuit's domain tag
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CircuitId::TradeExecution => "domex.circuit.trade.v1",
+            CircuitId::TradeBatch => "domex.circuit.trade_batch.v1",
+        }
+    }
+}
+
+/// A proof as produced by a backend, tagged with the circuit it proves
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ZkProof {
+    pub circuit: CircuitId,
+    pub backend: String,             // Backend name, e.g. "mock" or "plonky2"
+    pub public_inputs_hash: String,  // Hex commitment to the public inputs
+    pub proof_bytes: Vec<u8>,
+}
+
+/// Errors raised by proving backends
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProofBackendError {
+    /// Backend has no circuit for this identifier
+    UnsupportedCircuit(CircuitId),
+
+    /// Public inputs cannot be encoded for this circuit
+    InvalidInput(String),
+
+    /// Prover failed
+    ProvingFailed(String),
+
+    /// Proof bytes could not be decoded
+    MalformedProof(String),
+
+    /// Proof was produced by a different backend
+    BackendMismatch { expected: String, got: String },
+}
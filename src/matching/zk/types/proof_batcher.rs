@@ -0,0 +1,45 @@
+// ================================================
+// types/proof_batcher.rs — Epoch Trade Batch Types
+// ================================================
+
+use serde::{Serialize, Deserialize};
+
+use crate::matching::zk::types::proof_input::ZkProofInput;
+
+/// One vault's committed trades for (part of) an epoch, proven as a single
+/// transition from `pre_batch_root` to `post_batch_root`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ZkBatchProofInput {
+    pub vault_id: String,
+    pub epoch: u64,
+    pub pre_batch_root: String,   // Vault Merkle root before the first trade
+    pub post_batch_root: String,  // Vault Merkle root after the last trade
+    pub trades: Vec<ZkProofInput>,
+}
+
+/// Why a batch was closed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchFlushReason {
+    /// Epoch ended with the batch still open
+    EpochBoundary,
+
+    /// Batch reached the trade cap
+    CapReached,
+}
+
+/// A closed batch ready to be proven
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FlushedBatch {
+    pub input: ZkBatchProofInput,
+    pub reason: BatchFlushReason,
+}
+
+/// Rejections when adding a trade to a batch
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BatchError {
+    /// Trade belongs to an epoch the batcher has already closed
+    StaleEpoch { epoch: u64, current: u64 },
+
+    /// Trade's pre-root does not continue from the batch's current root
+    RootDiscontinuity { expected: String, got: String },
+}
@@ -3,7 +3,7 @@
 // ===============================
 
 use serde::{Deserialize, Serialize};
-use crate::types::zk::ZkProofInput;
+use crate::matching::zk::types::proof_input::ZkProofInput;
 
 /// Metadata wrapper for cached proof input.
 /// Used for recovery, audit, or batch re-proving.
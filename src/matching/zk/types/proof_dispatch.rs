@@ -2,8 +2,8 @@
 // zk/proof_dispatch.rs : ZK Proof Dispatch Trigger (Raft-aware)
 // ==================================================================
 
-use crate::zk::proof_generator::generate_proof;
-use crate::types::zk::ZkProofInput;
+use crate::matching::zk::proof_generator::generate_proof;
+use crate::matching::zk::types::proof_input::ZkProofInput;
 use crate::infra::raft_context::is_raft_leader;
 
 /// Attempts to dispatch a ZK proof if this node is the Raft leader.
@@ -26,6 +26,6 @@ pub fn dispatch_if_leader(input: ZkProofInput) {
     } else {
         println!("[ZK Dispatch] Not leader — skipping proof dispatch.");
         // Optionally store input locally for fallback
-        // crate::zk::proof_cache::store(input);
+        // crate::matching::zk::proof_cache::store(input);
     }
 }
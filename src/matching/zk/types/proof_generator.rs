@@ -2,7 +2,7 @@
 // zk/proof_generator.rs : Domex ZK Proof Engine (Plonky2 + PastaCurves)
 // ===============================
 
-use crate::types::zk::ZkProofInput;
+use crate::matching::zk::types::proof_input::ZkProofInput;
 
 /// Generates a zero-knowledge proof based on the committed trade data.
 ///
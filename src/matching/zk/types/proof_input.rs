@@ -6,7 +6,7 @@ use serde::{Serialize, Deserialize};
 
 /// Represents the balance change for an identity and token after trade execution.
 /// This is typically used to compute Merkle deltas in ZK circuits.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BalanceChange {
     /// Poseidon hash of the identity (vault owner)
     pub identity: String,
@@ -22,7 +22,7 @@ pub struct BalanceChange {
 ///
 /// This structure captures all state changes and participants required to
 /// generate a valid ZK proof of execution correctness and liquidity compliance.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ZkProofInput {
     /// Vault identifier (e.g., "vault-btc-usdt")
     pub vault_id: String,
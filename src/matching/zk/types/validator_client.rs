@@ -0,0 +1,40 @@
+// ================================================
+// types/validator_client.rs — Proof Submission Client Types
+// ================================================
+
+use std::time::Duration;
+
+/// Connection, timeout and retry settings for submitting proofs to a validator
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransportConfig {
+    pub connect_timeout: Duration,
+    pub request_timeout: Duration, // Per read/write on an open connection
+    pub max_attempts: u32,         // Including the first try
+    pub initial_backoff: Duration, // Doubled after every failed attempt
+    pub max_backoff: Duration,
+}
+
+impl Default for TransportConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(3),
+            request_timeout: Duration::from_secs(10),
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Errors returned to the proof submitter
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransportError {
+    /// Validator address could not be parsed or resolved
+    BadAddress(String),
+
+    /// Validator refused the proof; retrying will not help
+    Rejected(String),
+
+    /// Every attempt failed; holds the last failure
+    RetriesExhausted { attempts: u32, last_error: String },
+}
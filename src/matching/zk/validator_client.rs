@@ -0,0 +1,217 @@
+// ================================================
+// zk/validator_client.rs — Vault Leader → Validator Proof Submission
+// ================================================
+
+//! Sends proofs to a validator's intake over TCP using the framed protocol
+//! in `common/proof_wire.rs`.
+//!
+//! - One connection is kept open and reused; it is re-dialled after any failure
+//! - Failed attempts are retried with exponential backoff under the same
+//!   idempotency key, so a proof is never registered twice
+//! - Rejections marked non-retryable are returned at once
+
+use crate::common::proof_wire::{
+    read_frame, write_frame, ProofAck, ProofSubmitRequest, ProofSubmitResponse, WireMessage,
+};
+use crate::matching::zk::types::proof_backend::ZkProof;
+use crate::matching::zk::types::validator_client::{TransportConfig, TransportError};
+
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Idempotency key for a proof: stable across retries and restarts
+pub fn idempotency_key(proof: &ZkProof) -> String {
+    format!("{}:{}", proof.circuit.as_str(), proof.public_inputs_hash)
+}
+
+/// Builds the intake request for `proof`
+pub fn proof_request(proof: &ZkProof, vault_id: &str, token: &str, size: u64, owner_hash: &str) -> ProofSubmitRequest {
+    ProofSubmitRequest {
+        idempotency_key: idempotency_key(proof),
+        vault_id: vault_id.to_string(),
+        token: token.to_string(),
+        size,
+        owner_hash: owner_hash.to_string(),
+        circuit: proof.circuit.as_str().to_string(),
+        backend: proof.backend.clone(),
+        public_inputs_hash: proof.public_inputs_hash.clone(),
+        zk_payload: proof.proof_bytes.clone(),
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+    }
+}
+
+/// Client for one validator's proof intake
+pub struct ValidatorClient {
+    addr: SocketAddr,
+    config: TransportConfig,
+    connection: Mutex<Option<TcpStream>>,
+}
+
+impl ValidatorClient {
+    pub fn new(addr: &str, config: TransportConfig) -> Result<Self, TransportError> {
+        let addr = addr
+            .to_socket_addrs()
+            .map_err(|e| TransportError::BadAddress(format!("{}: {}", addr, e)))?
+            .next()
+            .ok_or_else(|| TransportError::BadAddress(addr.to_string()))?;
+
+        Ok(Self {
+            addr,
+            config,
+            connection: Mutex::new(None),
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Submits a proof, retrying transient failures, and returns the validator's ack
+    pub fn submit(&self, request: &ProofSubmitRequest) -> Result<ProofAck, TransportError> {
+        let attempts = self.config.max_attempts.max(1);
+        let mut backoff = self.config.initial_backoff;
+        let mut last_error = String::new();
+
+        for attempt in 1..=attempts {
+            match self.try_submit(request) {
+                Ok(ProofSubmitResponse::Accepted(ack)) => {
+                    println!(
+                        "[ZK NET] Proof {} accepted by {} as {}{}",
+                        ack.idempotency_key,
+                        self.addr,
+                        hex::encode(ack.proof_id),
+                        if ack.duplicate { " (duplicate)" } else { "" }
+                    );
+                    return Ok(ack);
+                }
+                Ok(ProofSubmitResponse::Rejected { retryable: false, reason }) => {
+                    return Err(TransportError::Rejected(reason));
+                }
+                Ok(ProofSubmitResponse::Rejected { retryable: true, reason }) => last_error = reason,
+                Err(e) => {
+                    // Connection state is unknown: drop it and re-dial next time
+                    *self.connection.lock().expect("connection lock poisoned") = None;
+                    last_error = e;
+                }
+            }
+
+            if attempt < attempts {
+                eprintln!(
+                    "[ZK NET] Attempt {}/{} to {} failed ({}), retrying in {:?}",
+                    attempt, attempts, self.addr, last_error, backoff
+                );
+                thread::sleep(backoff);
+                backoff = (backoff * 2).min(self.config.max_backoff);
+            }
+        }
+
+        Err(TransportError::RetriesExhausted { attempts, last_error })
+    }
+
+    fn try_submit(&self, request: &ProofSubmitRequest) -> Result<ProofSubmitResponse, String> {
+        let mut connection = self.connection.lock().expect("connection lock poisoned");
+        if connection.is_none() {
+            let stream = TcpStream::connect_timeout(&self.addr, self.config.connect_timeout)
+                .map_err(|e| format!("connect: {}", e))?;
+            stream
+                .set_read_timeout(Some(self.config.request_timeout))
+                .and_then(|_| stream.set_write_timeout(Some(self.config.request_timeout)))
+                .map_err(|e| format!("configure: {}", e))?;
+            *connection = Some(stream);
+        }
+        let stream = connection.as_mut().expect("connection just opened");
+
+        write_frame(stream, &WireMessage::Submit(request.clone())).map_err(|e| format!("send: {:?}", e))?;
+        match read_frame(stream).map_err(|e| format!("receive: {:?}", e))? {
+            WireMessage::Response(response) => Ok(response),
+            WireMessage::Submit(_) => Err("validator sent a submission instead of a reply".into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matching::zk::types::proof_backend::CircuitId;
+    use crate::validator::proof_intake_server::{spawn_intake_server, stub_check, ProofIntake};
+
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    fn fast_config() -> TransportConfig {
+        TransportConfig {
+            connect_timeout: Duration::from_millis(500),
+            request_timeout: Duration::from_millis(500),
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(40),
+        }
+    }
+
+    fn proof() -> ZkProof {
+        proof_with_inputs(&"ab".repeat(32))
+    }
+
+    fn proof_with_inputs(public_inputs_hash: &str) -> ZkProof {
+        ZkProof {
+            circuit: CircuitId::TradeExecution,
+            backend: "mock".into(),
+            public_inputs_hash: public_inputs_hash.into(),
+            proof_bytes: vec![7; 64],
+        }
+    }
+
+    #[test]
+    fn test_submit_to_local_stub_is_idempotent() {
+        let intake = ProofIntake::with_check(stub_check);
+        let (addr, _server) = spawn_intake_server("127.0.0.1:0", intake.clone()).unwrap();
+        let client = ValidatorClient::new(&addr.to_string(), fast_config()).unwrap();
+
+        let request = proof_request(&proof(), "vault-1", "dBTC", 5, &"00".repeat(32));
+        let first = client.submit(&request).unwrap();
+        let retry = client.submit(&request).unwrap();
+
+        assert!(!first.duplicate);
+        assert!(retry.duplicate);
+        assert_eq!(first.proof_id, retry.proof_id);
+        assert_eq!(intake.take_accepted().len(), 1);
+
+        // Keys differing only past their first 32 bytes get distinct IDs
+        let next = proof_request(&proof_with_inputs(&format!("{}cd", "ab".repeat(31))), "vault-1", "dBTC", 5, &"00".repeat(32));
+        let second = client.submit(&next).unwrap();
+        assert!(!second.duplicate);
+        assert_ne!(second.proof_id, first.proof_id);
+        assert_eq!(intake.take_accepted().len(), 1);
+
+        let mut empty = request.clone();
+        empty.idempotency_key = "other".into();
+        empty.zk_payload.clear();
+        assert!(matches!(client.submit(&empty), Err(TransportError::Rejected(_))));
+    }
+
+    #[test]
+    fn test_retries_after_dropped_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let intake = ProofIntake::with_check(stub_check);
+        let server_intake = intake.clone();
+
+        // First connection is dropped before replying, second is served
+        thread::spawn(move || {
+            drop(listener.accept().unwrap());
+            let (stream, _) = listener.accept().unwrap();
+            let _ = server_intake.serve_connection(stream);
+        });
+
+        let client = ValidatorClient::new(&addr.to_string(), fast_config()).unwrap();
+        let ack = client.submit(&proof_request(&proof(), "vault-1", "dBTC", 5, "owner")).unwrap();
+
+        assert!(!ack.duplicate);
+        assert_eq!(intake.take_accepted()[0].proof_id, ack.proof_id);
+    }
+}
@@ -36,7 +36,7 @@ impl BalanceSnapshot {
 
     /// Returns a Merkle-style hash for the snapshot for proof-of-inclusion (optional)
     pub fn snapshot_hash(&self) -> [u8; 32] {
-        use crate::poseidon_utils::poseidon_hash;
+        use crate::matching::core::poseidon_utils::poseidon_hash;
 
         // Flatten: [address, balance] bytes for all users
         let mut all_data = vec![];
@@ -6,7 +6,7 @@
 // Works alongside token_config.rs for constants and supply cap enforcement.
 
 use std::collections::HashMap;
-use crate::token_config::*;
+use super::token_config::*;
 
 pub struct DomexTokenLedger {
     balances: HashMap<String, u64>, // Maps address (or vault_id) → balance
@@ -57,6 +57,24 @@ impl DomexTokenLedger {
         Ok(())
     }
 
+    /// Largest amount `mint` still accepts under the supply cap
+    pub fn mint_headroom(&self) -> u64 {
+        DOMEX_TOTAL_SUPPLY.saturating_sub(self.total_minted)
+    }
+
+    /// Moves DOMEX between addresses (e.g. paying out escrowed rewards)
+    pub fn transfer(&mut self, from: &str, to: &str, amount: u64) -> Result<(), String> {
+        let from_balance = self.balance_of(from);
+        if amount > from_balance {
+            return Err("Insufficient balance to transfer".into());
+        }
+
+        self.balances.insert(from.to_string(), from_balance - amount);
+        let to_balance = self.balance_of(to);
+        self.balances.insert(to.to_string(), to_balance + amount);
+        Ok(())
+    }
+
     /// Recycles burned DOMEX back into unminted pool (for validator rewards)
     pub fn recycled_supply(&self) -> u64 {
         recycled_fuel_amount(self.total_burned)
@@ -8,7 +8,7 @@
 // - Calculate validator-eligible pool balances
 //
 
-use super::token_config::{FUEL_RECYCLE_RATIO, recycled_fuel_amount, UNMINTED_REWARD_POOL};
+use super::token_config::{recycled_fuel_amount, UNMINTED_REWARD_POOL};
 use std::sync::atomic::{AtomicU64, Ordering};
 
 /// Global atomic counter for tracking total fuel burned
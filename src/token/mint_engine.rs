@@ -12,15 +12,15 @@ use super::token_config::*;
 use super::token_state::TokenState;
 
 /// Mints reward tokens to validator and updates global token state.
+/// The amount comes from epoch settlement (validator/reward_settlement.rs),
+/// which computes it from finalized work.
 ///
-/// Returns `Ok(())` if reward was successful, else `Err` with reason.
+/// Returns the minted amount if reward was successful, else `Err` with reason.
 pub fn mint_validator_reward(
     state: &mut TokenState,
     validator_id: &str,
-    num_blocks: u64,
+    reward_amount: u64,
 ) -> Result<u64, String> {
-    let reward_amount = num_blocks * MAX_VALIDATOR_REWARD;
-
     if reward_amount == 0 {
         return Err("Zero reward amount".into());
     }
@@ -36,10 +36,9 @@ pub fn mint_validator_reward(
 
     // Log or broadcast event (placeholder)
     println!(
-        "[MintEngine] ✅ Validator {} rewarded: {:.6} DOMEX ({} blocks)",
+        "[MintEngine] ✅ Validator {} rewarded: {:.6} DOMEX",
         validator_id,
         reward_amount as f64 / DOMEX_DECIMALS as f64,
-        num_blocks
     );
 
     Ok(reward_amount)
@@ -17,6 +17,12 @@ pub struct DomexToken {
     pub state: TokenState,
 }
 
+impl Default for DomexToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DomexToken {
     /// Initializes a new DOMEX token context (fresh or restored state)
     pub fn new() -> Self {
@@ -25,21 +31,20 @@ impl DomexToken {
         }
     }
 
-    /// Mint reward for a validator over N blocks
-    pub fn reward_validator(&mut self, validator_id: &str, blocks: u64) -> Result<u64, String> {
-        mint_validator_reward(&mut self.state, validator_id, blocks)
+    /// Mint a settled reward amount for a validator
+    pub fn reward_validator(&mut self, validator_id: &str, amount: u64) -> Result<u64, String> {
+        mint_validator_reward(&mut self.state, validator_id, amount)
     }
 
     /// Record fuel burn and return recycled amount
     pub fn burn_fuel(&mut self, burned: u64) -> u64 {
-        let recycled = recycled_fuel_amount(burned);
-        self.state.recycle(recycled);
-        recycled
+        self.state.record_burn(burned);
+        recycled_fuel_amount(burned)
     }
 
     /// Returns total minted DOMEX so far
     pub fn total_minted(&self) -> u64 {
-        self.state.total_minted
+        self.state.minted
     }
 
     /// Returns available unminted supply
@@ -5,10 +5,21 @@
 // Maintains Merkle root of DOMEX token state.
 // Used in validator attestation, onboarding, withdrawal proofs.
 //
+// Same fixed-slot sparse tree as vault balances (`validator/sparse_merkle.rs`):
+// each (vault_id, owner) has its own slot, so every update rehashes one path.
+//
 
-use poseidon_utils::{poseidon_hash, PoseidonLeaf};
+use pasta_curves::group::ff::PrimeField;
+use crate::common::poseidon_hasher::PoseidonHasher;
+use crate::matching::core::poseidon_utils::string_to_limbs;
+use crate::validator::poseidon_utils::u64_to_fp;
+use crate::validator::sparse_merkle::SparseMerkleTree;
+use crate::validator::types::sparse_merkle::{SmtHash, SmtLeaf, SmtProof};
 use std::collections::BTreeMap;
 
+/// Domain tag for token balance keys
+const TOKEN_KEY_DOMAIN: u64 = 9;
+
 /// Represents a user token balance entry to be inserted into the Merkle tree.
 #[derive(Debug, Clone)]
 pub struct TokenLeaf {
@@ -18,36 +29,66 @@ pub struct TokenLeaf {
 }
 
 impl TokenLeaf {
-    pub fn to_poseidon_leaf(&self) -> PoseidonLeaf {
-        PoseidonLeaf::new(vec![
-            poseidon_hash(&[self.vault_id.as_bytes()]),
-            poseidon_hash(&[self.owner.as_bytes()]),
-            poseidon_hash(&[self.balance.to_be_bytes()]),
-        ])
+    /// Slot of this balance: Poseidon(TOKEN_KEY || vault_id || owner), limb-encoded
+    pub fn key(&self) -> SmtHash {
+        token_key(&self.vault_id, &self.owner)
+    }
+
+    /// The sparse-tree leaf holding this balance
+    pub fn smt_leaf(&self) -> SmtLeaf {
+        SmtLeaf {
+            key: self.key(),
+            available: self.balance,
+            reserved: 0,
+        }
     }
 }
 
+/// Tree key for a (vault_id, owner) token balance
+pub fn token_key(vault_id: &str, owner: &str) -> SmtHash {
+    let mut inputs = vec![u64_to_fp(TOKEN_KEY_DOMAIN)];
+    inputs.extend(string_to_limbs(vault_id));
+    inputs.extend(string_to_limbs(owner));
+
+    let mut hasher = PoseidonHasher::new();
+    hasher.hash(&inputs).to_repr()
+}
+
 /// Merkle tree over token balances.
-/// Stores sorted leaves and updates root when needed.
-#[derive(Debug)]
+/// Internal nodes are cached, so each update rehashes one path.
 pub struct TokenMerkle {
     pub leaves: BTreeMap<String, TokenLeaf>, // key = vault_id + owner
     pub root: [u8; 32],
+    tree: SparseMerkleTree,
+}
+
+impl Default for TokenMerkle {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TokenMerkle {
     pub fn new() -> Self {
+        let tree = SparseMerkleTree::new();
         TokenMerkle {
             leaves: BTreeMap::new(),
-            root: [0u8; 32],
+            root: tree.root(),
+            tree,
         }
     }
 
-    /// Insert or update a token leaf and recompute Merkle root.
+    /// Insert or update a token leaf. O(depth).
     pub fn insert_or_update(&mut self, leaf: TokenLeaf) {
-        let key = format!("{}:{}", leaf.vault_id, leaf.owner);
-        self.leaves.insert(key, leaf);
-        self.root = self.compute_root();
+        self.root = self.tree.insert(leaf.smt_leaf());
+        self.leaves.insert(format!("{}:{}", leaf.vault_id, leaf.owner), leaf);
+    }
+
+    /// Apply many balance changes, one path each.
+    pub fn insert_or_update_batch(&mut self, leaves: Vec<TokenLeaf>) {
+        for leaf in leaves {
+            self.insert_or_update(leaf);
+        }
     }
 
     /// Returns the Merkle root of current token state.
@@ -55,28 +96,12 @@ impl TokenMerkle {
         self.root
     }
 
-    /// Internal: recomputes the Merkle root from current leaves.
-    fn compute_root(&self) -> [u8; 32] {
-        let mut poseidon_leaves: Vec<PoseidonLeaf> = self
-            .leaves
-            .values()
-            .map(|leaf| leaf.to_poseidon_leaf())
-            .collect();
-
-        // Pad to next power of 2 if necessary
-        while poseidon_leaves.len() & (poseidon_leaves.len() - 1) != 0 {
-            poseidon_leaves.push(PoseidonLeaf::default());
-        }
-
-        poseidon_utils::compute_poseidon_merkle_root(poseidon_leaves)
-    }
-
-    /// Returns inclusion proof for a given vault + owner.
-    pub fn generate_proof(&self, vault_id: &str, owner: &str) -> Option<Vec<[u8; 32]>> {
-        let key = format!("{}:{}", vault_id, owner);
-        self.leaves.get(&key).map(|leaf| {
-            let leaf_hash = leaf.to_poseidon_leaf();
-            poseidon_utils::generate_merkle_proof(&leaf_hash, &self.leaves)
-        })
+    /// Returns inclusion proof for a given vault + owner, served from cached nodes.
+    /// Verify with `sparse_merkle::verify_inclusion(&root, &leaf.smt_leaf(), &proof)`.
+    pub fn generate_proof(&self, vault_id: &str, owner: &str) -> Option<SmtProof> {
+        self.tree
+            .prove_inclusion(&token_key(vault_id, owner))
+            .ok()
+            .map(|(_, proof)| proof)
     }
 }
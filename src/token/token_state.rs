@@ -23,6 +23,12 @@ pub struct TokenState {
     pub recycled: u64,
 }
 
+impl Default for TokenState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TokenState {
     /// Creates a new TokenState with only the genesis validator mint applied
     pub fn new() -> Self {
@@ -43,6 +49,11 @@ impl TokenState {
         Ok(())
     }
 
+    /// Largest amount `mint_reward` still accepts under the supply cap
+    pub fn mint_headroom(&self) -> u64 {
+        DOMEX_TOTAL_SUPPLY.saturating_sub(self.minted)
+    }
+
     /// Record a fuel burn event (e.g., from proof submission)
     pub fn record_burn(&mut self, amount: u64) {
         self.burned += amount;
@@ -1,9 +1,28 @@
 // Domex Validator Attestation Builder
 // Generates attestation message after ZK proof verification
 
-use crate::types::NormalizedProof;
-use crate::utils::poseidon_hash;
-use chrono::Utc;
+use pasta_curves::group::ff::PrimeField;
+use crate::validator::types::inbound_proof::NormalizedProof;
+use crate::validator::types::delegation_pool::DelegationAction;
+use crate::matching::core::poseidon_utils::string_to_limbs;
+use crate::validator::poseidon_utils::u64_to_fp;
+use crate::common::signing::{self, Digest, SigningKey};
+use crate::common::poseidon_hasher::PoseidonHasher;
+
+/// Domain tag for attestation signature digests
+const ATTESTATION_AUTH_DOMAIN: u64 = 3;
+
+/// Domain tag for self-reported code hashes
+const CODE_REPORT_DOMAIN: u64 = 7;
+
+/// Domain tag for commission terms a validator opens to delegation with
+const COMMISSION_DOMAIN: u64 = 8;
+
+/// Domain tag for a committee member's vote on a round's batch hash
+const VOTE_DOMAIN: u64 = 10;
+
+/// Domain tag for a delegator's signed delegation operation
+const DELEGATION_DOMAIN: u64 = 11;
 
 /// Structure representing the final attestation data
 #[derive(Debug, Clone)]
@@ -14,21 +33,178 @@ pub struct ProofAttestation {
     pub owner_hash: String,
     pub zk_root: String,
     pub attestation_hash: String,
-    pub timestamp: u64,
+    pub epoch: u64,           // Oracle epoch of the attested slot
+    pub proof_id: String,     // Proof or batch the slot is for (hex)
+    pub validator_id: String, // Attesting validator
+    pub signature: String,    // secp256k1 signature over `attestation_digest`, hex
+}
+
+impl ProofAttestation {
+    /// Slot the attestation is for: (oracle epoch, proof or batch ID).
+    /// Both are committed to by `attestation_hash`, so a validator signs
+    /// exactly one hash per slot.
+    pub fn slot(&self) -> (u64, &str) {
+        (self.epoch, &self.proof_id)
+    }
+}
+
+/// Digest a validator signs: Poseidon(AUTH || validator_id || attestation_hash),
+/// both strings limb-encoded in full
+pub fn attestation_digest(validator_id: &str, attestation_hash: &str) -> Digest {
+    let mut inputs = vec![u64_to_fp(ATTESTATION_AUTH_DOMAIN)];
+    inputs.extend(string_to_limbs(validator_id));
+    inputs.extend(string_to_limbs(attestation_hash));
+
+    let mut hasher = PoseidonHasher::new();
+    hasher.hash(&inputs).to_repr()
+}
+
+/// Signature binding `validator_id` to `attestation_hash` under the validator's secret key
+pub fn attestation_signature(key: &SigningKey, validator_id: &str, attestation_hash: &str) -> String {
+    signing::sign(key, &attestation_digest(validator_id, attestation_hash))
+}
+
+/// True if `signature` over (validator_id, attestation_hash) verifies under `public_key`
+pub fn verify_attestation_signature(public_key: &str, validator_id: &str, attestation_hash: &str, signature: &str) -> bool {
+    signing::verify(public_key, &attestation_digest(validator_id, attestation_hash), signature)
+}
+
+/// Hash a validator signs to report the code it runs in `epoch`:
+/// Poseidon(CODE_REPORT || epoch || code_hash), hex
+pub fn code_report_hash(code_hash: &str, epoch: u64) -> String {
+    let mut inputs = vec![u64_to_fp(CODE_REPORT_DOMAIN), u64_to_fp(epoch)];
+    inputs.extend(string_to_limbs(code_hash));
+
+    let mut hasher = PoseidonHasher::new();
+    hex::encode(hasher.hash(&inputs).to_repr())
+}
+
+/// Signed report that `validator_id` runs `code_hash` in `epoch`
+pub fn sign_code_report(key: &SigningKey, validator_id: &str, code_hash: &str, epoch: u64) -> String {
+    attestation_signature(key, validator_id, &code_report_hash(code_hash, epoch))
+}
+
+/// Hash a validator signs to set its commission terms in `epoch`:
+/// Poseidon(COMMISSION || epoch || rate_bps || max_change_bps), hex
+pub fn commission_hash(rate_bps: u16, max_change_bps: u16, epoch: u64) -> String {
+    let inputs = vec![
+        u64_to_fp(COMMISSION_DOMAIN),
+        u64_to_fp(epoch),
+        u64_to_fp(rate_bps.into()),
+        u64_to_fp(max_change_bps.into()),
+    ];
+
+    let mut hasher = PoseidonHasher::new();
+    hex::encode(hasher.hash(&inputs).to_repr())
+}
+
+/// Signed commission terms of `validator_id` for `epoch`
+pub fn sign_commission(key: &SigningKey, validator_id: &str, rate_bps: u16, max_change_bps: u16, epoch: u64) -> String {
+    attestation_signature(key, validator_id, &commission_hash(rate_bps, max_change_bps, epoch))
+}
+
+/// Hash a committee member signs to vote for `batch_hash` in `round`:
+/// Poseidon(VOTE || round || batch_hash), hex
+pub fn vote_hash(round: u64, batch_hash: &str) -> String {
+    let mut inputs = vec![u64_to_fp(VOTE_DOMAIN), u64_to_fp(round)];
+    inputs.extend(string_to_limbs(batch_hash));
+
+    let mut hasher = PoseidonHasher::new();
+    hex::encode(hasher.hash(&inputs).to_repr())
+}
+
+/// Signed vote of `validator_id` for `batch_hash` in `round`
+pub fn sign_vote(key: &SigningKey, validator_id: &str, round: u64, batch_hash: &str) -> String {
+    attestation_signature(key, validator_id, &vote_hash(round, batch_hash))
+}
+
+/// Hash a delegator signs for one delegation operation, `to` empty unless
+/// redelegating: Poseidon(DELEGATION || action || nonce || amount || validator || to), hex
+pub fn delegation_hash(action: DelegationAction, validator: &str, to: &str, amount: u64, nonce: u64) -> String {
+    let mut inputs = vec![
+        u64_to_fp(DELEGATION_DOMAIN),
+        u64_to_fp(action as u64),
+        u64_to_fp(nonce),
+        u64_to_fp(amount),
+    ];
+    inputs.extend(string_to_limbs(validator));
+    inputs.extend(string_to_limbs(to));
+
+    let mut hasher = PoseidonHasher::new();
+    hex::encode(hasher.hash(&inputs).to_repr())
+}
+
+/// Signed delegation operation of `delegator` (its hex public key) at `nonce`
+pub fn sign_delegation(
+    key: &SigningKey,
+    delegator: &str,
+    action: DelegationAction,
+    validator: &str,
+    to: &str,
+    amount: u64,
+    nonce: u64,
+) -> String {
+    attestation_signature(key, delegator, &delegation_hash(action, validator, to, amount, nonce))
+}
+
+/// Poseidon hash over all fields and the slot (epoch, proof ID) to create a
+/// unique attestation identifier, strings limb-encoded in full
+pub fn compute_attestation_hash(
+    vault_id: &str,
+    token: &str,
+    owner_hash: &str,
+    zk_root: &str,
+    epoch: u64,
+    proof_id: &str,
+) -> String {
+    let mut inputs = Vec::new();
+    for field in [vault_id, token, owner_hash, zk_root] {
+        inputs.extend(string_to_limbs(field));
+    }
+    inputs.push(u64_to_fp(epoch));
+    inputs.extend(string_to_limbs(proof_id));
+
+    let mut hasher = PoseidonHasher::new();
+    hex::encode(hasher.hash(&inputs).to_repr())
+}
+
+/// True if `attestation_hash` commits to the attestation's own fields
+pub fn verify_attestation_hash(attestation: &ProofAttestation) -> bool {
+    attestation.attestation_hash
+        == compute_attestation_hash(
+            &attestation.vault_id,
+            &attestation.token,
+            &attestation.owner_hash,
+            &attestation.zk_root,
+            attestation.epoch,
+            &attestation.proof_id,
+        )
+}
+
+/// Checks an attestation's signature against its signer's registered public key
+pub fn verify_attestation(attestation: &ProofAttestation, public_key: &str) -> bool {
+    verify_attestation_signature(
+        public_key,
+        &attestation.validator_id,
+        &attestation.attestation_hash,
+        &attestation.signature,
+    )
 }
 
-/// Builds a ProofAttestation struct from a normalized, verified proof
-pub fn build_attestation(proof: &NormalizedProof, zk_root: &str) -> ProofAttestation {
-    let timestamp = Utc::now().timestamp() as u64;
+/// Builds a ProofAttestation struct from a normalized, verified proof for
+/// the slot (`epoch`, `proof_id`), signed by `validator_id` with `key`
+pub fn build_attestation(
+    proof: &NormalizedProof,
+    zk_root: &str,
+    epoch: u64,
+    proof_id: &str,
+    validator_id: &str,
+    key: &SigningKey,
+) -> ProofAttestation {
+    let attestation_hash =
+        compute_attestation_hash(&proof.vault_id, &proof.token, &proof.owner_hash, zk_root, epoch, proof_id);
 
-    // Poseidon hash over all fields to create a unique attestation identifier
-    let attestation_hash = poseidon_hash(&[
-        &proof.vault_id,
-        &proof.token,
-        &proof.owner_hash,
-        &zk_root.to_string(),
-        &timestamp.to_string(),
-    ]);
+    let signature = attestation_signature(key, validator_id, &attestation_hash);
 
     ProofAttestation {
         vault_id: proof.vault_id.clone(),
@@ -37,6 +213,9 @@ pub fn build_attestation(proof: &NormalizedProof, zk_root: &str) -> ProofAttesta
         owner_hash: proof.owner_hash.clone(),
         zk_root: zk_root.to_string(),
         attestation_hash,
-        timestamp,
+        epoch,
+        proof_id: proof_id.to_string(),
+        validator_id: validator_id.to_string(),
+        signature,
     }
 }
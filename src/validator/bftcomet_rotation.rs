@@ -3,14 +3,23 @@
 // ==========================================================
 //
 // Handles validator committee rotation, attestation quorum checks,
-// majority validator selection via Poseidon-hashed epoch,
+// stake-weighted majority validator sortition seeded by
+// Poseidon(epoch || previous finalized root),
 // and supports scale-up to 99,700 validator submissions per epoch.
+// Each closed epoch's finality is recorded and fed to downtime tracking;
+// slashed validators are kept out of the next committee.
 //
 
-use std::collections::{HashSet, VecDeque};
-use crate::validator::attestation::ProofAttestation;
-use crate::common::poseidon_utils::poseidon_hash_u64;
-use chrono::Utc;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use crate::common::merkle::hash_from_hex;
+use crate::validator::epoch_oracle::EpochOracle;
+use crate::validator::liveness::LivenessTracker;
+use crate::validator::attestation::{verify_attestation, ProofAttestation};
+use crate::validator::types::bftcomet::{AttestationRejection, CommitteeFinality, EpochReport, RejectedAttestation, SlashDetection};
+use crate::validator::types::liveness::LivenessConfig;
+use crate::validator::types::quorum_sync::QuorumThreshold;
+use crate::validator::types::sortition::SortitionCandidate;
+use crate::validator::sortition::{sortition_seed, select_weighted};
 
 /// Constants
 pub const MINORITY_SIZE: usize = 300;
@@ -22,6 +31,7 @@ pub struct Validator {
     pub id: String,              // Poseidon identity or pubkey
     pub last_active_epoch: u64, // Epoch participation tracking
     pub stake: u64,             // Used for ranking
+    pub public_key: String,     // Verifies this validator's attestation signatures (hex)
 }
 
 /// Committee rotation and validation manager
@@ -30,18 +40,46 @@ pub struct BFTCometRotation {
     majority_validators: HashSet<Validator>, // Global validator pool (up to 99,700)
     full_committee: HashSet<Validator>,      // 300 + 1 selected for attestation (301)
     epoch: u64,                              // Current epoch number
-    epoch_start_ts: u64,                     // Timestamp in seconds
+    epoch_start_ts: Option<u64>,             // Genesis-anchored start (None when block-driven)
+    finality_threshold: QuorumThreshold,     // Share of the full committee needed (default 2/3)
+    finalized_root: [u8; 32],                // Last finalized root, chained into sortition
+    oracle: EpochOracle,                     // Genesis anchor for epoch start times
+    excluded: HashSet<String>,               // Jailed or tombstoned, kept out of the committee
+    liveness: LivenessTracker,               // Duties of closed epochs, for downtime evidence
+    finalities: BTreeMap<u64, CommitteeFinality>, // Duty record per closed epoch, within the liveness window
 }
 
 impl BFTCometRotation {
-    /// Initialize new BFT-Comet rotation
-    pub fn new(minority: Vec<Validator>, majority: Vec<Validator>, epoch: u64) -> Self {
+    /// Initialize new BFT-Comet rotation with wall-clock epochs since `genesis_ts`
+    pub fn new(minority: Vec<Validator>, majority: Vec<Validator>, epoch: u64, genesis_ts: u64) -> Self {
+        Self::with_oracle(minority, majority, epoch, EpochOracle::new(genesis_ts))
+    }
+
+    /// Initialize with the network's epoch oracle; epoch start times come
+    /// from its genesis anchor, so every node reports the same ones
+    pub fn with_oracle(
+        minority: Vec<Validator>,
+        majority: Vec<Validator>,
+        epoch: u64,
+        oracle: EpochOracle,
+    ) -> Self {
+        // Canonical order, so every node rotates the same judge whatever
+        // order it received the committee in
+        let mut minority = minority;
+        minority.sort_by(|a, b| a.id.cmp(&b.id));
         let minority_committee = VecDeque::from(minority);
         let majority_validators = majority.into_iter().collect::<HashSet<_>>();
 
         let mut full_committee = minority_committee.iter().cloned().collect::<HashSet<_>>();
 
-        if let Some(selected) = Self::select_majority_validator(epoch, &majority_validators, &full_committee) {
+        let finalized_root = [0u8; 32];
+        if let Some(selected) = Self::select_majority_validator(
+            epoch,
+            &finalized_root,
+            &majority_validators,
+            &full_committee,
+            &HashSet::new(),
+        ) {
             full_committee.insert(selected);
         }
 
@@ -50,27 +88,80 @@ impl BFTCometRotation {
             majority_validators,
             full_committee,
             epoch,
-            epoch_start_ts: Utc::now().timestamp() as u64,
+            epoch_start_ts: oracle.epoch_start_ts(epoch),
+            finality_threshold: QuorumThreshold::default(),
+            finalized_root,
+            oracle,
+            excluded: HashSet::new(),
+            liveness: LivenessTracker::default(),
+            finalities: BTreeMap::new(),
         }
     }
 
-    /// Deterministically selects a majority validator using Poseidon(epoch)
+    /// Replaces the downtime rule; duties tracked so far are dropped
+    pub fn set_liveness_config(&mut self, config: LivenessConfig) {
+        self.liveness = LivenessTracker::new(config);
+    }
+
+    /// Committee records of recent closed epochs, finalized or not: the
+    /// record downtime evidence is checked against (`EvidenceContext::finalities`)
+    pub fn finalities(&self) -> &BTreeMap<u64, CommitteeFinality> {
+        &self.finalities
+    }
+
+    /// Records the latest finalized root; the next committee update is seeded with it
+    pub fn set_finalized_root(&mut self, root: [u8; 32]) {
+        self.finalized_root = root;
+    }
+
+    /// Sets the share of the full committee that must agree for finality
+    pub fn set_finality_threshold(&mut self, threshold: QuorumThreshold) {
+        self.finality_threshold = threshold;
+    }
+
+    /// Replaces the excluded set (e.g. `Slasher::excluded`) and rebuilds the
+    /// full committee without them until they unjail
+    pub fn set_excluded(&mut self, excluded: HashSet<String>) {
+        self.excluded = excluded;
+        self.update_full_committee();
+    }
+
+    /// Replaces validator stakes with their weights (self-stake plus delegations,
+    /// see `DelegationPool::weights`) and rebuilds the full committee
+    pub fn update_weights(&mut self, weights: &HashMap<String, u64>) {
+        let reweigh = |mut v: Validator| {
+            if let Some(weight) = weights.get(&v.id) {
+                v.stake = *weight;
+            }
+            v
+        };
+        self.minority_committee = self.minority_committee.drain(..).map(reweigh).collect();
+        self.majority_validators = self.majority_validators.drain().map(reweigh).collect();
+        self.update_full_committee();
+    }
+
+    /// Stake-weighted sortition of one majority validator, seeded with
+    /// Poseidon(epoch || previous finalized root) over ID-sorted candidates
     fn select_majority_validator(
         epoch: u64,
+        finalized_root: &[u8; 32],
         majority: &HashSet<Validator>,
         exclude: &HashSet<Validator>,
+        excluded_ids: &HashSet<String>,
     ) -> Option<Validator> {
-        let seed = poseidon_hash_u64(epoch);
-        let available: Vec<_> = majority.iter().filter(|v| !exclude.contains(v)).cloned().collect();
-        if available.is_empty() {
-            None
-        } else {
-            let index = (seed % available.len() as u64) as usize;
-            Some(available[index].clone())
-        }
+        let candidates: Vec<SortitionCandidate> = majority
+            .iter()
+            .filter(|v| !exclude.contains(v) && !excluded_ids.contains(&v.id))
+            .map(|v| SortitionCandidate { id: v.id.clone(), stake: v.stake })
+            .collect();
+
+        let seed = sortition_seed(epoch, finalized_root);
+        let winner = select_weighted(&candidates, &seed, 1).pop()?;
+        majority.iter().find(|v| v.id == winner).cloned()
     }
 
-    /// Rotate 1 validator out of the 300-judge committee
+    /// Rotate 1 validator out of the 300-judge committee: the front judge
+    /// in the canonical (ID-ordered, then rotated) sequence moves to the back
     pub fn rotate_minority(&mut self) {
         if let Some(rotated) = self.minority_committee.pop_front() {
             self.minority_committee.push_back(rotated);
@@ -79,74 +170,225 @@ impl BFTCometRotation {
 
     /// Rebuilds full committee after rotation
     pub fn update_full_committee(&mut self) {
-        self.full_committee = self.minority_committee.iter().cloned().collect();
+        self.full_committee = self
+            .minority_committee
+            .iter()
+            .filter(|v| !self.excluded.contains(&v.id))
+            .cloned()
+            .collect();
 
-        if let Some(selected) = Self::select_majority_validator(self.epoch, &self.majority_validators, &self.full_committee) {
+        if let Some(selected) = Self::select_majority_validator(
+            self.epoch,
+            &self.finalized_root,
+            &self.majority_validators,
+            &self.full_committee,
+            &self.excluded,
+        ) {
             self.full_committee.insert(selected);
         }
     }
 
+    /// Current epoch and the time it started (None when epochs are block-driven)
+    pub fn epoch(&self) -> (u64, Option<u64>) {
+        (self.epoch, self.epoch_start_ts)
+    }
+
     /// Returns the current full committee (301 validators)
     pub fn current_committee(&self) -> &HashSet<Validator> {
         &self.full_committee
     }
 
-    /// Validates if all attestations match (301 required)
+    /// True once enough of the full committee agrees on one hash
     pub fn validate_attestations(&self, attestations: &[ProofAttestation]) -> bool {
-        if attestations.len() != FULL_COMMITTEE_SIZE {
-            return false;
-        }
-        let hashes: HashSet<_> = attestations.iter().map(|a| &a.attestation_hash).collect();
-        hashes.len() == 1
+        self.finality(attestations).is_some()
     }
 
-    /// Detects validators who submitted wrong attestations (for slashing)
-    pub fn detect_slashable_validators(&self, attestations: &[ProofAttestation]) -> Vec<Validator> {
-        if attestations.len() != FULL_COMMITTEE_SIZE {
-            return vec![];
+    /// Finality report if the leading hash reaches the threshold of the full
+    /// committee: every agreeing member, plus dissenting and missing members
+    /// for downtime tracking
+    pub fn finality(&self, attestations: &[ProofAttestation]) -> Option<CommitteeFinality> {
+        Some(self.duty_record(attestations)).filter(|record| record.finalized)
+    }
+
+    /// Every full-committee member's duty over `attestations`, whether or not
+    /// the leading hash finalizes: members who attested it, members who
+    /// attested another hash, and members with no authenticated attestation
+    pub fn duty_record(&self, attestations: &[ProofAttestation]) -> CommitteeFinality {
+        let (accepted, _) = self.screen_attestations(attestations);
+        let (hash, votes) = Self::leading_hash(&accepted).unwrap_or_default();
+        let committee_size = self.full_committee.len() as u64;
+
+        let voted: HashMap<&str, &str> = accepted
+            .iter()
+            .map(|att| (att.validator_id.as_str(), att.attestation_hash.as_str()))
+            .collect();
+
+        let mut members: Vec<&str> = self.full_committee.iter().map(|v| v.id.as_str()).collect();
+        members.sort_unstable();
+
+        let mut finality = CommitteeFinality {
+            finalized: votes > 0 && self.finality_threshold.is_met(votes as u64, committee_size),
+            attestation_hash: hash.clone(),
+            selected_majority: self
+                .full_committee
+                .iter()
+                .find(|v| self.minority_committee.iter().all(|m| m.id != v.id))
+                .map(|v| v.id.clone()),
+            ..CommitteeFinality::default()
+        };
+        for id in members {
+            match voted.get(id) {
+                Some(h) if *h == hash => finality.validators.push(id.to_string()),
+                Some(_) => finality.dissenting.push(id.to_string()),
+                None => finality.missing.push(id.to_string()),
+            }
         }
+        finality
+    }
+
+    /// Detects validators who submitted wrong attestations (for slashing).
+    ///
+    /// Each attestation is attributed to the validator it names, and only counts
+    /// if that validator is in the full committee, has not attested already, and
+    /// the signature verifies under its public key. Authenticated signers of a hash
+    /// other than the most attested one are slashable.
+    pub fn detect_slashable_validators(&self, attestations: &[ProofAttestation]) -> SlashDetection {
+        let (accepted, rejected) = self.screen_attestations(attestations);
+        let mut detection = SlashDetection {
+            rejected,
+            ..SlashDetection::default()
+        };
+
+        let quorum_hash = match Self::leading_hash(&accepted) {
+            Some((hash, _)) => hash,
+            None => return detection,
+        };
+
+        detection.slashable = accepted
+            .iter()
+            .filter(|att| att.attestation_hash != quorum_hash)
+            .map(|att| att.validator_id.clone())
+            .collect();
+        detection.quorum_hash = Some(quorum_hash);
+        detection
+    }
+
+    /// Splits attestations into countable ones (authenticated committee
+    /// members, first attestation each) and rejected ones
+    fn screen_attestations<'a>(
+        &self,
+        attestations: &'a [ProofAttestation],
+    ) -> (Vec<&'a ProofAttestation>, Vec<RejectedAttestation>) {
+        let committee: HashMap<&str, &Validator> =
+            self.full_committee.iter().map(|v| (v.id.as_str(), v)).collect();
+
+        let mut seen: HashSet<&str> = HashSet::new();
+        let mut accepted = Vec::new();
+        let mut rejected = Vec::new();
 
-        let mut counts = std::collections::HashMap::new();
         for att in attestations {
-            *counts.entry(&att.attestation_hash).or_insert(0) += 1;
-        }
+            let reason = match committee.get(att.validator_id.as_str()) {
+                None => Some(AttestationRejection::NotCommitteeMember),
+                Some(v) if !verify_attestation(att, &v.public_key) => Some(AttestationRejection::BadSignature),
+                Some(_) if !seen.insert(att.validator_id.as_str()) => Some(AttestationRejection::Duplicate),
+                Some(_) => None,
+            };
 
-        let quorum_hash = counts.iter().max_by_key(|(_, count)| *count).map(|(h, _)| *h);
-        if quorum_hash.is_none() {
-            return vec![];
+            match reason {
+                Some(reason) => rejected.push(RejectedAttestation {
+                    validator_id: att.validator_id.clone(),
+                    reason,
+                }),
+                None => accepted.push(att),
+            }
         }
+        (accepted, rejected)
+    }
 
-        attestations.iter().enumerate()
-            .filter_map(|(i, att)| {
-                if &att.attestation_hash != quorum_hash.unwrap() {
-                    self.full_committee.iter().nth(i).cloned()
-                } else {
-                    None
-                }
-            })
-            .collect()
+    /// Most attested hash and its vote count (ties broken by hash so every
+    /// node picks the same one)
+    fn leading_hash(accepted: &[&ProofAttestation]) -> Option<(String, usize)> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for att in accepted {
+            *counts.entry(att.attestation_hash.as_str()).or_insert(0) += 1;
+        }
+        counts
+            .into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(hash, votes)| (hash.to_string(), votes))
     }
 
-    /// Step to the next epoch and revalidate
-    pub fn epoch_step(&mut self, attestations: &[ProofAttestation]) -> bool {
+    /// Closes the current epoch and steps to the next.
+    ///
+    /// The closing committee's duty record over `attestations` is kept and its
+    /// duties tracked even when the epoch does not finalize (silence is a miss
+    /// either way); validators over the downtime limit come back as evidence
+    /// for `Slasher::submit_evidence`. A finalized hash becomes the root the
+    /// next committee's sortition is seeded with. The next committee is built
+    /// without `excluded` (`Slasher::excluded`, taken after that evidence is applied).
+    pub fn epoch_step(&mut self, attestations: &[ProofAttestation], excluded: HashSet<String>) -> EpochReport {
+        let closed = self.epoch;
+        let record = self.duty_record(attestations);
+        if record.finalized {
+            match hash_from_hex(&record.attestation_hash) {
+                Some(root) => self.set_finalized_root(root),
+                None => println!("[ROTATION] Finalized hash {} is not a root; seed unchanged", record.attestation_hash),
+            }
+        }
+        self.liveness.record_finality(closed, &record);
+        self.finalities.insert(closed, record.clone());
+        let window = self.liveness.config().window_epochs;
+        self.finalities = self.finalities.split_off(&closed.saturating_sub(window.saturating_sub(1)));
+        let downtime = self.liveness.evaluate(closed);
+
         self.epoch += 1;
-        self.epoch_start_ts = Utc::now().timestamp() as u64;
+        self.epoch_start_ts = self.oracle.epoch_start_ts(self.epoch);
         self.rotate_minority();
-        self.update_full_committee();
+        self.set_excluded(excluded);
 
-        self.validate_attestations(attestations)
+        EpochReport {
+            epoch: closed,
+            finality: Some(record).filter(|record| record.finalized),
+            downtime,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::signing::fixtures::{key, never_verifies};
+    use crate::common::signing::public_key_hex;
+    use crate::validator::attestation::attestation_signature;
+    use crate::validator::delegation_pool::DelegationPool;
+    use crate::validator::slashing::{EvidenceContext, Slasher};
+    use crate::validator::staking_pool::StakingPool;
+    use crate::common::clock::ManualClock;
+    use std::sync::Arc;
+
+    const GENESIS: u64 = 1_700_000_000;
 
     fn make_validator(id: &str) -> Validator {
         Validator {
             id: id.to_string(),
             last_active_epoch: 0,
             stake: 1000,
+            public_key: public_key_hex(&key(id)),
+        }
+    }
+
+    fn signed(id: &str, hash: &str) -> ProofAttestation {
+        ProofAttestation {
+            vault_id: "vault1".into(),
+            token: "dBTC".into(),
+            size: 100,
+            owner_hash: "hash_owner".into(),
+            zk_root: "zkroot".into(),
+            attestation_hash: hash.into(),
+            epoch: 1,
+            proof_id: "proof-1".into(),
+            validator_id: id.into(),
+            signature: attestation_signature(&key(id), id, hash),
         }
     }
 
@@ -160,22 +402,205 @@ mod tests {
             .map(|i| make_validator(&format!("majority{}", i)))
             .collect();
 
-        let mut rotation = BFTCometRotation::new(minority, majority, 1);
+        let mut rotation = BFTCometRotation::new(minority, majority, 1, GENESIS);
 
-        let attestation = ProofAttestation {
-            vault_id: "vault1".into(),
-            token: "dBTC".into(),
-            size: 100,
-            owner_hash: "hash_owner".into(),
-            zk_root: "zkroot".into(),
-            attestation_hash: "onehash".into(),
-            timestamp: Utc::now().timestamp() as u64,
+        // 300 of 301 agree: the selected majority validator is offline
+        let attestations: Vec<_> = (0..MINORITY_SIZE)
+            .map(|i| signed(&format!("minority{}", i), "onehash"))
+            .collect();
+
+        let finality = rotation.finality(&attestations).unwrap();
+        assert_eq!(finality.validators.len(), MINORITY_SIZE);
+        assert_eq!(finality.missing.len(), 1);
+        assert!(rotation.validate_attestations(&attestations));
+
+        let report = rotation.epoch_step(&attestations, HashSet::new());
+        assert_eq!(report.epoch, 1);
+        assert_eq!(report.finality, Some(finality));
+        assert_eq!(rotation.finalities().len(), 1);
+    }
+
+    #[test]
+    fn test_epoch_start_is_genesis_anchored() {
+        let minority: Vec<Validator> = (0..3).map(|i| make_validator(&format!("m{}", i))).collect();
+
+        // Nodes whose clocks disagree still report the same epoch start
+        let fast = Arc::new(ManualClock::new(GENESIS + 500));
+        let slow = Arc::new(ManualClock::new(GENESIS + 3));
+        let mut a = BFTCometRotation::with_oracle(minority.clone(), Vec::new(), 1, EpochOracle::with_clock(GENESIS, 12, fast));
+        let mut b = BFTCometRotation::with_oracle(minority.clone(), Vec::new(), 1, EpochOracle::with_clock(GENESIS, 12, slow));
+        assert_eq!(a.epoch(), (1, Some(GENESIS + 12)));
+
+        a.epoch_step(&[], HashSet::new());
+        b.epoch_step(&[], HashSet::new());
+        assert_eq!(a.epoch(), (2, Some(GENESIS + 24)));
+        assert_eq!(a.epoch(), b.epoch());
+
+        let clock = Arc::new(ManualClock::new(GENESIS));
+        let blocks = BFTCometRotation::with_oracle(minority, Vec::new(), 1, EpochOracle::block_driven(0, 10, clock));
+        assert_eq!(blocks.epoch(), (1, None));
+    }
+
+    #[test]
+    fn test_rotation_order_ignores_input_order() {
+        let ids = ["m2", "m0", "m1"];
+        let forward: Vec<Validator> = ids.iter().map(|id| make_validator(id)).collect();
+        let reversed: Vec<Validator> = ids.iter().rev().map(|id| make_validator(id)).collect();
+
+        let mut a = BFTCometRotation::new(forward, Vec::new(), 1, GENESIS);
+        let mut b = BFTCometRotation::new(reversed, Vec::new(), 1, GENESIS);
+        for _ in 0..4 {
+            a.rotate_minority();
+            b.rotate_minority();
+            assert_eq!(a.minority_committee, b.minority_committee);
+        }
+        assert_eq!(a.minority_committee.front().map(|v| v.id.as_str()), Some("m1"));
+    }
+
+    #[test]
+    fn test_epoch_step_chains_the_finalized_root() {
+        let minority: Vec<Validator> = (0..3).map(|i| make_validator(&format!("m{}", i))).collect();
+        let majority: Vec<Validator> = (0..50).map(|i| make_validator(&format!("o{}", i))).collect();
+        let mut rotation = BFTCometRotation::new(minority.clone(), majority.clone(), 1, GENESIS);
+        let mut other = BFTCometRotation::new(minority, majority, 1, GENESIS);
+
+        let root = "ab".repeat(32);
+        let attestations: Vec<_> = ["m0", "m1", "m2"].iter().map(|id| signed(id, &root)).collect();
+        rotation.epoch_step(&attestations, HashSet::new());
+        assert_eq!(rotation.finalized_root, [0xab; 32]);
+
+        // Another node closing on the same root picks the same majority member
+        other.set_finalized_root([0xab; 32]);
+        other.epoch += 1;
+        other.rotate_minority();
+        other.update_full_committee();
+        assert_eq!(rotation.current_committee(), other.current_committee());
+    }
+
+    #[test]
+    fn test_duties_are_recorded_without_finality() {
+        let minority: Vec<Validator> = (0..3).map(|i| make_validator(&format!("m{}", i))).collect();
+        let keys: HashMap<String, String> = minority.iter().map(|v| (v.id.clone(), v.public_key.clone())).collect();
+        let mut rotation = BFTCometRotation::new(minority, Vec::new(), 1, GENESIS);
+
+        // Only m0 attests: no epoch finalizes, yet m1 and m2 miss every duty
+        let mut downtime = Vec::new();
+        for _ in 0..10 {
+            let report = rotation.epoch_step(&[signed("m0", "h")], HashSet::new());
+            assert!(report.finality.is_none());
+            downtime = report.downtime;
+        }
+        assert_eq!(rotation.finalities().len(), 10);
+        assert!(rotation.finalities().values().all(|record| !record.finalized));
+
+        let offenders: Vec<&str> = downtime.iter().map(|evidence| evidence.offender()).collect();
+        assert_eq!(offenders, vec!["m1", "m2"]);
+
+        // The slasher re-counts the same misses from the kept record
+        let code = HashMap::new();
+        let ctx = EvidenceContext {
+            attestation_keys: &keys,
+            committee_size: 3,
+            finality_threshold: QuorumThreshold::TWO_THIRDS,
+            liveness: LivenessConfig::default(),
+            finalities: rotation.finalities(),
+            registered_code: &code,
+            verify_proof: never_verifies,
         };
+        assert!(downtime.iter().all(|evidence| evidence.verify(&ctx).is_ok()));
+    }
 
-        let attestations = vec![attestation.clone(); FULL_COMMITTEE_SIZE];
+    #[test]
+    fn test_threshold_is_configurable() {
+        let minority: Vec<Validator> = (0..3).map(|i| make_validator(&format!("m{}", i))).collect();
+        let mut rotation = BFTCometRotation::new(minority, Vec::new(), 1, GENESIS);
+        let attestations = vec![signed("m0", "h"), signed("m1", "h"), signed("m2", "other")];
 
-        assert!(rotation.validate_attestations(&attestations));
-        assert!(rotation.epoch_step(&attestations));
+        assert!(rotation.validate_attestations(&attestations)); // 2 of 3
+        rotation.set_finality_threshold(QuorumThreshold::new(3, 3).unwrap());
+        assert!(!rotation.validate_attestations(&attestations));
+    }
+
+    #[test]
+    fn test_jailed_validators_leave_the_committee() {
+        let minority: Vec<Validator> = (0..3).map(|i| make_validator(&format!("m{}", i))).collect();
+        let mut rotation = BFTCometRotation::new(minority, Vec::new(), 1, GENESIS);
+
+        rotation.set_excluded(["m0".to_string()].into_iter().collect());
+        assert_eq!(rotation.current_committee().len(), 2);
+
+        let detection = rotation.detect_slashable_validators(&[signed("m0", "h")]);
+        assert_eq!(detection.rejected[0].reason, AttestationRejection::NotCommitteeMember);
+
+        rotation.set_excluded(HashSet::new());
+        assert_eq!(rotation.current_committee().len(), 3);
+    }
+
+    #[test]
+    fn test_slashing_is_attributed_to_authenticated_signer() {
+        let minority: Vec<Validator> = (0..4).map(|i| make_validator(&format!("m{}", i))).collect();
+        let rotation = BFTCometRotation::new(minority, Vec::new(), 1, GENESIS);
+
+        let mut forged = signed("m0", "evil");
+        forged.validator_id = "m1".into(); // m0's signature claimed as m1's
+
+        let attestations = vec![
+            signed("m0", "good"),
+            signed("m1", "good"),
+            signed("m2", "good"),
+            signed("m3", "evil"),
+            signed("m3", "good"),       // Repeat from m3
+            signed("outsider", "good"), // Not in the committee
+            forged,
+        ];
+
+        let detection = rotation.detect_slashable_validators(&attestations);
+        assert_eq!(detection.quorum_hash.as_deref(), Some("good"));
+        assert_eq!(detection.slashable, vec!["m3".to_string()]);
+
+        let reasons: Vec<_> = detection.rejected.iter().map(|r| (r.validator_id.as_str(), r.reason.clone())).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                ("m3", AttestationRejection::Duplicate),
+                ("outsider", AttestationRejection::NotCommitteeMember),
+                ("m1", AttestationRejection::BadSignature),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_downtime_is_slashed_and_excluded_through_epoch_steps() {
+        let minority: Vec<Validator> = (0..3).map(|i| make_validator(&format!("m{}", i))).collect();
+        let keys: HashMap<String, String> = minority.iter().map(|v| (v.id.clone(), v.public_key.clone())).collect();
+        let mut rotation = BFTCometRotation::new(minority, Vec::new(), 1, GENESIS);
+        let mut slasher = Slasher::new();
+
+        // m2 never attests; m0 and m1 still finalize every epoch
+        let attestations = vec![signed("m0", "h"), signed("m1", "h")];
+        let mut downtime = Vec::new();
+        for _ in 0..10 {
+            assert!(downtime.is_empty());
+            downtime = rotation.epoch_step(&attestations, slasher.excluded()).downtime;
+        }
+        assert_eq!(downtime.len(), 1);
+
+        let code = HashMap::new();
+        let ctx = EvidenceContext {
+            attestation_keys: &keys,
+            committee_size: 3,
+            finality_threshold: QuorumThreshold::TWO_THIRDS,
+            liveness: LivenessConfig::default(),
+            finalities: rotation.finalities(),
+            registered_code: &code,
+            verify_proof: never_verifies,
+        };
+        let (epoch, _) = rotation.epoch();
+        slasher
+            .submit_evidence(&downtime[0], &ctx, &mut StakingPool::new(), &mut DelegationPool::new(), epoch)
+            .unwrap();
+
+        rotation.epoch_step(&attestations, slasher.excluded());
+        assert!(rotation.current_committee().iter().all(|v| v.id != "m2"));
     }
-      
 }
@@ -1,7 +1,6 @@
 // circuit_constants.rs
 // Domex zk onboarding constants shared across client, circuit, and validator logic
 
-use pasta_curves::Fp;
 
 /// Poseidon input arity used for vault identity hash: Poseidon(sk, vault_id, zk_node_id)
 pub const POSEIDON_IDENTITY_ARITY: usize = 3;
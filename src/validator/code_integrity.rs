@@ -1,7 +1,7 @@
 // src/validator/code_integrity.rs
 
 use std::collections::HashMap;
-use crate::types::validator::Validator;
+use crate::validator::types::bftcomet::Validator;
 use crate::common::poseidon_utils::poseidon_hash_bytes;
 
 /// Tracks code hash of each validator
@@ -0,0 +1,746 @@
+// ==========================================================
+// delegation_pool.rs — Domex Delegated Staking
+// ==========================================================
+//
+// Lets DOMEX holders who do not run validators bond to one.
+//
+// - Delegated stake counts toward the validator's weight in
+//   selection and stake-weighted quorum (`weights`)
+// - Rewards: commission first, the rest pro rata over the
+//   validator's own stake and every delegation (`split_rewards`)
+// - Slashes burn the same basis points from every delegation,
+//   from unbonding delegations and from redelegations that left
+//   at or after the offence epoch
+// - Commission changes once per epoch, by at most `max_change_bps`,
+//   itself capped at `MAX_COMMISSION_CHANGE_BPS`
+// - Only the validator sets its commission terms, signed with its
+//   registered attestation key
+// - Undelegating and redelegating follow the staking pool's
+//   unbonding period; redelegated stake cannot move on again until
+//   it matures, and undelegating it does not shed the source's slashes
+// - Delegated DOMEX sits in `DELEGATION_ESCROW` on the token ledger:
+//   delegating moves it in, claiming matured unbonding moves it out;
+//   slashed stake is never paid back out
+// - A delegator is its hex public key; delegating, undelegating and
+//   redelegating need its signature over the operation and its next
+//   nonce, so no one else can move its stake and no signature replays
+//
+
+use std::collections::HashMap;
+use crate::token::domex_token::DomexTokenLedger;
+use crate::validator::types::delegation_pool::{Commission, DelegationAction, DelegationError, Redelegation, RewardSplit};
+use crate::validator::types::staking_pool::UnbondingEntry;
+use crate::validator::attestation::{commission_hash, delegation_hash, verify_attestation_signature};
+use crate::validator::slashing::{penalty_amount, BPS_DENOMINATOR};
+use crate::validator::staking_pool::{Amount, StakingPool, DEFAULT_UNBONDING_EPOCHS};
+use crate::validator::types::validator_identity::ValidatorId;
+
+/// Largest per-epoch commission change a validator may allow itself (1%)
+pub const MAX_COMMISSION_CHANGE_BPS: u16 = 100;
+
+/// Ledger address holding bonded and unbonding delegations
+pub const DELEGATION_ESCROW: &str = "domex:delegation-escrow";
+
+/// Delegations bonded to one validator
+#[derive(Debug, Clone)]
+struct ValidatorDelegations {
+    commission: Commission,
+    delegations: HashMap<String, Amount>,
+}
+
+/// Delegations to all validators
+pub struct DelegationPool {
+    validators: HashMap<ValidatorId, ValidatorDelegations>,
+    unbonding: HashMap<(String, ValidatorId), Vec<UnbondingEntry>>, // (delegator, validator)
+    redelegations: Vec<Redelegation>,
+    unbonding_epochs: u64,
+    next_unbonding_id: u64,
+    nonces: HashMap<String, u64>, // Delegator → operations signed so far
+}
+
+impl DelegationPool {
+    pub fn new() -> Self {
+        Self::with_unbonding_period(DEFAULT_UNBONDING_EPOCHS)
+    }
+
+    /// Pool whose undelegations and redelegations mature after `unbonding_epochs`
+    pub fn with_unbonding_period(unbonding_epochs: u64) -> Self {
+        Self {
+            validators: HashMap::new(),
+            unbonding: HashMap::new(),
+            redelegations: Vec::new(),
+            unbonding_epochs,
+            next_unbonding_id: 0,
+            nonces: HashMap::new(),
+        }
+    }
+
+    /// Open a validator to delegation with its initial commission. `signature`
+    /// is the validator's `sign_commission` over the terms and `epoch`, checked
+    /// against its key in `keys` (`ValidatorRegistry::attestation_keys`).
+    pub fn register_validator(
+        &mut self,
+        validator: ValidatorId,
+        rate_bps: u16,
+        max_change_bps: u16,
+        epoch: u64,
+        keys: &HashMap<String, String>,
+        signature: &str,
+    ) -> Result<(), DelegationError> {
+        if self.validators.contains_key(&validator) {
+            return Err(DelegationError::AlreadyRegistered);
+        }
+        if rate_bps as u64 > BPS_DENOMINATOR {
+            return Err(DelegationError::RateTooHigh);
+        }
+        if max_change_bps > MAX_COMMISSION_CHANGE_BPS {
+            return Err(DelegationError::MaxChangeTooHigh { limit: MAX_COMMISSION_CHANGE_BPS });
+        }
+        Self::check_signed(&validator, rate_bps, max_change_bps, epoch, keys, signature)?;
+        self.validators.insert(
+            validator,
+            ValidatorDelegations {
+                commission: Commission { rate_bps, max_change_bps, last_change_epoch: None },
+                delegations: HashMap::new(),
+            },
+        );
+        Ok(())
+    }
+
+    pub fn commission(&self, validator: &str) -> Option<Commission> {
+        self.validators.get(validator).map(|v| v.commission)
+    }
+
+    /// Change the commission rate: once per epoch, within `max_change_bps`,
+    /// signed by the validator like its registration
+    pub fn set_commission(
+        &mut self,
+        validator: &str,
+        rate_bps: u16,
+        epoch: u64,
+        keys: &HashMap<String, String>,
+        signature: &str,
+    ) -> Result<(), DelegationError> {
+        let commission = &mut self
+            .validators
+            .get_mut(validator)
+            .ok_or(DelegationError::UnknownValidator)?
+            .commission;
+
+        Self::check_signed(validator, rate_bps, commission.max_change_bps, epoch, keys, signature)?;
+
+        if rate_bps as u64 > BPS_DENOMINATOR {
+            return Err(DelegationError::RateTooHigh);
+        }
+        if commission.last_change_epoch == Some(epoch) {
+            return Err(DelegationError::CommissionChangedThisEpoch);
+        }
+        if rate_bps.abs_diff(commission.rate_bps) > commission.max_change_bps {
+            return Err(DelegationError::CommissionChangeTooLarge { max_change_bps: commission.max_change_bps });
+        }
+
+        commission.rate_bps = rate_bps;
+        commission.last_change_epoch = Some(epoch);
+        Ok(())
+    }
+
+    /// Next nonce `delegator` must sign a delegation operation with
+    pub fn nonce(&self, delegator: &str) -> u64 {
+        self.nonces.get(delegator).copied().unwrap_or(0)
+    }
+
+    /// Bond `amount` from `delegator` to `validator`, moving it from the
+    /// delegator's ledger balance into escrow. `signature` is the delegator's
+    /// `sign_delegation` over the operation at its current nonce.
+    pub fn delegate(
+        &mut self,
+        ledger: &mut DomexTokenLedger,
+        delegator: &str,
+        validator: &str,
+        amount: Amount,
+        signature: &str,
+    ) -> Result<(), DelegationError> {
+        self.check_delegator_signed(delegator, DelegationAction::Delegate, validator, "", amount, signature)?;
+        let bonded = self.credited(delegator, validator, amount)?;
+        ledger
+            .transfer(delegator, DELEGATION_ESCROW, amount)
+            .map_err(|_| DelegationError::InsufficientBalance)?;
+
+        self.set_delegation(delegator, validator, bonded);
+        self.bump_nonce(delegator);
+        Ok(())
+    }
+
+    /// Start unbonding a delegation; claimable after the unbonding period.
+    /// Signed by the delegator like `delegate`. Returns the unbonding entry ID.
+    pub fn undelegate(
+        &mut self,
+        delegator: &str,
+        validator: &str,
+        amount: Amount,
+        epoch: u64,
+        signature: &str,
+    ) -> Result<u64, DelegationError> {
+        self.check_delegator_signed(delegator, DelegationAction::Undelegate, validator, "", amount, signature)?;
+        self.take_delegation(delegator, validator, amount)?;
+        self.bump_nonce(delegator);
+
+        let id = self.next_unbonding_id;
+        self.next_unbonding_id += 1;
+        self.unbonding
+            .entry((delegator.to_string(), validator.to_string()))
+            .or_default()
+            .push(UnbondingEntry {
+                id,
+                amount,
+                start_epoch: epoch,
+                mature_epoch: epoch + self.unbonding_epochs,
+            });
+        Ok(id)
+    }
+
+    /// Move a delegation to another validator at once, signed by the delegator
+    /// like `delegate`; the stake stays in escrow. The moved stake still
+    /// answers for `from`'s offences until the unbonding period has passed, so
+    /// stake redelegated into `from` must mature before it can move again.
+    pub fn redelegate(
+        &mut self,
+        delegator: &str,
+        from: &str,
+        to: &str,
+        amount: Amount,
+        epoch: u64,
+        signature: &str,
+    ) -> Result<(), DelegationError> {
+        self.check_delegator_signed(delegator, DelegationAction::Redelegate, from, to, amount, signature)?;
+        if from == to {
+            return Err(DelegationError::SameValidator);
+        }
+        let bonded = self.credited(delegator, to, amount)?;
+        if self
+            .redelegations
+            .iter()
+            .any(|r| r.delegator == delegator && r.to == from && epoch < r.mature_epoch)
+        {
+            return Err(DelegationError::RedelegationPending);
+        }
+        self.take_delegation(delegator, from, amount)?;
+        self.set_delegation(delegator, to, bonded);
+        self.bump_nonce(delegator);
+
+        self.redelegations.push(Redelegation {
+            delegator: delegator.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            amount,
+            start_epoch: epoch,
+            mature_epoch: epoch + self.unbonding_epochs,
+        });
+        Ok(())
+    }
+
+    /// Pay every matured undelegation of `delegator` out of escrow to its
+    /// ledger balance; returns the amount paid out
+    pub fn claim_unbonded(
+        &mut self,
+        ledger: &mut DomexTokenLedger,
+        delegator: &str,
+        epoch: u64,
+    ) -> Result<Amount, DelegationError> {
+        let mature = |entry: &UnbondingEntry| entry.is_mature(epoch);
+        let released: Amount = self
+            .unbonding
+            .iter()
+            .filter(|((owner, _), _)| owner == delegator)
+            .flat_map(|(_, entries)| entries.iter().filter(|entry| mature(entry)))
+            .map(|entry| entry.amount)
+            .sum();
+        if released > 0 {
+            ledger
+                .transfer(DELEGATION_ESCROW, delegator, released)
+                .map_err(|_| DelegationError::InsufficientBalance)?;
+        }
+
+        for ((owner, _), entries) in self.unbonding.iter_mut() {
+            if owner == delegator {
+                entries.retain(|entry| !mature(entry));
+            }
+        }
+        self.unbonding.retain(|_, entries| !entries.is_empty());
+        self.redelegations
+            .retain(|r| r.delegator != delegator || epoch < r.mature_epoch);
+        Ok(released)
+    }
+
+    pub fn delegation(&self, delegator: &str, validator: &str) -> Amount {
+        self.validators
+            .get(validator)
+            .and_then(|v| v.delegations.get(delegator))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Total stake delegated to a validator
+    pub fn delegated_to(&self, validator: &str) -> Amount {
+        self.validators
+            .get(validator)
+            .map_or(0, |v| v.delegations.values().fold(0, |total, a| total.saturating_add(*a)))
+    }
+
+    /// Pending undelegations of `delegator` from `validator`, oldest first
+    pub fn unbonding_entries(&self, delegator: &str, validator: &str) -> &[UnbondingEntry] {
+        self.unbonding
+            .get(&(delegator.to_string(), validator.to_string()))
+            .map_or(&[], |entries| entries.as_slice())
+    }
+
+    /// Redelegations still slashable for their source validator
+    pub fn pending_redelegations(&self, delegator: &str) -> Vec<&Redelegation> {
+        self.redelegations.iter().filter(|r| r.delegator == delegator).collect()
+    }
+
+    /// Selection and quorum weight: bonded self-stake plus delegations
+    pub fn weight(&self, staking: &StakingPool, validator: &str) -> Amount {
+        staking.get_balance(&validator.to_string()).saturating_add(self.delegated_to(validator))
+    }
+
+    /// Weight of every validator open to delegation
+    pub fn weights(&self, staking: &StakingPool) -> HashMap<ValidatorId, Amount> {
+        self.validators
+            .keys()
+            .map(|id| (id.clone(), self.weight(staking, id)))
+            .collect()
+    }
+
+    /// Divide `reward` earned by `validator`: commission first, then pro rata
+    /// over `self_stake` and every delegation (rounding dust stays with the validator)
+    pub fn split_rewards(&self, validator: &str, reward: Amount, self_stake: Amount) -> RewardSplit {
+        let (rate_bps, mut delegations) = match self.validators.get(validator) {
+            Some(v) => (
+                v.commission.rate_bps,
+                v.delegations.iter().map(|(d, a)| (d.clone(), *a)).collect::<Vec<_>>(),
+            ),
+            None => (0, Vec::new()),
+        };
+        delegations.sort();
+
+        let commission = penalty_amount(reward, rate_bps);
+        let shared = reward - commission;
+        let total_stake = self_stake as u128 + delegations.iter().map(|(_, a)| *a as u128).sum::<u128>();
+
+        let delegators: Vec<(String, Amount)> = if total_stake == 0 {
+            Vec::new()
+        } else {
+            delegations
+                .into_iter()
+                .map(|(delegator, amount)| {
+                    (delegator, (shared as u128 * amount as u128 / total_stake) as Amount)
+                })
+                .collect()
+        };
+        let paid: Amount = delegators.iter().map(|(_, a)| *a).sum();
+
+        RewardSplit {
+            validator_id: validator.to_string(),
+            commission,
+            self_share: shared - paid,
+            delegators,
+        }
+    }
+
+    /// Burn `slash_bps` from every delegation to `validator`, from its
+    /// undelegations and from redelegations away from it that started at or
+    /// after `offence_epoch` (from the new delegation first, then from its
+    /// undelegations since the redelegation). Returns the amount burned.
+    pub fn slash(&mut self, validator: &str, slash_bps: u16, offence_epoch: u64) -> Amount {
+        let mut burned = 0;
+
+        if let Some(v) = self.validators.get_mut(validator) {
+            for amount in v.delegations.values_mut() {
+                let penalty = penalty_amount(*amount, slash_bps);
+                *amount -= penalty;
+                burned += penalty;
+            }
+        }
+
+        for ((_, from), entries) in self.unbonding.iter_mut() {
+            if from != validator {
+                continue;
+            }
+            for entry in entries.iter_mut().filter(|e| e.start_epoch >= offence_epoch) {
+                let penalty = penalty_amount(entry.amount, slash_bps);
+                entry.amount -= penalty;
+                burned += penalty;
+            }
+        }
+
+        let redelegations: Vec<usize> = self
+            .redelegations
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.from == validator && r.start_epoch >= offence_epoch)
+            .map(|(i, _)| i)
+            .collect();
+        for i in redelegations {
+            let r = self.redelegations[i].clone();
+            let mut due = penalty_amount(r.amount, slash_bps);
+
+            if let Some(amount) = self
+                .validators
+                .get_mut(&r.to)
+                .and_then(|v| v.delegations.get_mut(&r.delegator))
+            {
+                let penalty = due.min(*amount);
+                *amount -= penalty;
+                due -= penalty;
+            }
+            // Whatever was undelegated from `to` since the move still pays
+            if let Some(entries) = self.unbonding.get_mut(&(r.delegator.clone(), r.to.clone())) {
+                for entry in entries.iter_mut().filter(|e| e.start_epoch >= r.start_epoch) {
+                    let penalty = due.min(entry.amount);
+                    entry.amount -= penalty;
+                    due -= penalty;
+                }
+            }
+
+            let penalty = penalty_amount(r.amount, slash_bps) - due;
+            self.redelegations[i].amount -= penalty;
+            burned += penalty;
+        }
+
+        burned
+    }
+
+    /// Checks `signature` is `validator`'s signature over its commission terms
+    fn check_signed(
+        validator: &str,
+        rate_bps: u16,
+        max_change_bps: u16,
+        epoch: u64,
+        keys: &HashMap<String, String>,
+        signature: &str,
+    ) -> Result<(), DelegationError> {
+        let terms = commission_hash(rate_bps, max_change_bps, epoch);
+        match keys.get(validator) {
+            Some(key) if verify_attestation_signature(key, validator, &terms, signature) => Ok(()),
+            _ => Err(DelegationError::BadSignature),
+        }
+    }
+
+    /// Checks `signature` is `delegator`'s signature over the operation at its current nonce
+    fn check_delegator_signed(
+        &self,
+        delegator: &str,
+        action: DelegationAction,
+        validator: &str,
+        to: &str,
+        amount: Amount,
+        signature: &str,
+    ) -> Result<(), DelegationError> {
+        let operation = delegation_hash(action, validator, to, amount, self.nonce(delegator));
+        if verify_attestation_signature(delegator, delegator, &operation, signature) {
+            Ok(())
+        } else {
+            Err(DelegationError::BadSignature)
+        }
+    }
+
+    fn bump_nonce(&mut self, delegator: &str) {
+        *self.nonces.entry(delegator.to_string()).or_insert(0) += 1;
+    }
+
+    /// `delegator`'s delegation to `validator` after adding `amount`, without changing it
+    fn credited(&self, delegator: &str, validator: &str, amount: Amount) -> Result<Amount, DelegationError> {
+        if amount == 0 {
+            return Err(DelegationError::ZeroAmount);
+        }
+        if !self.validators.contains_key(validator) {
+            return Err(DelegationError::UnknownValidator);
+        }
+        self.delegation(delegator, validator)
+            .checked_add(amount)
+            .ok_or(DelegationError::Overflow)
+    }
+
+    fn set_delegation(&mut self, delegator: &str, validator: &str, amount: Amount) {
+        if let Some(v) = self.validators.get_mut(validator) {
+            v.delegations.insert(delegator.to_string(), amount);
+        }
+    }
+
+    fn take_delegation(&mut self, delegator: &str, validator: &str, amount: Amount) -> Result<(), DelegationError> {
+        if amount == 0 {
+            return Err(DelegationError::ZeroAmount);
+        }
+        let delegations = &mut self
+            .validators
+            .get_mut(validator)
+            .ok_or(DelegationError::UnknownValidator)?
+            .delegations;
+        let current = delegations
+            .get_mut(delegator)
+            .filter(|current| **current >= amount)
+            .ok_or(DelegationError::InsufficientDelegation)?;
+
+        *current -= amount;
+        if *current == 0 {
+            delegations.remove(delegator);
+        }
+        Ok(())
+    }
+}
+
+impl Default for DelegationPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Signed delegation operations for tests, by delegator name
+#[cfg(test)]
+pub mod fixtures {
+    use super::*;
+    use crate::common::signing::fixtures::key;
+    use crate::common::signing::public_key_hex;
+    use crate::validator::attestation::sign_delegation;
+
+    /// Delegator account of `name`: the public key of `key(name)`
+    pub fn delegator(name: &str) -> String {
+        public_key_hex(&key(name))
+    }
+
+    fn sign(
+        pool: &DelegationPool,
+        name: &str,
+        action: DelegationAction,
+        validator: &str,
+        to: &str,
+        amount: Amount,
+    ) -> String {
+        let id = delegator(name);
+        sign_delegation(&key(name), &id, action, validator, to, amount, pool.nonce(&id))
+    }
+
+    /// `name` bonds `amount` to `validator` from its ledger balance
+    pub fn delegate(
+        pool: &mut DelegationPool,
+        ledger: &mut DomexTokenLedger,
+        name: &str,
+        validator: &str,
+        amount: Amount,
+    ) -> Result<(), DelegationError> {
+        let signature = sign(pool, name, DelegationAction::Delegate, validator, "", amount);
+        pool.delegate(ledger, &delegator(name), validator, amount, &signature)
+    }
+
+    pub fn undelegate(
+        pool: &mut DelegationPool,
+        name: &str,
+        validator: &str,
+        amount: Amount,
+        epoch: u64,
+    ) -> Result<u64, DelegationError> {
+        let signature = sign(pool, name, DelegationAction::Undelegate, validator, "", amount);
+        pool.undelegate(&delegator(name), validator, amount, epoch, &signature)
+    }
+
+    pub fn redelegate(
+        pool: &mut DelegationPool,
+        name: &str,
+        from: &str,
+        to: &str,
+        amount: Amount,
+        epoch: u64,
+    ) -> Result<(), DelegationError> {
+        let signature = sign(pool, name, DelegationAction::Redelegate, from, to, amount);
+        pool.redelegate(&delegator(name), from, to, amount, epoch, &signature)
+    }
+
+    /// Ledger holding `amount` for each named delegator
+    pub fn funded_ledger(names: &[&str], amount: Amount) -> DomexTokenLedger {
+        let mut ledger = DomexTokenLedger::new("genesis");
+        for name in names {
+            ledger.transfer("genesis", &delegator(name), amount).unwrap();
+        }
+        ledger
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::fixtures::{delegate, delegator, funded_ledger, redelegate, undelegate};
+    use crate::common::signing::fixtures::key;
+    use crate::common::signing::public_key_hex;
+    use crate::validator::attestation::{sign_commission, sign_delegation};
+
+    fn keys() -> HashMap<String, String> {
+        ["val", "other", "third"]
+            .iter()
+            .map(|id| (id.to_string(), public_key_hex(&key(id))))
+            .collect()
+    }
+
+    fn register(pool: &mut DelegationPool, id: &str, rate_bps: u16) {
+        let signature = sign_commission(&key(id), id, rate_bps, 100, 0);
+        pool.register_validator(id.into(), rate_bps, 100, 0, &keys(), &signature).unwrap();
+    }
+
+    fn set_commission(pool: &mut DelegationPool, id: &str, rate_bps: u16, epoch: u64) -> Result<(), DelegationError> {
+        let signature = sign_commission(&key(id), id, rate_bps, 100, epoch);
+        pool.set_commission(id, rate_bps, epoch, &keys(), &signature)
+    }
+
+    fn pool() -> (DelegationPool, DomexTokenLedger) {
+        let mut ledger = funded_ledger(&["alice", "bob"], 10_000);
+        let mut pool = DelegationPool::with_unbonding_period(10);
+        register(&mut pool, "val", 1_000);
+        register(&mut pool, "other", 500);
+        delegate(&mut pool, &mut ledger, "alice", "val", 3_000).unwrap();
+        delegate(&mut pool, &mut ledger, "bob", "val", 1_000).unwrap();
+        (pool, ledger)
+    }
+
+    #[test]
+    fn test_rewards_split_after_commission() {
+        let (pool, _) = pool();
+        let (alice, bob) = (delegator("alice"), delegator("bob"));
+
+        // 10% commission of 1_000, then 900 over 4_000 self + 4_000 delegated
+        let split = pool.split_rewards("val", 1_000, 4_000);
+        assert_eq!(split.commission, 100);
+        let mut expected = vec![(alice, 337), (bob, 112)];
+        expected.sort();
+        assert_eq!(split.delegators, expected);
+        assert_eq!(split.self_share, 451);
+        assert_eq!(split.validator_total() + 337 + 112, 1_000);
+
+        let mut staking = StakingPool::new();
+        staking.deposit("val".into(), 4_000);
+        assert_eq!(pool.weight(&staking, "val"), 8_000);
+    }
+
+    #[test]
+    fn test_commission_change_is_bounded_per_epoch() {
+        let (mut pool, _) = pool();
+        assert_eq!(
+            set_commission(&mut pool, "val", 1_200, 3),
+            Err(DelegationError::CommissionChangeTooLarge { max_change_bps: 100 })
+        );
+        set_commission(&mut pool, "val", 1_100, 3).unwrap();
+        assert_eq!(set_commission(&mut pool, "val", 1_150, 3), Err(DelegationError::CommissionChangedThisEpoch));
+        set_commission(&mut pool, "val", 1_200, 4).unwrap();
+        assert_eq!(pool.commission("val").unwrap().rate_bps, 1_200);
+    }
+
+    #[test]
+    fn test_only_the_validator_sets_its_terms() {
+        let (mut pool, _) = pool();
+
+        // Someone else cannot open "third" to delegation, nor change "val"'s rate
+        let squatted = sign_commission(&key("val"), "third", 0, 100, 0);
+        assert_eq!(
+            pool.register_validator("third".into(), 0, 100, 0, &keys(), &squatted),
+            Err(DelegationError::BadSignature)
+        );
+        let forged = sign_commission(&key("other"), "val", 1_100, 100, 3);
+        assert_eq!(pool.set_commission("val", 1_100, 3, &keys(), &forged), Err(DelegationError::BadSignature));
+
+        // A validator cannot allow itself unbounded jumps either
+        let signature = sign_commission(&key("third"), "third", 0, 10_000, 0);
+        assert_eq!(
+            pool.register_validator("third".into(), 0, 10_000, 0, &keys(), &signature),
+            Err(DelegationError::MaxChangeTooHigh { limit: MAX_COMMISSION_CHANGE_BPS })
+        );
+    }
+
+    #[test]
+    fn test_delegation_beyond_balance_is_refused() {
+        let (mut pool, mut ledger) = pool();
+        let alice = delegator("alice");
+        assert_eq!(
+            delegate(&mut pool, &mut ledger, "alice", "other", 7_001),
+            Err(DelegationError::InsufficientBalance)
+        );
+        assert_eq!(pool.delegation(&alice, "other"), 0);
+        assert_eq!(ledger.balance_of(&alice), 7_000);
+    }
+
+    #[test]
+    fn test_tokens_move_through_escrow() {
+        let (mut pool, mut ledger) = pool();
+        let bob = delegator("bob");
+        assert_eq!(ledger.balance_of(DELEGATION_ESCROW), 4_000);
+        assert_eq!(ledger.balance_of(&bob), 9_000);
+
+        undelegate(&mut pool, "bob", "val", 400, 5).unwrap();
+        assert_eq!(pool.claim_unbonded(&mut ledger, &bob, 14), Ok(0));
+        assert_eq!(pool.claim_unbonded(&mut ledger, &bob, 15), Ok(400));
+        assert_eq!(ledger.balance_of(&bob), 9_400);
+        assert_eq!(ledger.balance_of(DELEGATION_ESCROW), 3_600);
+    }
+
+    #[test]
+    fn test_operations_need_the_delegator_signature() {
+        let (mut pool, mut ledger) = pool();
+        let alice = delegator("alice");
+
+        // Bob cannot move Alice's stake, nor spend her balance
+        let nonce = pool.nonce(&alice);
+        let forged = sign_delegation(&key("bob"), &alice, DelegationAction::Undelegate, "val", "", 3_000, nonce);
+        assert_eq!(pool.undelegate(&alice, "val", 3_000, 5, &forged), Err(DelegationError::BadSignature));
+        let forged = sign_delegation(&key("bob"), &alice, DelegationAction::Delegate, "other", "", 100, nonce);
+        assert_eq!(pool.delegate(&mut ledger, &alice, "other", 100, &forged), Err(DelegationError::BadSignature));
+
+        // A used signature does not replay: the nonce has moved on
+        let signature = sign_delegation(&key("alice"), &alice, DelegationAction::Redelegate, "val", "other", 500, nonce);
+        pool.redelegate(&alice, "val", "other", 500, 5, &signature).unwrap();
+        assert_eq!(
+            pool.redelegate(&alice, "val", "other", 500, 5, &signature),
+            Err(DelegationError::BadSignature)
+        );
+        assert_eq!(pool.delegation(&alice, "other"), 500);
+    }
+
+    #[test]
+    fn test_slash_reaches_unbonding_and_redelegated_stake() {
+        let (mut pool, mut ledger) = pool();
+        let (alice, bob) = (delegator("alice"), delegator("bob"));
+        undelegate(&mut pool, "bob", "val", 1_000, 5).unwrap();
+        redelegate(&mut pool, "alice", "val", "other", 1_000, 6).unwrap();
+
+        // Offence at epoch 5: everything that left "val" since then still pays 10%
+        let burned = pool.slash("val", 1_000, 5);
+        assert_eq!(burned, 200 + 100 + 100);
+        assert_eq!(pool.delegation(&alice, "val"), 1_800);
+        assert_eq!(pool.delegation(&alice, "other"), 900);
+        assert_eq!(pool.unbonding_entries(&bob, "val")[0].amount, 900);
+
+        assert_eq!(pool.claim_unbonded(&mut ledger, &bob, 14), Ok(0));
+        assert_eq!(pool.claim_unbonded(&mut ledger, &bob, 15), Ok(900));
+        assert_eq!(pool.claim_unbonded(&mut ledger, &alice, 16), Ok(0));
+        assert!(pool.pending_redelegations(&alice).is_empty());
+    }
+
+    #[test]
+    fn test_redelegated_stake_cannot_escape_the_source_slash() {
+        let (mut pool, _) = pool();
+        register(&mut pool, "third", 0);
+        redelegate(&mut pool, "alice", "val", "other", 1_000, 6).unwrap();
+
+        // No second hop while the first is still slashable for "val"
+        assert_eq!(
+            redelegate(&mut pool, "alice", "other", "third", 1_000, 7),
+            Err(DelegationError::RedelegationPending)
+        );
+
+        // Undelegating the moved stake leaves it slashable for "val"
+        undelegate(&mut pool, "alice", "other", 1_000, 7).unwrap();
+        let burned = pool.slash("val", 1_000, 5);
+        assert_eq!(burned, 200 + 100 + 100);
+        assert_eq!(pool.unbonding_entries(&delegator("alice"), "other")[0].amount, 900);
+
+        redelegate(&mut pool, "bob", "val", "other", 500, 6).unwrap();
+        redelegate(&mut pool, "bob", "other", "third", 500, 16).unwrap();
+    }
+}
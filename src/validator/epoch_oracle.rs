@@ -8,89 +8,230 @@
 // It tracks epoch progression, validator finality deadlines, and
 // signals when validator proofs must be rotated or finalized.
 //
-// Epochs are aligned to real-world UTC intervals (e.g. 12s blocks).
+// Epochs are derived from a shared anchor, never from when a node
+// started: either wall time since the genesis timestamp (through an
+// injectable `Clock`), or finalized block height since genesis.
 // Finality is governed by epoch timers and proof quorum thresholds.
 //
 
-use chrono::{Utc, Duration};
+use std::sync::Arc;
+
+use crate::common::clock::{system_clock, Clock};
+use crate::validator::types::epoch_oracle::EpochSource;
 
 /// Epoch parameters
 pub const EPOCH_INTERVAL_SECONDS: u64 = 12;  // e.g., 12s finality epochs
 pub const FINALITY_TIMEOUT_SECONDS: u64 = 36;  // max 3 epochs delay
 
+/// Epochs a validator may miss before it counts as late
+pub const FINALITY_TIMEOUT_EPOCHS: u64 = FINALITY_TIMEOUT_SECONDS / EPOCH_INTERVAL_SECONDS;
+
 /// Represents the Domex epoch clock
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct EpochOracle {
-    pub current_epoch: u64,
-    pub epoch_start_ts: i64, // Unix timestamp
+    pub current_epoch: u64, // Last epoch this node has processed
+    source: EpochSource,
+    clock: Arc<dyn Clock>,
+    finalized_height: u64,  // Latest finalized block seen (block-driven mode)
 }
 
 impl EpochOracle {
-    /// Initialize with the current UTC time
-    pub fn new() -> Self {
-        let now = Utc::now().timestamp();
-        Self {
+    /// Wall-clock epochs of `EPOCH_INTERVAL_SECONDS` since `genesis_ts`
+    pub fn new(genesis_ts: u64) -> Self {
+        Self::with_clock(genesis_ts, EPOCH_INTERVAL_SECONDS, system_clock())
+    }
+
+    /// Wall-clock epochs read from `clock`
+    pub fn with_clock(genesis_ts: u64, interval_secs: u64, clock: Arc<dyn Clock>) -> Self {
+        Self::from_source(
+            EpochSource::WallClock {
+                genesis_ts,
+                interval_secs: interval_secs.max(1),
+            },
+            clock,
+        )
+    }
+
+    /// Epochs driven by finalized block height
+    pub fn block_driven(genesis_height: u64, blocks_per_epoch: u64, clock: Arc<dyn Clock>) -> Self {
+        Self::from_source(
+            EpochSource::BlockHeight {
+                genesis_height,
+                blocks_per_epoch: blocks_per_epoch.max(1),
+            },
+            clock,
+        )
+    }
+
+    fn from_source(source: EpochSource, clock: Arc<dyn Clock>) -> Self {
+        let mut oracle = Self {
             current_epoch: 0,
-            epoch_start_ts: now,
+            source,
+            clock,
+            finalized_height: 0,
+        };
+        oracle.current_epoch = oracle.network_epoch();
+        oracle
+    }
+
+    pub fn source(&self) -> EpochSource {
+        self.source
+    }
+
+    /// Current Unix time from the injected clock
+    pub fn now(&self) -> u64 {
+        self.clock.now_unix()
+    }
+
+    /// Epoch containing Unix time `ts` (wall-clock mode)
+    pub fn epoch_at_time(&self, ts: u64) -> Option<u64> {
+        match self.source {
+            EpochSource::WallClock { genesis_ts, interval_secs } => {
+                Some(ts.saturating_sub(genesis_ts) / interval_secs)
+            }
+            EpochSource::BlockHeight { .. } => None,
+        }
+    }
+
+    /// Epoch containing block `height` (block-driven mode)
+    pub fn epoch_at_height(&self, height: u64) -> Option<u64> {
+        match self.source {
+            EpochSource::BlockHeight { genesis_height, blocks_per_epoch } => {
+                Some(height.saturating_sub(genesis_height) / blocks_per_epoch)
+            }
+            EpochSource::WallClock { .. } => None,
+        }
+    }
+
+    /// Epoch the network is in right now, whatever this node has processed
+    pub fn network_epoch(&self) -> u64 {
+        match self.source {
+            EpochSource::WallClock { .. } => self.epoch_at_time(self.now()),
+            EpochSource::BlockHeight { .. } => self.epoch_at_height(self.finalized_height),
         }
+        .unwrap_or(0)
+    }
+
+    /// Record a finalized block (ignored if older than the latest seen)
+    pub fn on_finalized_block(&mut self, height: u64) {
+        self.finalized_height = self.finalized_height.max(height);
     }
 
     /// Returns true if it's time to transition to next epoch
     pub fn is_epoch_expired(&self) -> bool {
-        let now = Utc::now().timestamp();
-        (now - self.epoch_start_ts) >= EPOCH_INTERVAL_SECONDS as i64
+        self.network_epoch() > self.current_epoch
     }
 
     /// Advances to the next epoch
     pub fn next_epoch(&mut self) {
         self.current_epoch += 1;
-        self.epoch_start_ts = Utc::now().timestamp();
     }
 
-    /// Checks if a validator is overdue (missed quorum window)
+    /// Catches up to the network epoch, returning how many epochs were skipped over
+    pub fn sync(&mut self) -> u64 {
+        let target = self.network_epoch();
+        let advanced = target.saturating_sub(self.current_epoch);
+        self.current_epoch = self.current_epoch.max(target);
+        advanced
+    }
+
+    /// Checks if a validator is overdue (missed quorum window):
+    /// a full `FINALITY_TIMEOUT_EPOCHS` behind the current epoch
     pub fn is_validator_late(&self, last_active_epoch: u64) -> bool {
         if self.current_epoch <= last_active_epoch {
             return false;
         }
 
         let delta = self.current_epoch - last_active_epoch;
-        delta * EPOCH_INTERVAL_SECONDS > FINALITY_TIMEOUT_SECONDS
+        delta >= FINALITY_TIMEOUT_EPOCHS
+    }
+
+    /// Unix time at which `epoch` starts (wall-clock mode)
+    pub fn epoch_start_ts(&self, epoch: u64) -> Option<u64> {
+        match self.source {
+            EpochSource::WallClock { genesis_ts, interval_secs } => Some(genesis_ts + epoch * interval_secs),
+            EpochSource::BlockHeight { .. } => None,
+        }
     }
 
-    /// Returns seconds left in current epoch
-    pub fn time_remaining(&self) -> i64 {
-        let now = Utc::now().timestamp();
-        let end_ts = self.epoch_start_ts + EPOCH_INTERVAL_SECONDS as i64;
-        (end_ts - now).max(0)
+    /// Returns seconds left in the network's current epoch (wall-clock mode)
+    pub fn time_remaining(&self) -> Option<u64> {
+        let end_ts = self.epoch_start_ts(self.network_epoch() + 1)?;
+        Some(end_ts.saturating_sub(self.now()))
+    }
+
+    /// Returns finalized blocks left in the network's current epoch (block-driven mode)
+    pub fn blocks_remaining(&self) -> Option<u64> {
+        match self.source {
+            EpochSource::BlockHeight { genesis_height, blocks_per_epoch } => {
+                let next_start = genesis_height + (self.network_epoch() + 1) * blocks_per_epoch;
+                Some(next_start.saturating_sub(self.finalized_height))
+            }
+            EpochSource::WallClock { .. } => None,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::clock::ManualClock;
+
+    const GENESIS: u64 = 1_700_000_000;
 
     #[test]
     fn test_epoch_oracle_basics() {
-        let mut oracle = EpochOracle::new();
+        let clock = Arc::new(ManualClock::new(GENESIS + 5));
+        let mut oracle = EpochOracle::with_clock(GENESIS, EPOCH_INTERVAL_SECONDS, clock.clone());
         let start_epoch = oracle.current_epoch;
 
-        assert_eq!(oracle.is_epoch_expired(), false);
-        assert_eq!(oracle.time_remaining() <= EPOCH_INTERVAL_SECONDS as i64, true);
+        assert_eq!(start_epoch, 0);
+        assert!(!oracle.is_epoch_expired());
+        assert_eq!(oracle.time_remaining(), Some(EPOCH_INTERVAL_SECONDS - 5));
 
-        // Simulate expiry by manually adjusting timestamp
-        oracle.epoch_start_ts -= EPOCH_INTERVAL_SECONDS as i64 + 1;
-        assert_eq!(oracle.is_epoch_expired(), true);
+        clock.advance(EPOCH_INTERVAL_SECONDS);
+        assert!(oracle.is_epoch_expired());
 
         oracle.next_epoch();
         assert_eq!(oracle.current_epoch, start_epoch + 1);
+        assert!(!oracle.is_epoch_expired());
+    }
+
+    #[test]
+    fn test_nodes_started_apart_agree_on_epoch() {
+        let clock = Arc::new(ManualClock::new(GENESIS + 100));
+        let early = EpochOracle::with_clock(GENESIS, EPOCH_INTERVAL_SECONDS, clock.clone());
+
+        clock.set(GENESIS + 1_000);
+        let mut late = EpochOracle::with_clock(GENESIS, EPOCH_INTERVAL_SECONDS, clock.clone());
+
+        assert_eq!(early.network_epoch(), late.network_epoch());
+        assert_eq!(late.current_epoch, 1_000 / EPOCH_INTERVAL_SECONDS);
+        assert_eq!(late.sync(), 0);
+    }
+
+    #[test]
+    fn test_block_driven_epochs() {
+        let clock = Arc::new(ManualClock::new(GENESIS));
+        let mut oracle = EpochOracle::block_driven(100, 10, clock);
+
+        oracle.on_finalized_block(109);
+        assert!(!oracle.is_epoch_expired());
+        assert_eq!(oracle.blocks_remaining(), Some(1));
+
+        oracle.on_finalized_block(135);
+        oracle.on_finalized_block(120); // Stale block is ignored
+        assert_eq!(oracle.sync(), 3);
+        assert_eq!(oracle.current_epoch, 3);
+        assert_eq!(oracle.time_remaining(), None);
     }
 
     #[test]
     fn test_validator_lateness() {
-        let mut oracle = EpochOracle::new();
+        let mut oracle = EpochOracle::new(GENESIS);
         oracle.current_epoch = 10;
 
-        assert_eq!(oracle.is_validator_late(9), false);
-        assert_eq!(oracle.is_validator_late(7), true);
+        assert!(!oracle.is_validator_late(9));
+        assert!(oracle.is_validator_late(7));
     }
 }
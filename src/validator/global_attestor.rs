@@ -1,151 +1,433 @@
 // src/validator/global_attestor.rs
 
-use std::collections::{HashMap, HashSet};
-use chrono::Utc;
-use crate::validator::attestation::ProofAttestation;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use crate::validator::attestation::{verify_attestation_signature, vote_hash, ProofAttestation};
 use crate::validator::slashing_engine::SlashingEngine;
-use crate::types::bftcomet::Validator;
-use crate::common::poseidon_utils::poseidon_hash_many;
+use crate::common::poseidon_hasher::PoseidonHasher;
+use crate::matching::core::poseidon_utils::string_to_limbs;
+use crate::validator::poseidon_utils::u64_to_fp;
+use pasta_curves::group::ff::PrimeField;
+use crate::validator::types::global_attestor::AttestationOutcome;
+use crate::validator::types::bftcomet::AttestationRejection;
+use crate::validator::types::quorum_sync::QuorumThreshold;
+use crate::validator::types::slashing::SlashingEvidence;
+use crate::validator::slashing::EvidenceContext;
 
 /// Represents an aggregated ZK batch submitted by one validator
 #[derive(Debug, Clone)]
 pub struct ProofSubmission {
     pub validator_id: String,
+    pub round: u64, // Attestation round the vote is for
     pub batch_hash: String,
     pub attestations: Vec<ProofAttestation>,
     pub timestamp: u64,
+    pub signature: String, // Member's signature over (round, batch_hash), `attestation::sign_vote`
 }
 
 /// GlobalAttestor handles aggregation, verification, and slashing
 pub struct GlobalAttestor {
     pub minority_committee: HashSet<String>,               // 300 fixed
-    pub submissions: Vec<ProofSubmission>,                 // All incoming validator proofs
+    pub submissions: BTreeMap<u64, Vec<ProofSubmission>>,  // round → signed member votes, first per member
     pub slashing_engine: SlashingEngine,                   // Handles validator slashing
     pub verified_attestation_hash: Option<String>,         // The final agreed proof hash
+    pub finalized_round: Option<(u64, Vec<String>)>,       // Last finalized round and its votes' batch hashes
+    pub threshold: QuorumThreshold,                        // Share of committee stake needed
+    pub delegated_stake: HashMap<String, u64>,             // Delegations counted toward each member's vote
 }
 
 impl GlobalAttestor {
     pub fn new(minority_committee: HashSet<String>, slashing_engine: SlashingEngine) -> Self {
+        Self::with_threshold(minority_committee, slashing_engine, QuorumThreshold::default())
+    }
+
+    /// Attestor finalizing at a custom share of committee stake
+    pub fn with_threshold(
+        minority_committee: HashSet<String>,
+        slashing_engine: SlashingEngine,
+        threshold: QuorumThreshold,
+    ) -> Self {
         Self {
             minority_committee,
-            submissions: Vec::new(),
+            submissions: BTreeMap::new(),
             slashing_engine,
             verified_attestation_hash: None,
+            finalized_round: None,
+            threshold,
+            delegated_stake: HashMap::new(),
         }
     }
 
-    /// Accept proof from global validator (up to 99,700 origin)
-    pub fn submit_proof(&mut self, submission: ProofSubmission) {
-        self.submissions.push(submission);
+    /// Sets the delegated stake added to each member's registry stake
+    /// (see `DelegationPool::delegated_to`)
+    pub fn set_delegated_stake(&mut self, delegated: HashMap<String, u64>) {
+        self.delegated_stake = delegated;
     }
 
-    /// Run attestation by minority (300)
-    pub fn run_attestation_round(&mut self) -> bool {
-        if self.submissions.is_empty() {
-            return false;
+    /// Accept a committee member's vote for its round.
+    /// The vote must be signed over (round, batch_hash) by the member's
+    /// registered key; a member's first vote in a round is the one that counts.
+    pub fn submit_proof(&mut self, submission: ProofSubmission) -> Result<(), AttestationRejection> {
+        if !self.minority_committee.contains(&submission.validator_id) {
+            return Err(AttestationRejection::NotCommitteeMember);
         }
 
-        // Step 1: Aggregate all hashes submitted
-        let mut hash_count: HashMap<String, usize> = HashMap::new();
+        let signed = self
+            .slashing_engine
+            .registry
+            .get_by_hash(&submission.validator_id)
+            .is_some_and(|entry| {
+                verify_attestation_signature(
+                    &entry.pubkey,
+                    &submission.validator_id,
+                    &vote_hash(submission.round, &submission.batch_hash),
+                    &submission.signature,
+                )
+            });
+        if !signed {
+            println!("[ATTEST] Dropped unsigned vote from {}", submission.validator_id);
+            return Err(AttestationRejection::BadSignature);
+        }
+
+        let round = self.submissions.entry(submission.round).or_default();
+        if round.iter().any(|vote| vote.validator_id == submission.validator_id) {
+            return Err(AttestationRejection::Duplicate);
+        }
+        round.push(submission);
+        Ok(())
+    }
+
+    /// Registry stake plus delegated stake of a validator (0 if unknown).
+    /// Sums saturate, so no amount of stake wraps to a small weight.
+    fn stake_of(&self, validator_id: &str) -> u64 {
+        self.slashing_engine
+            .registry
+            .get_validator(validator_id)
+            .map(|v| v.stake.saturating_add(self.delegated_stake.get(validator_id).copied().unwrap_or(0)))
+            .unwrap_or(0)
+    }
+
+    /// Run attestation by minority (300) for `round`.
+    ///
+    /// Only signed committee votes for the round count (see `submit_proof`),
+    /// weighted by registry stake. A hash finalizes when its stake reaches
+    /// `threshold` of the whole committee's stake; only then are dissenting
+    /// members slashed, and only where their own signed attestations contradict
+    /// the quorum's (`ctx`). Finalizing drains the round and every earlier one;
+    /// a round without finality keeps its votes for a later run.
+    pub fn run_attestation_round(&mut self, ctx: &EvidenceContext, round: u64, epoch: u64) -> AttestationOutcome {
+        let committee_stake = self
+            .minority_committee
+            .iter()
+            .fold(0u64, |total, id| total.saturating_add(self.stake_of(id)));
+
+        // Step 1: One stake-weighted vote per committee member
+        let votes: Vec<&ProofSubmission> =
+            self.submissions.get(&round).map(|votes| votes.iter().collect()).unwrap_or_default();
+        let mut hash_stake: HashMap<&str, u64> = HashMap::new();
 
-        for sub in &self.submissions {
-            *hash_count.entry(sub.batch_hash.clone()).or_insert(0) += 1;
+        for sub in &votes {
+            let stake = hash_stake.entry(&sub.batch_hash).or_insert(0);
+            *stake = stake.saturating_add(self.stake_of(&sub.validator_id));
         }
 
-        // Step 2: Find most common hash (quorum)
-        if let Some((most_common_hash, _)) = hash_count.iter().max_by_key(|(_, v)| *v) {
-            self.verified_attestation_hash = Some(most_common_hash.clone());
-
-            // Step 3: Identify and slash invalid proof submissions
-            for sub in &self.submissions {
-                if &sub.batch_hash != most_common_hash {
-                    let dummy_attestation = sub.attestations.get(0).cloned().unwrap_or_else(|| ProofAttestation {
-                        vault_id: "unknown".into(),
-                        token: "dUNKNOWN".into(),
-                        size: 0,
-                        owner_hash: "0".into(),
-                        zk_root: "0".into(),
-                        attestation_hash: sub.batch_hash.clone(),
-                        timestamp: sub.timestamp,
-                    });
-
-                    self.slashing_engine.slash_for_attestation_mismatch(
-                        &sub.validator_id,
-                        &dummy_attestation,
-                        most_common_hash,
-                        0,
-                    );
-                }
+        // Step 2: Leading hash by stake (ties broken by hash for determinism)
+        let leading = hash_stake
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(hash, stake)| (hash.to_string(), *stake));
+
+        let (batch_hash, attesting_stake) = match leading {
+            Some((hash, stake)) if self.threshold.is_met(stake, committee_stake) => (hash, stake),
+            other => {
+                println!(
+                    "[ATTEST] No finality: leading stake {} of {} below {}/{}",
+                    other.as_ref().map_or(0, |l| l.1),
+                    committee_stake,
+                    self.threshold.numerator(),
+                    self.threshold.denominator()
+                );
+                return AttestationOutcome::NoFinality {
+                    leading_stake: other.as_ref().map_or(0, |l| l.1),
+                    leading_hash: other.map(|l| l.0),
+                    committee_stake,
+                };
             }
+        };
+
+        // Step 3: Slash committee members who attested a different hash, with the
+        // agreeing members' attestations for the same slot as evidence
+        let (agreeing, dissenters): (Vec<&ProofSubmission>, Vec<&ProofSubmission>) =
+            votes.into_iter().partition(|sub| sub.batch_hash == batch_hash);
+        let quorum_attestations: Vec<ProofAttestation> =
+            agreeing.iter().flat_map(|sub| sub.attestations.iter().cloned()).collect();
+        let evidence: Vec<SlashingEvidence> = dissenters
+            .iter()
+            .filter_map(|sub| mismatch_evidence(sub, &quorum_attestations))
+            .collect();
+
+        let round_hashes: Vec<String> = agreeing
+            .iter()
+            .chain(&dissenters)
+            .map(|sub| sub.batch_hash.clone())
+            .collect();
 
-            return true;
+        let mut slashed = Vec::new();
+        for evidence in evidence {
+            match self.slashing_engine.submit_evidence(&evidence, ctx, epoch) {
+                Ok(record) => slashed.push(record.validator_id),
+                Err(e) => println!("[ATTEST] {} not slashed: {:?}", evidence.offender(), e),
+            }
         }
 
-        false
+        // Round settled: later votes for it or any earlier round are stale
+        self.submissions = match round.checked_add(1) {
+            Some(next) => self.submissions.split_off(&next),
+            None => BTreeMap::new(),
+        };
+        self.finalized_round = Some((round, round_hashes));
+        self.verified_attestation_hash = Some(batch_hash.clone());
+        AttestationOutcome::Finalized {
+            batch_hash,
+            attesting_stake,
+            committee_stake,
+            slashed,
+        }
     }
 
-    /// Poseidon hash of all submission roots (for broadcasting)
+    /// Poseidon hash of the last finalized round and its votes' roots (for broadcasting)
     pub fn generate_proof_attest_root(&self) -> Option<String> {
-        if self.verified_attestation_hash.is_none() {
-            return None;
-        }
+        let (round, hashes) = self.finalized_round.as_ref()?;
 
-        let roots: Vec<String> = self
-            .submissions
-            .iter()
-            .map(|s| s.batch_hash.clone())
-            .collect();
+        let mut inputs = vec![u64_to_fp(*round)];
+        inputs.extend(hashes.iter().flat_map(|hash| string_to_limbs(hash)));
 
-        Some(poseidon_hash_many(&roots))
+        let mut hasher = PoseidonHasher::new();
+        Some(hex::encode(hasher.hash(&inputs).to_repr()))
     }
 }
 
+/// Mismatch evidence from the dissenter's first attestation that the quorum
+/// attested differently for the same slot (epoch, proof ID)
+fn mismatch_evidence(dissenter: &ProofSubmission, quorum: &[ProofAttestation]) -> Option<SlashingEvidence> {
+    dissenter.attestations.iter().find_map(|attestation| {
+        let contradicting: Vec<ProofAttestation> = quorum
+            .iter()
+            .filter(|q| {
+                q.slot() == attestation.slot() && q.attestation_hash != attestation.attestation_hash
+            })
+            .cloned()
+            .collect();
+        (!contradicting.is_empty()).then(|| SlashingEvidence::AttestationMismatch {
+            attestation: attestation.clone(),
+            quorum: contradicting,
+        })
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::BTreeMap;
+    use crate::common::signing::fixtures::{key, never_verifies};
+    use crate::common::signing::public_key_hex;
+    use crate::validator::types::bftcomet::CommitteeFinality;
+    use crate::validator::types::liveness::LivenessConfig;
+    use crate::validator::attestation::{attestation_signature, compute_attestation_hash, sign_vote};
+    use crate::validator::types::bftcomet::Validator;
+    use crate::validator::delegation_pool::DelegationPool;
     use crate::validator::slashing_engine::SlashingEngine;
+    use crate::validator::staking_pool::StakingPool;
     use crate::validator::validator_registry::ValidatorRegistry;
 
-    fn mock_attestation(hash: &str) -> ProofAttestation {
+    /// `validator_id`'s signed attestation of vault123 in slot (1, batch-1);
+    /// the batch hash doubles as the attested zk_root
+    fn mock_attestation(validator_id: &str, hash: &str) -> ProofAttestation {
+        let attestation_hash = compute_attestation_hash("vault123", "dBTC", "poseidon(owner)", hash, 1, "batch-1");
         ProofAttestation {
             vault_id: "vault123".into(),
             token: "dBTC".into(),
             size: 10,
             owner_hash: "poseidon(owner)".into(),
-            zk_root: "poseidon(root)".into(),
-            attestation_hash: hash.into(),
-            timestamp: Utc::now().timestamp() as u64,
+            zk_root: hash.into(),
+            signature: attestation_signature(&key(validator_id), validator_id, &attestation_hash),
+            attestation_hash,
+            epoch: 1,
+            proof_id: "batch-1".into(),
+            validator_id: validator_id.into(),
+        }
+    }
+
+    /// `validator_id`'s signed round-1 vote for `hash`
+    fn submission(validator_id: &str, hash: &str) -> ProofSubmission {
+        ProofSubmission {
+            validator_id: validator_id.into(),
+            round: 1,
+            batch_hash: hash.into(),
+            attestations: vec![mock_attestation(validator_id, hash)],
+            timestamp: 10,
+            signature: sign_vote(&key(validator_id), validator_id, 1, hash),
+        }
+    }
+
+    struct Keys {
+        keys: HashMap<String, String>,
+        code: HashMap<String, String>,
+        finalities: BTreeMap<u64, CommitteeFinality>,
+    }
+
+    impl Keys {
+        fn new(ids: &[&str]) -> Self {
+            Self {
+                keys: ids.iter().map(|id| (id.to_string(), public_key_hex(&key(id)))).collect(),
+                code: HashMap::new(),
+                finalities: BTreeMap::new(),
+            }
+        }
+
+        fn ctx(&self) -> EvidenceContext<'_> {
+            EvidenceContext {
+                attestation_keys: &self.keys,
+                committee_size: self.keys.len(),
+                finality_threshold: QuorumThreshold::TWO_THIRDS,
+                liveness: LivenessConfig::default(),
+                finalities: &self.finalities,
+                registered_code: &self.code,
+                verify_proof: never_verifies,
+            }
         }
     }
 
+    fn attestor(stakes: &[(&str, u64)]) -> GlobalAttestor {
+        let registry = ValidatorRegistry::new();
+        let mut pool = StakingPool::new();
+        for (id, stake) in stakes {
+            registry.add_validator_with_pubkey(
+                Validator {
+                    id: id.to_string(),
+                    stake: *stake,
+                    last_active_epoch: 1,
+                },
+                &public_key_hex(&key(id)),
+            );
+            pool.deposit(id.to_string(), *stake);
+        }
+        let committee = stakes.iter().map(|(id, _)| id.to_string()).collect();
+        GlobalAttestor::new(committee, SlashingEngine::new(registry, pool, DelegationPool::new()))
+    }
+
     #[test]
     fn test_attestation_round() {
-        let mut registry = ValidatorRegistry::new();
-        registry.add_validator(Validator {
-            id: "v1".into(),
-            stake: 1000,
-            last_active_epoch: 1,
-        });
-
-        let engine = SlashingEngine::new(registry);
-        let mut attestor = GlobalAttestor::new(HashSet::new(), engine);
-
-        attestor.submit_proof(ProofSubmission {
-            validator_id: "v1".into(),
-            batch_hash: "hash_a".into(),
-            attestations: vec![mock_attestation("hash_a")],
-            timestamp: Utc::now().timestamp() as u64,
-        });
-
-        attestor.submit_proof(ProofSubmission {
-            validator_id: "v2".into(),
-            batch_hash: "hash_b".into(),
-            attestations: vec![mock_attestation("hash_b")],
-            timestamp: Utc::now().timestamp() as u64,
-        });
-
-        let success = attestor.run_attestation_round();
-        assert!(success);
-        assert_eq!(attestor.verified_attestation_hash.is_some(), true);
+        let keys = Keys::new(&["v1", "v2", "v3"]);
+        let mut attestor = attestor(&[("v1", 1000), ("v2", 1000), ("v3", 1000)]);
+        attestor.submit_proof(submission("v1", "hash_a")).unwrap();
+        attestor.submit_proof(submission("v2", "hash_a")).unwrap();
+        attestor.submit_proof(submission("v3", "hash_b")).unwrap();
+
+        match attestor.run_attestation_round(&keys.ctx(), 1, 1) {
+            AttestationOutcome::Finalized { batch_hash, attesting_stake, slashed, .. } => {
+                assert_eq!(batch_hash, "hash_a");
+                assert_eq!(attesting_stake, 2000);
+                assert_eq!(slashed, vec!["v3".to_string()]);
+            }
+            other => panic!("expected finality, got {:?}", other),
+        }
+        assert_eq!(attestor.verified_attestation_hash.as_deref(), Some("hash_a"));
+        assert_eq!(attestor.slashing_engine.registry.get_validator("v3").unwrap().stake, 900);
+    }
+
+    #[test]
+    fn test_dissent_without_signed_evidence_is_not_slashed() {
+        let keys = Keys::new(&["v1", "v2", "v3"]);
+        let mut attestor = attestor(&[("v1", 1000), ("v2", 1000), ("v3", 1000)]);
+        attestor.submit_proof(submission("v1", "hash_a")).unwrap();
+        attestor.submit_proof(submission("v2", "hash_a")).unwrap();
+        let mut unsigned = submission("v3", "hash_b");
+        unsigned.attestations.clear();
+        attestor.submit_proof(unsigned).unwrap();
+
+        match attestor.run_attestation_round(&keys.ctx(), 1, 1) {
+            AttestationOutcome::Finalized { slashed, .. } => assert!(slashed.is_empty()),
+            other => panic!("expected finality, got {:?}", other),
+        }
+        assert!(attestor.slashing_engine.all_events().is_empty());
+    }
+
+    #[test]
+    fn test_single_submission_without_quorum_is_not_final() {
+        let keys = Keys::new(&["v1", "v2", "v3"]);
+        let mut attestor = attestor(&[("v1", 1000), ("v2", 1000), ("v3", 1000)]);
+        attestor.submit_proof(submission("v1", "hash_a")).unwrap();
+        attestor.submit_proof(submission("v2", "hash_b")).unwrap();
+        // Outsiders and repeat votes carry no weight
+        assert_eq!(
+            attestor.submit_proof(submission("outsider", "hash_a")),
+            Err(AttestationRejection::NotCommitteeMember)
+        );
+        assert_eq!(attestor.submit_proof(submission("v1", "hash_a")), Err(AttestationRejection::Duplicate));
+
+        let outcome = attestor.run_attestation_round(&keys.ctx(), 1, 1);
+        assert!(!outcome.is_finalized());
+        assert!(attestor.verified_attestation_hash.is_none());
+        assert!(attestor.slashing_engine.all_events().is_empty());
+    }
+
+    #[test]
+    fn test_delegated_stake_counts_toward_quorum() {
+        let keys = Keys::new(&["v1", "v2", "v3"]);
+        let mut attestor = attestor(&[("v1", 1000), ("v2", 1000), ("v3", 1000)]);
+        attestor.set_delegated_stake(HashMap::from([("v1".to_string(), 3000)]));
+        attestor.submit_proof(submission("v1", "hash_a")).unwrap();
+        attestor.submit_proof(submission("v2", "hash_b")).unwrap();
+
+        // 4000 of 6000 committee stake
+        assert!(attestor.run_attestation_round(&keys.ctx(), 1, 1).is_finalized());
+    }
+
+    #[test]
+    fn test_unsigned_votes_are_dropped() {
+        let mut attestor = attestor(&[("v1", 1000), ("v2", 1000), ("v3", 1000)]);
+
+        let mut unsigned = submission("v1", "hash_a");
+        unsigned.signature.clear();
+        assert_eq!(attestor.submit_proof(unsigned), Err(AttestationRejection::BadSignature));
+
+        // v2's key cannot vote for v1, nor a signature move to another hash or round
+        let mut forged = submission("v1", "hash_a");
+        forged.signature = sign_vote(&key("v2"), "v1", 1, "hash_a");
+        assert_eq!(attestor.submit_proof(forged), Err(AttestationRejection::BadSignature));
+        let mut moved = submission("v1", "hash_b");
+        moved.signature = submission("v1", "hash_a").signature;
+        assert_eq!(attestor.submit_proof(moved), Err(AttestationRejection::BadSignature));
+        let mut replayed = submission("v1", "hash_a");
+        replayed.round = 2;
+        assert_eq!(attestor.submit_proof(replayed), Err(AttestationRejection::BadSignature));
+
+        assert!(attestor.submissions.is_empty());
+    }
+
+    #[test]
+    fn test_finality_drains_settled_rounds() {
+        let keys = Keys::new(&["v1", "v2", "v3"]);
+        let mut attestor = attestor(&[("v1", 1000), ("v2", 1000), ("v3", 1000)]);
+        attestor.submit_proof(submission("v1", "hash_a")).unwrap();
+        attestor.submit_proof(submission("v2", "hash_a")).unwrap();
+
+        // A vote for the next round stays queued
+        let mut next = submission("v1", "hash_c");
+        next.round = 2;
+        next.signature = sign_vote(&key("v1"), "v1", 2, "hash_c");
+        attestor.submit_proof(next).unwrap();
+
+        // No finality on round 2 yet: its votes are kept
+        assert!(!attestor.run_attestation_round(&keys.ctx(), 2, 1).is_finalized());
+        assert!(attestor.submissions.contains_key(&2));
+
+        assert!(attestor.run_attestation_round(&keys.ctx(), 1, 1).is_finalized());
+        assert!(!attestor.submissions.contains_key(&1));
+        assert_eq!(attestor.submissions[&2].len(), 1);
+        assert!(attestor.generate_proof_attest_root().is_some());
+
+        // Re-running the settled round finds no votes
+        assert!(!attestor.run_attestation_round(&keys.ctx(), 1, 1).is_finalized());
     }
 }
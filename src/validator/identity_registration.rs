@@ -1,28 +1,94 @@
-// src/validator/identity_registration.rs 
+// src/validator/identity_registration.rs
 // Handles validator identity registration into the global system
+//
+// - The pubkey is the validator's secp256k1 signing key (hex); the one key
+//   proves control of the identity and signs its attestations
+// - Identity hash = Poseidon(IDENTITY || pubkey), over the canonical key
+//   encoding, so anyone can recompute it from the registration data (no
+//   timestamp) and no one can claim another key's identity
+// - Control of the identity is proven with a signature under the pubkey over
+//   the current epoch's registration challenge; leaving takes a signature
+//   over a deregistration challenge for that registration
+// - Stake must be bonded under the identity hash before registering
+// - Registered validators live in the shared `ValidatorRegistry`
 
-use crate::utils::poseidon_hash; use crate::types::ValidatorIdentity; use std::collections::HashMap; use chrono::Utc;
+use pasta_curves::group::ff::PrimeField;
+use crate::common::poseidon_hasher::PoseidonHasher;
+use crate::common::signing::{public_key_hex, SigningKey};
+use crate::matching::core::poseidon_utils::string_to_limbs;
+use crate::validator::types::validator_registry::{RegistrationRequest, RegistryEntry, RegistryError};
+use crate::validator::attestation::{attestation_signature, verify_attestation_signature};
+use crate::validator::poseidon_utils::u64_to_fp;
+use crate::validator::staking_pool::StakingPool;
+use crate::validator::validator_registry::ValidatorRegistry;
 
-// Simulated global registry (in-memory for now) static mut VALIDATOR_REGISTRY: Option<HashMap<String, ValidatorIdentity>> = None;
+/// Domain tag for validator identity hashes
+const IDENTITY_DOMAIN: u64 = 6;
 
-/// Registers a new validator with a Poseidon-based identity fingerprint pub fn register_validator(pubkey: &str, metadata: &str) -> ValidatorIdentity { let timestamp = Utc::now().timestamp() as u64; let identity_hash = poseidon_hash(&[pubkey, metadata, &timestamp.to_string()]);
+/// Poseidon(IDENTITY || pubkey), hex; `pubkey` is limb-encoded in full and
+/// should be canonical (`signing::canonical_public_key`)
+pub fn identity_hash(pubkey: &str) -> String {
+    let mut inputs = vec![u64_to_fp(IDENTITY_DOMAIN)];
+    inputs.extend(string_to_limbs(pubkey));
 
-let validator = ValidatorIdentity {
-    pubkey: pubkey.to_string(),
-    metadata: metadata.to_string(),
-    joined_at: timestamp,
-    identity_hash: identity_hash.clone(),
-};
+    let mut hasher = PoseidonHasher::new();
+    hex::encode(hasher.hash(&inputs).to_repr())
+}
+
+/// Message a registrant authenticates to prove control in `epoch`
+pub fn registration_challenge(epoch: u64) -> String {
+    format!("domex:register:{}", epoch)
+}
+
+/// Message the validator signs to leave the set: bound to the registration
+/// being ended, so a signature cannot end a later one
+pub fn deregistration_challenge(registered_epoch: u64, epoch: u64) -> String {
+    format!("domex:deregister:{}:{}", registered_epoch, epoch)
+}
 
-unsafe {
-    VALIDATOR_REGISTRY
-        .get_or_insert_with(HashMap::new)
-        .insert(identity_hash.clone(), validator.clone());
+/// Proof of control of `identity_hash` for `epoch`
+pub fn identity_proof(key: &SigningKey, identity_hash: &str, epoch: u64) -> String {
+    attestation_signature(key, identity_hash, &registration_challenge(epoch))
 }
 
-validator
+/// Signature ending the registration of `key`'s identity made in
+/// `registered_epoch`, valid in `epoch`
+pub fn deregistration_proof(key: &SigningKey, registered_epoch: u64, epoch: u64) -> String {
+    let hash = identity_hash(&public_key_hex(key));
+    attestation_signature(key, &hash, &deregistration_challenge(registered_epoch, epoch))
+}
 
+/// Builds a signed registration request for `epoch`
+pub fn registration_request(metadata: &str, key: &SigningKey, epoch: u64) -> RegistrationRequest {
+    let pubkey = public_key_hex(key);
+    let hash = identity_hash(&pubkey);
+    RegistrationRequest {
+        pubkey,
+        metadata: metadata.to_string(),
+        epoch,
+        identity_proof: identity_proof(key, &hash, epoch),
+    }
 }
 
-/// Retrieves a validator by its hashed identity pub fn get_validator_by_hash(hash: &str) -> Option<ValidatorIdentity> { unsafe { VALIDATOR_REGISTRY .as_ref() .and_then(|registry| registry.get(hash).cloned()) } }
+/// True if the request's proof verifies under its pubkey for `epoch`.
+/// `pubkey` must already be canonical.
+pub fn verify_identity_proof(request: &RegistrationRequest, epoch: u64) -> bool {
+    let hash = identity_hash(&request.pubkey);
+    request.epoch == epoch
+        && verify_attestation_signature(&request.pubkey, &hash, &registration_challenge(epoch), &request.identity_proof)
+}
 
+/// Registers a new validator after checking its identity proof and bonded stake
+pub fn register_validator(
+    registry: &ValidatorRegistry,
+    request: &RegistrationRequest,
+    staking: &StakingPool,
+    epoch: u64,
+) -> Result<RegistryEntry, RegistryError> {
+    registry.register(request, staking, epoch)
+}
+
+/// Retrieves a validator by its hashed identity
+pub fn get_validator_by_hash(registry: &ValidatorRegistry, hash: &str) -> Option<RegistryEntry> {
+    registry.get_by_hash(hash)
+}
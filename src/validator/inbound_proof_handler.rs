@@ -1,8 +1,17 @@
 // Domex Phase 1 - Validator Proof Intake Handler
 // Receives and parses ZK proof submissions from local nodes
 
-use crate::types::{IncomingProof, NormalizedProof};
-use crate::utils::{is_valid_vault, is_valid_poseidon_hash};
+use crate::validator::types::inbound_proof::{IncomingProof, NormalizedProof};
+
+/// Vault IDs are non-empty printable ASCII (e.g. "BTC/USDT")
+fn is_valid_vault(vault_id: &str) -> bool {
+    !vault_id.is_empty() && vault_id.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// Poseidon hashes travel as 32 bytes of hex
+fn is_valid_poseidon_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit())
+}
 
 /// Main entry for handling an incoming ZK proof request from a local node
 pub fn handle_incoming_proof(proof: IncomingProof) -> Result<NormalizedProof, String> {
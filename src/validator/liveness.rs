@@ -0,0 +1,197 @@
+// src/validator/liveness.rs
+
+// Domex Liveness Tracking
+// Sliding-window record of attestation duties per validator, turning sustained
+// downtime into slashing evidence (validator/slashing.rs).
+//
+// Exact rule (defaults from `LivenessConfig`):
+// - A duty is one epoch in which the validator sat on the closing committee,
+//   whether or not that epoch finalized. Attesting counts as done even if the
+//   vote dissented; only silence is missed.
+// - Only duties from the last `window_epochs` (100) epochs count.
+// - Nothing is judged until the window holds `min_duties` (10) duties, so a
+//   validator that is rarely selected is never punished on a handful of misses.
+// - Downtime is missing strictly more than `max_missed` (1/2) of the duties in
+//   the window: 5 of 10 is fine, 6 of 10 is downtime.
+// - Downtime costs `Offence::Downtime` from the policy table: 0.1% of stake
+//   (10 bps, so the loss scales with stake) and 100 epochs in jail, out of
+//   committee selection. After the jail ends the validator unjails itself
+//   (`Slasher::unjail`) if its stake is still at the minimum.
+// - The window is cleared when evidence is issued, so the same misses are
+//   never punished twice.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::validator::types::bftcomet::CommitteeFinality;
+use crate::validator::types::liveness::LivenessConfig;
+use crate::validator::types::slashing::SlashingEvidence;
+
+/// One epoch's duty outcome
+#[derive(Debug, Clone, Copy)]
+struct Duty {
+    epoch: u64,
+    attested: bool,
+}
+
+/// Per-validator sliding window of attestation duties
+pub struct LivenessTracker {
+    config: LivenessConfig,
+    duties: HashMap<String, VecDeque<Duty>>,
+}
+
+impl LivenessTracker {
+    pub fn new(config: LivenessConfig) -> Self {
+        Self {
+            config,
+            duties: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &LivenessConfig {
+        &self.config
+    }
+
+    /// Record one duty; a repeat for the same epoch overrides the earlier outcome
+    pub fn record_duty(&mut self, validator_id: &str, epoch: u64, attested: bool) {
+        let window = self.duties.entry(validator_id.to_string()).or_default();
+        match window.iter_mut().find(|d| d.epoch == epoch) {
+            Some(duty) => duty.attested |= attested,
+            None => window.push_back(Duty { epoch, attested }),
+        }
+        Self::prune(window, epoch, self.config.window_epochs);
+    }
+
+    /// Record every committee member's duty for a closed epoch, finalized or not
+    pub fn record_finality(&mut self, epoch: u64, finality: &CommitteeFinality) {
+        for id in finality.validators.iter().chain(&finality.dissenting) {
+            self.record_duty(id, epoch, true);
+        }
+        for id in &finality.missing {
+            self.record_duty(id, epoch, false);
+        }
+    }
+
+    /// (duties, missed) in the window ending at `epoch`
+    pub fn window_stats(&self, validator_id: &str, epoch: u64) -> (u32, u32) {
+        let start = epoch.saturating_sub(self.config.window_epochs.saturating_sub(1));
+        self.duties.get(validator_id).map_or((0, 0), |window| {
+            window
+                .iter()
+                .filter(|d| d.epoch >= start && d.epoch <= epoch)
+                .fold((0, 0), |(duties, missed), d| (duties + 1, missed + u32::from(!d.attested)))
+        })
+    }
+
+    /// True if the validator's window at `epoch` meets the downtime rule
+    pub fn is_down(&self, validator_id: &str, epoch: u64) -> bool {
+        let (duties, missed) = self.window_stats(validator_id, epoch);
+        duties >= self.config.min_duties && self.config.max_missed.is_exceeded(missed.into(), duties.into())
+    }
+
+    /// Downtime evidence for every validator over the limit at `epoch`, in ID order.
+    /// Their windows are cleared so the same misses are not punished twice.
+    pub fn evaluate(&mut self, epoch: u64) -> Vec<SlashingEvidence> {
+        let mut offenders: Vec<String> = self
+            .duties
+            .keys()
+            .filter(|id| self.is_down(id, epoch))
+            .cloned()
+            .collect();
+        offenders.sort();
+
+        let window_start = epoch.saturating_sub(self.config.window_epochs.saturating_sub(1));
+        offenders
+            .into_iter()
+            .map(|validator_id| {
+                let (duties, missed) = self.window_stats(&validator_id, epoch);
+                // Start at the first counted duty: earlier epochs were cleared by
+                // previous evidence, and windows must not overlap (`Slasher`)
+                let from_epoch = self.duties[&validator_id]
+                    .iter()
+                    .map(|d| d.epoch)
+                    .find(|e| *e >= window_start)
+                    .unwrap_or(window_start);
+                self.duties.remove(&validator_id);
+                println!(
+                    "[LIVENESS] {} missed {}/{} duties in epochs {}..={}",
+                    validator_id, missed, duties, from_epoch, epoch
+                );
+                SlashingEvidence::Downtime {
+                    validator_id,
+                    from_epoch,
+                    to_epoch: epoch,
+                    duties,
+                    missed,
+                }
+            })
+            .collect()
+    }
+
+    fn prune(window: &mut VecDeque<Duty>, latest: u64, window_epochs: u64) {
+        let start = latest.saturating_sub(window_epochs.saturating_sub(1));
+        window.retain(|d| d.epoch >= start);
+    }
+}
+
+impl Default for LivenessTracker {
+    fn default() -> Self {
+        Self::new(LivenessConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(tracker: &mut LivenessTracker, id: &str, outcomes: &[bool]) {
+        for (epoch, attested) in outcomes.iter().enumerate() {
+            tracker.record_duty(id, epoch as u64, *attested);
+        }
+    }
+
+    #[test]
+    fn test_threshold_is_strict_and_needs_min_duties() {
+        let mut tracker = LivenessTracker::default();
+
+        // 5 of 10 missed: at the limit, not over it
+        record(&mut tracker, "half", &[true, false].repeat(5));
+        // 6 of 10 missed
+        record(&mut tracker, "flaky", &[false, false, true, false, true, false, true, false, false, true]);
+        // Every duty missed, but only 9 duties so far
+        record(&mut tracker, "new", &[false; 9]);
+
+        assert!(!tracker.is_down("half", 9));
+        assert!(tracker.is_down("flaky", 9));
+        assert!(!tracker.is_down("new", 9));
+
+        let evidence = tracker.evaluate(9);
+        assert_eq!(evidence.len(), 1);
+        match &evidence[0] {
+            SlashingEvidence::Downtime { validator_id, duties, missed, .. } => {
+                assert_eq!((validator_id.as_str(), *duties, *missed), ("flaky", 10, 6));
+            }
+            other => panic!("unexpected evidence {:?}", other),
+        }
+
+        // Window cleared: no second punishment for the same misses
+        assert!(tracker.evaluate(9).is_empty());
+    }
+
+    #[test]
+    fn test_old_misses_slide_out_of_the_window() {
+        let mut tracker = LivenessTracker::new(LivenessConfig {
+            window_epochs: 10,
+            ..LivenessConfig::default()
+        });
+
+        record(&mut tracker, "v", &[false; 10]);
+        assert!(tracker.is_down("v", 9));
+
+        // Ten good epochs push every miss out
+        for epoch in 10..20 {
+            tracker.record_duty("v", epoch, true);
+        }
+        assert_eq!(tracker.window_stats("v", 19), (10, 0));
+        assert!(!tracker.is_down("v", 19));
+    }
+}
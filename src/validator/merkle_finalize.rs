@@ -7,8 +7,8 @@ use crate::validator::merkle_state::compute_merkle_root;
 use crate::validator::proof_attestation::build_attestation;
 use crate::types::vault::VaultState;
 use crate::types::normalized_proof::NormalizedProof;
-use crate::types::attestation::ProofAttestation;
-use crate::types::merkle_state::MerkleRoot;
+use crate::validator::types::attestation::ProofAttestation;
+use crate::validator::types::merkle_state::MerkleRoot;
 
 /// Finalizes Merkle root from current vault state and binds it
 /// to the proof via Poseidon attestation for quorum broadcast.
@@ -19,7 +19,7 @@ pub fn finalize_merkle_attestation(
     verified_proof: &NormalizedProof,
 ) -> (MerkleRoot, ProofAttestation) {
     // Step 1: Compute the Merkle root from vault state entries
-    let (merkle_root, _proofs) = compute_merkle_root(state);
+    let merkle_root = compute_merkle_root(state);
 
     // Step 2: Build Poseidon-signed attestation for the zk_root
     // This binds validator identity to the verified proof and state transition
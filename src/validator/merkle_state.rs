@@ -1,70 +1,103 @@
 // src/validator/merkle_state.rs
 
 // Domex Merkle State Handler
-// Builds and verifies Merkle roots over vault balances
-
-use crate::types::merkle::{MerkleLeaf, MerkleRoot, MerkleProof};
-use crate::types::vault::VaultState;
-use crate::utils::poseidon::poseidon_hash_pair;
-use std::collections::BTreeMap;
-
-/// Computes the Merkle root from a VaultState
-/// Each leaf = Poseidon(identity + token + balance)
-pub fn compute_merkle_root(state: &VaultState) -> (MerkleRoot, Vec<MerkleLeaf>) {
-    let mut leaves: Vec<MerkleLeaf> = vec![];
-
-    for ((identity, token), balance) in state.balances.iter() {
-        let leaf = MerkleLeaf {
-            key: format!("{}::{}", identity, token),
-            value: balance.to_string(),
-        };
-        leaves.push(leaf);
+// Builds and verifies the vault balance root: the fixed-slot sparse tree in
+// `sparse_merkle.rs`, one leaf per (identity, token) balance
+
+use crate::validator::types::merkle_state::MerkleRoot;
+use crate::validator::types::sparse_merkle::{SmtLeaf, SmtProof};
+use crate::validator::sparse_merkle::{balance_key, verify_inclusion, SparseMerkleTree};
+use crate::matching::core::types::order_book::{PoseidonHash, VaultState};
+use crate::common::merkle::{hash_from_hex, hash_to_hex};
+
+/// Computes the Merkle root from a VaultState in one full build
+pub fn compute_merkle_root(state: &VaultState) -> MerkleRoot {
+    VaultMerkleCache::from_state(state).root()
+}
+
+/// Verifies a balance leaf's inclusion proof against a known Merkle root
+pub fn verify_merkle_proof(root: &MerkleRoot, leaf: &SmtLeaf, proof: &SmtProof) -> bool {
+    hash_from_hex(&root.0).is_some_and(|root| verify_inclusion(&root, leaf, proof))
+}
+
+/// (available, reserved) of one (identity, token) balance; `reserved` comes
+/// from the order-book state that locks it for resting orders
+fn balance_split(state: &VaultState, identity: &PoseidonHash, token: &str) -> (u64, u64) {
+    let reserved = state.get_reserved(identity, token);
+    let available = state.get_balance(identity, token).saturating_sub(reserved);
+    (available, reserved)
+}
+
+/// Cached vault balance tree for the hot path.
+/// Every balance has a fixed slot, so a trade rehashes only the paths of
+/// the balances it touches, whether they are new, changed or emptied.
+/// The root never depends on the order updates arrive in.
+pub struct VaultMerkleCache {
+    tree: SparseMerkleTree,
+}
+
+impl VaultMerkleCache {
+    /// One full build at startup or after a snapshot restore
+    pub fn from_state(state: &VaultState) -> Self {
+        let mut tree = SparseMerkleTree::new();
+        for (identity, token) in state.balances.keys() {
+            let (available, reserved) = balance_split(state, identity, token);
+            tree.set_balance(identity, token, available, reserved);
+        }
+        Self { tree }
     }
 
-    // Sort leaves lexicographically to ensure deterministic Merkle tree
-    leaves.sort_by(|a, b| a.key.cmp(&b.key));
-
-    let mut hashed_leaves: Vec<String> = leaves
-        .iter()
-        .map(|leaf| poseidon_hash_pair(&leaf.key, &leaf.value))
-        .collect();
-
-    while hashed_leaves.len() > 1 {
-        let mut next_layer = vec![];
-        let mut iter = hashed_leaves.chunks(2);
-
-        for pair in iter {
-            let combined = if pair.len() == 2 {
-                poseidon_hash_pair(&pair[0], &pair[1])
-            } else {
-                pair[0].clone()
-            };
-            next_layer.push(combined);
+    /// Re-reads the given (identity, token) balances from state and rewrites
+    /// their slots. A balance gone from state empties its slot.
+    pub fn apply_changes(&mut self, state: &VaultState, changed: &[(PoseidonHash, String)]) -> MerkleRoot {
+        for (identity, token) in changed {
+            let (available, reserved) = balance_split(state, identity, token);
+            self.tree.set_balance(identity, token, available, reserved);
         }
+        self.root()
+    }
 
-        hashed_leaves = next_layer;
+    pub fn root(&self) -> MerkleRoot {
+        MerkleRoot(hash_to_hex(&self.tree.root()))
     }
 
-    let root = hashed_leaves.first().cloned().unwrap_or_else(|| "0".repeat(64));
+    /// The committed tree, e.g. for offboarding non-inclusion proofs
+    pub fn tree(&self) -> &SparseMerkleTree {
+        &self.tree
+    }
 
-    (MerkleRoot(root), leaves)
+    /// Inclusion proof from the cached nodes, checked with `verify_merkle_proof`
+    pub fn proof(&self, identity: &PoseidonHash, token: &str) -> Option<(SmtLeaf, SmtProof)> {
+        self.tree.prove_inclusion(&balance_key(identity, token)).ok()
+    }
 }
 
-/// Verifies a Merkle proof against a known Merkle root
-pub fn verify_merkle_proof(
-    root: &MerkleRoot,
-    leaf: &MerkleLeaf,
-    proof: &MerkleProof,
-) -> bool {
-    let mut hash = poseidon_hash_pair(&leaf.key, &leaf.value);
-
-    for (sibling_hash, is_right) in &proof.path {
-        hash = if *is_right {
-            poseidon_hash_pair(sibling_hash, &hash)
-        } else {
-            poseidon_hash_pair(&hash, sibling_hash)
-        };
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    &hash == &root.0
+    const ALICE: PoseidonHash = [1u8; 32];
+    const BOB: PoseidonHash = [2u8; 32];
+
+    #[test]
+    fn test_cache_tracks_full_build() {
+        let mut state = VaultState::new("BTC/USDT");
+        state.increase_balance(&ALICE, "BTC", 100);
+        let mut cache = VaultMerkleCache::from_state(&state);
+
+        // New balance, then a reservation, then an emptied balance
+        state.increase_balance(&BOB, "BTC", 40);
+        state.reserve_balance(&ALICE, "BTC", 30).unwrap();
+        let root = cache.apply_changes(&state, &[(BOB, "BTC".into()), (ALICE, "BTC".into())]);
+        assert_eq!(root, compute_merkle_root(&state));
+
+        let (leaf, proof) = cache.proof(&ALICE, "BTC").unwrap();
+        assert_eq!((leaf.available, leaf.reserved), (70, 30));
+        assert!(verify_merkle_proof(&root, &leaf, &proof));
+
+        state.decrease_balance(&BOB, "BTC", 40);
+        let root = cache.apply_changes(&state, &[(BOB, "BTC".into())]);
+        assert_eq!(root, compute_merkle_root(&state));
+        assert!(cache.proof(&BOB, "BTC").is_none());
+    }
 }
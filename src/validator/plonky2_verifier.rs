@@ -4,7 +4,7 @@
 
 use crate::types::zk_client::ZkOnboardingPublicInputs;
 use crate::types::circuit_interface::Ponkey2ProofBytes;
-use crate::poseidon_utils::{string_to_fp, u64_to_fp, bytes_to_fp};
+use crate::matching::core::poseidon_utils::{string_to_fp, u64_to_fp, bytes_to_fp};
 
 use plonky2::field::goldilocks_field::GoldilocksField;
 use plonky2::plonk::config::GenericConfig;
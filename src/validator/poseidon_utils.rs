@@ -2,12 +2,13 @@
 // Validator-side Poseidon hash utilities (Plonky2 + Pasta)
 // Used for identity hashing, vault binding, and withdrawal verification.
 
+use pasta_curves::group::ff::PrimeField;
 use pasta_curves::Fp;
-use plonky2_poseidon::PoseidonHasher;
+use crate::common::poseidon_hasher::PoseidonHasher;
 
 /// Converts a 32-byte field-safe input to Pasta Fp
 pub fn bytes_to_fp(input: &[u8; 32]) -> Fp {
-    Fp::from_bytes(input).expect("Invalid bytes: not a valid Pasta field element")
+    Fp::from_repr(*input).expect("Invalid bytes: not a valid Pasta field element")
 }
 
 /// Converts a u64 (e.g., vault ID) to Pasta Fp
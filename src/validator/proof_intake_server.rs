@@ -0,0 +1,424 @@
+// src/validator/proof_intake_server.rs
+
+// Domex Validator Proof Intake (TCP)
+// Accepts framed proof submissions from vault leaders (common/proof_wire.rs).
+//
+// - Each submission is checked by `handle_incoming_proof` (or a custom
+//   intake check) and gets a proof ID = Poseidon(INTAKE || sequence || key),
+//   the key limb-encoded in full (`poseidon_utils::bytes_to_limbs`)
+// - The check runs outside the state lock, and nothing under the lock can
+//   panic, so one bad submission never poisons intake for other leaders
+// - Idempotency keys are remembered: a resubmission with the same payload
+//   gets the original proof ID back with `duplicate = true`; the same key
+//   with a different payload is rejected
+// - Proofs from the mock backend prove nothing and are rejected outside tests
+// - Accepted proofs queue up until `take_accepted` hands them to
+//   verification and attestation (ProofReceiver)
+// - Memory is bounded: the oldest idempotency keys are forgotten past
+//   `MAX_SEEN_KEYS`, and a full queue turns leaders away with a retryable
+//   rejection until the receiver drains it
+
+use pasta_curves::group::ff::PrimeField;
+use crate::common::poseidon_hasher::PoseidonHasher;
+
+use crate::common::common_types::ProofId;
+use crate::common::proof_wire::{
+    read_frame, write_frame, ProofAck, ProofSubmitRequest, ProofSubmitResponse, WireError, WireMessage,
+};
+use crate::matching::core::poseidon_utils::{bytes_to_limbs, RAW_BYTES_TAG};
+use crate::matching::zk::proof_backend::MOCK_BACKEND;
+use crate::validator::types::proof_intake_server::AcceptedProof;
+use crate::validator::types::inbound_proof::{IncomingProof, NormalizedProof};
+use crate::validator::inbound_proof_handler::handle_incoming_proof;
+use crate::validator::poseidon_utils::u64_to_fp;
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Domain tag for intake proof IDs
+const INTAKE_DOMAIN: u64 = 2;
+
+/// Idle time before a leader connection is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Mock proofs are only accepted by test builds
+const ALLOW_MOCK_PROOFS: bool = cfg!(test);
+
+/// Idempotency keys remembered for replies to retries
+pub const MAX_SEEN_KEYS: usize = 65_536;
+
+/// Accepted proofs waiting for `take_accepted`
+pub const MAX_PENDING_PROOFS: usize = 4_096;
+
+/// Validation applied to every submission before it is accepted
+pub type IntakeCheck = fn(IncomingProof) -> Result<NormalizedProof, String>;
+
+struct IntakeState {
+    next_sequence: u64,
+    seen: HashMap<String, (ProofId, u64)>, // key → (proof ID, payload fingerprint)
+    seen_order: VecDeque<String>,          // Keys oldest first, for eviction
+    accepted: Vec<AcceptedProof>,
+    max_seen: usize,
+    max_pending: usize,
+}
+
+impl IntakeState {
+    fn new(max_seen: usize, max_pending: usize) -> Self {
+        Self {
+            next_sequence: 0,
+            seen: HashMap::new(),
+            seen_order: VecDeque::new(),
+            accepted: Vec::new(),
+            max_seen: max_seen.max(1),
+            max_pending: max_pending.max(1),
+        }
+    }
+
+    /// Records a key's reply, forgetting the oldest keys past `max_seen`
+    fn remember(&mut self, idempotency_key: String, proof_id: ProofId, fingerprint: u64) {
+        self.seen_order.push_back(idempotency_key.clone());
+        self.seen.insert(idempotency_key, (proof_id, fingerprint));
+        while self.seen.len() > self.max_seen {
+            match self.seen_order.pop_front() {
+                Some(oldest) => self.seen.remove(&oldest),
+                None => break,
+            };
+        }
+    }
+
+    /// Reply for a key seen before: the original ID, or a rejection if the payload differs
+    fn replay(&self, idempotency_key: &str, fingerprint: u64) -> Option<ProofSubmitResponse> {
+        let (proof_id, seen_fingerprint) = self.seen.get(idempotency_key)?;
+        if *seen_fingerprint != fingerprint {
+            return Some(ProofSubmitResponse::Rejected {
+                retryable: false,
+                reason: "Idempotency key reused for a different proof".into(),
+            });
+        }
+        Some(ProofSubmitResponse::Accepted(ProofAck {
+            idempotency_key: idempotency_key.to_string(),
+            proof_id: *proof_id,
+            duplicate: true,
+        }))
+    }
+
+    fn is_full(&self) -> bool {
+        self.accepted.len() >= self.max_pending
+    }
+}
+
+/// Proof intake shared by every leader connection
+#[derive(Clone)]
+pub struct ProofIntake {
+    state: Arc<Mutex<IntakeState>>,
+    check: IntakeCheck,
+}
+
+impl ProofIntake {
+    pub fn new() -> Self {
+        Self::with_check(handle_incoming_proof)
+    }
+
+    /// Intake with a custom validation step (e.g. a local test stub)
+    pub fn with_check(check: IntakeCheck) -> Self {
+        Self::with_limits(check, MAX_SEEN_KEYS, MAX_PENDING_PROOFS)
+    }
+
+    /// Intake with custom memory bounds
+    pub fn with_limits(check: IntakeCheck, max_seen: usize, max_pending: usize) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(IntakeState::new(max_seen, max_pending))),
+            check,
+        }
+    }
+
+    /// Handles one submission, returning the reply to send
+    pub fn submit(&self, request: ProofSubmitRequest) -> ProofSubmitResponse {
+        if let Err(reason) = check_backend(&request.backend, ALLOW_MOCK_PROOFS) {
+            return ProofSubmitResponse::Rejected { retryable: false, reason };
+        }
+
+        let fingerprint = payload_fingerprint(&request);
+        if let Some(reply) = self.lock().replay(&request.idempotency_key, fingerprint) {
+            return reply;
+        }
+        if self.lock().is_full() {
+            return queue_full();
+        }
+
+        let proof = match (self.check)(IncomingProof {
+            vault_id: request.vault_id,
+            token: request.token,
+            size: request.size,
+            owner_hash: request.owner_hash,
+            zk_payload: request.zk_payload,
+            timestamp: request.timestamp,
+        }) {
+            Ok(proof) => proof,
+            Err(reason) => return ProofSubmitResponse::Rejected { retryable: false, reason },
+        };
+
+        let mut state = self.lock();
+        // Another connection may have accepted the same key while we checked
+        if let Some(reply) = state.replay(&request.idempotency_key, fingerprint) {
+            return reply;
+        }
+        if state.is_full() {
+            return queue_full();
+        }
+
+        let sequence = state.next_sequence;
+        state.next_sequence += 1;
+        let proof_id = intake_proof_id(sequence, &request.idempotency_key);
+
+        println!("[INTAKE] Accepted proof {} for vault {}", hex::encode(proof_id), proof.vault_id);
+
+        state.remember(request.idempotency_key.clone(), proof_id, fingerprint);
+        state.accepted.push(AcceptedProof {
+            proof_id,
+            idempotency_key: request.idempotency_key.clone(),
+            circuit: request.circuit,
+            public_inputs_hash: request.public_inputs_hash,
+            proof,
+        });
+
+        ProofSubmitResponse::Accepted(ProofAck {
+            idempotency_key: request.idempotency_key,
+            proof_id,
+            duplicate: false,
+        })
+    }
+
+    /// Drains accepted proofs in arrival order
+    pub fn take_accepted(&self) -> Vec<AcceptedProof> {
+        std::mem::take(&mut self.lock().accepted)
+    }
+
+    /// State updates are single inserts/pushes, so a poisoned lock still
+    /// guards consistent state
+    fn lock(&self) -> MutexGuard<'_, IntakeState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Serves one leader connection until it closes
+    pub fn serve_connection(&self, mut stream: TcpStream) -> Result<(), WireError> {
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        loop {
+            let reply = match read_frame(&mut stream) {
+                Ok(WireMessage::Submit(request)) => self.submit(request),
+                Ok(WireMessage::Response(_)) => ProofSubmitResponse::Rejected {
+                    retryable: false,
+                    reason: "Expected a submission".into(),
+                },
+                // Peer closed the connection between requests
+                Err(WireError::Io(_)) => return Ok(()),
+                Err(e) => {
+                    let reply = ProofSubmitResponse::Rejected {
+                        retryable: false,
+                        reason: format!("Malformed frame: {:?}", e),
+                    };
+                    write_frame(&mut stream, &WireMessage::Response(reply))?;
+                    return Err(e);
+                }
+            };
+            write_frame(&mut stream, &WireMessage::Response(reply))?;
+        }
+    }
+}
+
+impl Default for ProofIntake {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Proof ID = Poseidon(INTAKE || sequence || idempotency key as limbs).
+/// Keys are opaque labels of any length, so they are hashed as raw bytes.
+pub fn intake_proof_id(sequence: u64, idempotency_key: &str) -> ProofId {
+    let mut inputs = vec![u64_to_fp(INTAKE_DOMAIN), u64_to_fp(sequence)];
+    inputs.extend(bytes_to_limbs(RAW_BYTES_TAG, idempotency_key.as_bytes()));
+
+    let mut hasher = PoseidonHasher::new();
+    hasher.hash(&inputs).to_repr()
+}
+
+/// Retryable rejection while the accepted queue is full
+fn queue_full() -> ProofSubmitResponse {
+    ProofSubmitResponse::Rejected {
+        retryable: true,
+        reason: "Intake queue full".into(),
+    }
+}
+
+/// Rejects proofs from backends that prove nothing
+fn check_backend(backend: &str, allow_mock: bool) -> Result<(), String> {
+    if backend == MOCK_BACKEND && !allow_mock {
+        return Err("Mock proofs are not accepted".to_string());
+    }
+    Ok(())
+}
+
+fn payload_fingerprint(request: &ProofSubmitRequest) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    request.vault_id.hash(&mut hasher);
+    request.circuit.hash(&mut hasher);
+    request.backend.hash(&mut hasher);
+    request.public_inputs_hash.hash(&mut hasher);
+    request.zk_payload.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Binds `addr` and serves every connection on its own thread.
+/// Returns the bound address (useful with port 0) and the accept loop handle.
+pub fn spawn_intake_server(addr: &str, intake: ProofIntake) -> io::Result<(SocketAddr, JoinHandle<()>)> {
+    let listener = TcpListener::bind(addr)?;
+    let local_addr = listener.local_addr()?;
+    println!("[INTAKE] Listening for vault leaders on {}", local_addr);
+
+    let handle = thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let intake = intake.clone();
+                    thread::spawn(move || {
+                        if let Err(e) = intake.serve_connection(stream) {
+                            eprintln!("[INTAKE] Connection closed with error: {:?}", e);
+                        }
+                    });
+                }
+                Err(e) => eprintln!("[INTAKE] Accept failed: {}", e),
+            }
+        }
+    });
+
+    Ok((local_addr, handle))
+}
+
+/// Local validator stub: accepts any non-empty submission, no vault or hash checks
+pub fn stub_check(proof: IncomingProof) -> Result<NormalizedProof, String> {
+    if proof.zk_payload.is_empty() {
+        return Err("Empty proof payload".to_string());
+    }
+    Ok(NormalizedProof {
+        vault_id: proof.vault_id,
+        token: proof.token,
+        size: proof.size,
+        owner_hash: proof.owner_hash,
+        zk_payload: proof.zk_payload,
+        timestamp: proof.timestamp,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(key: &str, payload: Vec<u8>) -> ProofSubmitRequest {
+        ProofSubmitRequest {
+            idempotency_key: key.into(),
+            vault_id: "vault-1".into(),
+            token: "dBTC".into(),
+            size: 5,
+            owner_hash: "00".repeat(32),
+            circuit: "domex.circuit.trade.v1".into(),
+            backend: "plonky2".into(),
+            public_inputs_hash: key.into(),
+            zk_payload: payload,
+            timestamp: 1,
+        }
+    }
+
+    #[test]
+    fn test_idempotent_acceptance() {
+        let intake = ProofIntake::with_check(stub_check);
+
+        let first = intake.submit(request("k1", vec![1, 2]));
+        let again = intake.submit(request("k1", vec![1, 2]));
+        let (first, again) = match (first, again) {
+            (ProofSubmitResponse::Accepted(a), ProofSubmitResponse::Accepted(b)) => (a, b),
+            other => panic!("unexpected replies: {:?}", other),
+        };
+
+        assert!(!first.duplicate);
+        assert!(again.duplicate);
+        assert_eq!(first.proof_id, again.proof_id);
+        assert_eq!(intake.take_accepted().len(), 1);
+
+        // Same key, different proof
+        assert!(matches!(
+            intake.submit(request("k1", vec![9])),
+            ProofSubmitResponse::Rejected { retryable: false, .. }
+        ));
+    }
+
+    #[test]
+    fn test_failed_check_is_not_remembered() {
+        let intake = ProofIntake::with_check(stub_check);
+
+        assert!(matches!(
+            intake.submit(request("k2", Vec::new())),
+            ProofSubmitResponse::Rejected { retryable: false, .. }
+        ));
+        assert!(matches!(
+            intake.submit(request("k2", vec![1])),
+            ProofSubmitResponse::Accepted(ProofAck { duplicate: false, .. })
+        ));
+    }
+
+    #[test]
+    fn test_long_keys_hash_in_full() {
+        // Realistic "<circuit>:<public inputs hash>" keys share a 32-byte prefix
+        let a = format!("domex.circuit.trade.v1:{}", "zz".repeat(32));
+        let b = format!("domex.circuit.trade.v1:{}", "zy".repeat(32));
+        assert_ne!(intake_proof_id(0, &a), intake_proof_id(0, &b));
+        assert_ne!(intake_proof_id(0, &a), intake_proof_id(1, &a));
+
+        let intake = ProofIntake::with_check(stub_check);
+        for key in [&a, &b] {
+            assert!(matches!(
+                intake.submit(request(key, vec![1])),
+                ProofSubmitResponse::Accepted(ProofAck { duplicate: false, .. })
+            ));
+        }
+        assert_eq!(intake.take_accepted().len(), 2);
+    }
+
+    #[test]
+    fn test_mock_backend_rejected_outside_tests() {
+        assert!(check_backend("mock", false).is_err());
+        assert!(check_backend("plonky2", false).is_ok());
+        assert!(check_backend("mock", true).is_ok());
+    }
+
+    #[test]
+    fn test_intake_memory_is_bounded() {
+        let intake = ProofIntake::with_limits(stub_check, 2, 2);
+        for key in ["a", "b"] {
+            assert!(matches!(intake.submit(request(key, vec![1])), ProofSubmitResponse::Accepted(_)));
+        }
+
+        // Queue full: retry later
+        assert!(matches!(
+            intake.submit(request("c", vec![1])),
+            ProofSubmitResponse::Rejected { retryable: true, .. }
+        ));
+        assert_eq!(intake.take_accepted().len(), 2);
+        assert!(matches!(intake.submit(request("c", vec![1])), ProofSubmitResponse::Accepted(_)));
+
+        // "a" was evicted from the replay window; "c" is still remembered
+        assert!(matches!(
+            intake.submit(request("c", vec![1])),
+            ProofSubmitResponse::Accepted(ProofAck { duplicate: true, .. })
+        ));
+        assert!(matches!(
+            intake.submit(request("a", vec![1])),
+            ProofSubmitResponse::Accepted(ProofAck { duplicate: false, .. })
+        ));
+    }
+}
@@ -1,72 +1,117 @@
 // src/validator/proof_receiver.rs
 
-use std::collections::HashMap;
-use crate::types::proofs::ProofSubmission;
-use crate::validator::attestation::ProofAttestation;
-use crate::validator::slashing_engine::SlashingEngine;
-use crate::validator::global_attestor::GlobalAttestor;
-use crate::validator::cometbft_broadcast::CometBroadcaster;
-use crate::validator::proof_registry::ProofRegistry;
-use crate::common::zk_utils::verify_full_zk_proof;
+// Domex Validator Proof Receiver
+// Drains proofs accepted by the TCP intake (proof_intake_server.rs),
+// verifies each one and turns the valid ones into this validator's signed
+// attestations for the attestation round.
+//
+// - A proof is attested over `zk_root = proof_commitment(zk_payload)`, so a
+//   signed attestation of an invalid proof is slashable evidence
+// - Proofs failing verification are dropped and logged, never attested
 
-/// Handles receiving and routing submitted ZK proofs from origin validators
+use crate::common::signing::SigningKey;
+use crate::validator::attestation::{build_attestation, ProofAttestation};
+use crate::validator::proof_intake_server::ProofIntake;
+use crate::validator::slashing::proof_commitment;
+use crate::validator::types::proof_intake_server::AcceptedProof;
+
+/// Verification applied to every accepted proof before it is attested
+pub type ProofVerifier = fn(&AcceptedProof) -> bool;
+
+/// Hands intake proofs to verification and attestation
 pub struct ProofReceiver {
-    pub attestor: GlobalAttestor,
-    pub slashing: SlashingEngine,
-    pub broadcaster: CometBroadcaster,
-    pub registry: ProofRegistry,
+    pub intake: ProofIntake,
+    pub validator_id: String,
+    key: SigningKey,
+    verify: ProofVerifier,
 }
 
 impl ProofReceiver {
-    /// Create a new receiver
-    pub fn new(
-        attestor: GlobalAttestor,
-        slashing: SlashingEngine,
-        broadcaster: CometBroadcaster,
-        registry: ProofRegistry,
-    ) -> Self {
+    /// Receiver attesting as `validator_id`, signing with `key`
+    pub fn new(intake: ProofIntake, validator_id: &str, key: SigningKey, verify: ProofVerifier) -> Self {
         Self {
-            attestor,
-            slashing,
-            broadcaster,
-            registry,
+            intake,
+            validator_id: validator_id.to_string(),
+            key,
+            verify,
         }
     }
 
-    /// Process a proof submission from a global validator
-    pub fn process_submission(&mut self, submission: ProofSubmission) -> Result<(), String> {
-        // Verify the ZK proof
-        if !verify_full_zk_proof(&submission.zk_proof) {
-            self.slashing.mark_invalid(&submission.validator_id, "Invalid ZK proof");
-            return Err("Invalid proof".into());
+    /// Drains the intake queue, returning one signed attestation per verified
+    /// proof in arrival order, each for the slot (`epoch`, proof ID)
+    pub fn process_accepted(&self, epoch: u64) -> Vec<ProofAttestation> {
+        self.intake
+            .take_accepted()
+            .into_iter()
+            .filter_map(|accepted| self.attest(&accepted, epoch))
+            .collect()
+    }
+
+    fn attest(&self, accepted: &AcceptedProof, epoch: u64) -> Option<ProofAttestation> {
+        if !(self.verify)(accepted) {
+            println!(
+                "[RECEIVER] Proof {} for vault {} failed verification",
+                hex::encode(accepted.proof_id),
+                accepted.proof.vault_id
+            );
+            return None;
         }
 
-        // Check if this validator already submitted for this vault
-        if self.registry.has_submitted(&submission.validator_id, &submission.vault_id) {
-            return Err("Duplicate submission".into());
+        let zk_root = proof_commitment(&accepted.proof.zk_payload);
+        let proof_id = hex::encode(accepted.proof_id);
+        Some(build_attestation(&accepted.proof, &zk_root, epoch, &proof_id, &self.validator_id, &self.key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::proof_wire::{ProofSubmitRequest, ProofSubmitResponse};
+    use crate::common::signing::fixtures::key;
+    use crate::validator::attestation::{verify_attestation, verify_attestation_hash};
+    use crate::validator::proof_intake_server::stub_check;
+
+    fn request(key: &str, payload: Vec<u8>) -> ProofSubmitRequest {
+        ProofSubmitRequest {
+            idempotency_key: key.into(),
+            vault_id: "vault-1".into(),
+            token: "dBTC".into(),
+            size: 5,
+            owner_hash: "00".repeat(32),
+            circuit: "domex.circuit.trade.v1".into(),
+            backend: "plonky2".into(),
+            public_inputs_hash: key.into(),
+            zk_payload: payload,
+            timestamp: 1,
         }
+    }
 
-        // Construct attestation
-        let attestation = ProofAttestation {
-            vault_id: submission.vault_id.clone(),
-            token: submission.token.clone(),
-            size: submission.size,
-            owner_hash: submission.owner_hash.clone(),
-            zk_root: submission.zk_root.clone(),
-            attestation_hash: submission.attestation_hash.clone(),
-            timestamp: submission.timestamp,
-        };
+    // Stand-in verifier: payloads starting with 0xff are invalid
+    fn first_byte_check(accepted: &AcceptedProof) -> bool {
+        accepted.proof.zk_payload.first() != Some(&0xff)
+    }
+
+    #[test]
+    fn test_drains_intake_and_attests_verified_proofs() {
+        let intake = ProofIntake::with_check(stub_check);
+        let public_key = crate::common::signing::public_key_hex(&key("validator-1"));
+        let receiver = ProofReceiver::new(intake.clone(), "validator-1", key("validator-1"), first_byte_check);
 
-        // Record and propagate attestation
-        self.registry.record_submission(&submission.validator_id, &submission.vault_id);
-        self.attestor.collect(attestation.clone());
+        for (id, payload) in [("k1", vec![1, 2]), ("k2", vec![0xff]), ("k3", vec![3])] {
+            assert!(matches!(intake.submit(request(id, payload)), ProofSubmitResponse::Accepted(_)));
+        }
 
-        // If quorum reached, broadcast proof attestation
-        if self.attestor.has_quorum(&submission.vault_id) {
-            let proof = self.attestor.build_final_attestation(&submission.vault_id);
-            self.broadcaster.propagate_attestation(&proof);
+        let attestations = receiver.process_accepted(3);
+        assert_eq!(attestations.len(), 2);
+        for attestation in &attestations {
+            assert!(verify_attestation_hash(attestation));
+            assert!(verify_attestation(attestation, &public_key));
         }
+        assert_eq!(attestations[0].zk_root, proof_commitment(&[1, 2]));
+        assert_eq!(attestations[0].epoch, 3);
+        assert_ne!(attestations[0].slot(), attestations[1].slot());
 
-        Ok(())
+        // Queue drained
+        assert!(receiver.process_accepted(3).is_empty());
     }
 }
@@ -1,48 +1,207 @@
 // Domex Validator Quorum Sync
-// Aggregates 301 validator attestations and ensures hash-level consensus
+// Aggregates committee attestations and finalizes at a BFT threshold
+//
+// - One vote per committee member (first authenticated attestation counts):
+//   the package's fields must hash to its attestation_hash and the signature
+//   must verify under the member's registered public key; anything else,
+//   including attestations naming non-members, is ignored
+// - Finalizes as soon as one hash reaches the threshold (default 2/3: 201 of 301),
+//   so offline validators cannot stall finality
+// - Records every agreeing, dissenting and missing member for downtime tracking
+// - Reports `Unreachable` once no hash can reach the threshold, so the round
+//   can be abandoned instead of waiting forever
 
-use crate::types::{ZkAttestationPackage, QuorumSyncResult};
+use crate::validator::types::quorum_sync::{QuorumSyncError, QuorumSyncResult, QuorumThreshold, ZkAttestationPackage};
+use crate::validator::attestation::{compute_attestation_hash, verify_attestation_signature};
 use std::collections::HashMap;
 
-/// Syncs validator attestations and checks for exact hash match across the 301 quorum
+/// True if the package commits to its own fields and `public_key` signed it
+fn authentic(att: &ZkAttestationPackage, public_key: &str) -> bool {
+    att.attestation_hash == compute_attestation_hash(&att.vault_id, &att.token, &att.owner_hash, &att.zk_root, att.epoch, &att.proof_id)
+        && verify_attestation_signature(public_key, &att.validator_id, &att.attestation_hash, &att.signature)
+}
+
+/// Syncs committee attestations and finalizes once `threshold` of `committee` agrees.
+/// `public_keys` maps committee members to their registered attestation keys.
 pub fn sync_attestation_quorum(
     incoming_attestations: Vec<ZkAttestationPackage>,
-) -> Result<QuorumSyncResult, String> {
-    if incoming_attestations.len() != 301 {
-        return Err("Invalid quorum size: expected 301 attestations".into());
+    committee: &[String],
+    public_keys: &HashMap<String, String>,
+    threshold: QuorumThreshold,
+) -> Result<QuorumSyncResult, QuorumSyncError> {
+    if committee.is_empty() {
+        return Err(QuorumSyncError::EmptyCommittee);
+    }
+
+    // One authenticated vote per committee member
+    let members: HashMap<&str, &str> = committee
+        .iter()
+        .filter_map(|id| public_keys.get(id).map(|key| (id.as_str(), key.as_str())))
+        .collect();
+    let mut votes: HashMap<String, ZkAttestationPackage> = HashMap::new();
+    for att in incoming_attestations {
+        let Some(key) = members.get(att.validator_id.as_str()) else {
+            continue;
+        };
+        if !votes.contains_key(&att.validator_id) && authentic(&att, key) {
+            votes.insert(att.validator_id.clone(), att);
+        }
     }
 
     // Count how many validators submitted each attestation hash
-    let mut hash_counts: HashMap<String, u32> = HashMap::new();
-    for att in &incoming_attestations {
-        *hash_counts.entry(att.attestation_hash.clone()).or_insert(0) += 1;
+    let mut hash_counts: HashMap<&str, usize> = HashMap::new();
+    for att in votes.values() {
+        *hash_counts.entry(att.attestation_hash.as_str()).or_insert(0) += 1;
     }
 
-    // Identify the most frequent attestation hash
-    let (majority_hash, count) = hash_counts
+    // Ties broken by hash so every validator picks the same leader
+    let (leading_hash, leading_votes) = hash_counts
         .iter()
-        .max_by_key(|entry| entry.1)
-        .ok_or("Failed to compute majority hash")?;
+        .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+        .map(|(hash, count)| (hash.to_string(), *count))
+        .unwrap_or_default();
+
+    let needed = threshold.required(committee.len() as u64) as usize;
+    if leading_votes < needed {
+        let outstanding = committee.len() - votes.len();
+        return Err(if leading_votes + outstanding < needed {
+            QuorumSyncError::Unreachable { leading_votes, needed }
+        } else {
+            QuorumSyncError::Pending { leading_votes, needed, outstanding }
+        });
+    }
 
-    // Require perfect match across 301 validators
-    if *count != 301 {
-        return Err("Attestation quorum failed: inconsistent hashes".into());
+    // Partition the committee, in committee order
+    let mut validators = Vec::new();
+    let mut dissenting = Vec::new();
+    let mut missing = Vec::new();
+    for id in committee {
+        match votes.get(id) {
+            Some(att) if att.attestation_hash == leading_hash => validators.push(id.clone()),
+            Some(_) => dissenting.push(id.clone()),
+            None => missing.push(id.clone()),
+        }
     }
 
-    // Return the first attestation matching that hash as canonical
-    let canonical = incoming_attestations
-        .into_iter()
-        .find(|a| a.attestation_hash == *majority_hash)
-        .ok_or("Canonical attestation not found in quorum")?;
+    let canonical = votes
+        .remove(&validators[0])
+        .expect("agreeing validator has a vote");
+
+    println!(
+        "[QUORUM] Finalized {} with {}/{} ({} dissenting, {} missing)",
+        leading_hash,
+        validators.len(),
+        committee.len(),
+        dissenting.len(),
+        missing.len()
+    );
 
     Ok(QuorumSyncResult {
         zk_root: canonical.zk_root,
         attestation_hash: canonical.attestation_hash,
-        timestamp: canonical.timestamp,
         vault_id: canonical.vault_id,
         token: canonical.token,
         size: canonical.size,
         owner_hash: canonical.owner_hash,
-        validator_ids: vec![canonical.validator_id], // Optional: extend to collect all if needed
+        validators,
+        dissenting,
+        missing,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::signing::fixtures::key;
+    use crate::common::signing::public_key_hex;
+    use crate::validator::attestation::attestation_signature;
+
+    fn committee(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("v{}", i)).collect()
+    }
+
+    fn keys(committee: &[String]) -> HashMap<String, String> {
+        committee.iter().map(|id| (id.clone(), public_key_hex(&key(id)))).collect()
+    }
+
+    /// Signed attestation to the zk root `root-<label>`
+    fn attestation(validator_id: &str, label: &str) -> ZkAttestationPackage {
+        let zk_root = format!("root-{}", label);
+        let attestation_hash = compute_attestation_hash("vault1", "dBTC", "owner", &zk_root, 7, "batch-7");
+        ZkAttestationPackage {
+            validator_id: validator_id.into(),
+            signature: attestation_signature(&key(validator_id), validator_id, &attestation_hash),
+            attestation_hash,
+            zk_root,
+            vault_id: "vault1".into(),
+            token: "dBTC".into(),
+            size: 10,
+            owner_hash: "owner".into(),
+            epoch: 7,
+            proof_id: "batch-7".into(),
+        }
+    }
+
+    #[test]
+    fn test_finalizes_at_201_of_301() {
+        let committee = committee(301);
+        let mut incoming: Vec<_> = committee[..201].iter().map(|id| attestation(id, "good")).collect();
+        incoming.extend(committee[201..211].iter().map(|id| attestation(id, "bad")));
+
+        let result =
+            sync_attestation_quorum(incoming, &committee, &keys(&committee), QuorumThreshold::new(201, 301).unwrap())
+                .unwrap();
+
+        assert_eq!(result.zk_root, "root-good");
+        assert_eq!(result.validators.len(), 201);
+        assert_eq!(result.dissenting, committee[201..211].to_vec());
+        assert_eq!(result.missing.len(), 90);
+    }
+
+    #[test]
+    fn test_pending_then_unreachable() {
+        let committee = committee(301);
+
+        // 200 agree, the rest have not answered yet
+        let incoming: Vec<_> = committee[..200].iter().map(|id| attestation(id, "good")).collect();
+        assert_eq!(
+            sync_attestation_quorum(incoming.clone(), &committee, &keys(&committee), QuorumThreshold::TWO_THIRDS).err(),
+            Some(QuorumSyncError::Pending { leading_votes: 200, needed: 201, outstanding: 101 })
+        );
+
+        // Everyone answered, split 150/151: no hash can finalize
+        let mut split: Vec<_> = committee[..150].iter().map(|id| attestation(id, "a")).collect();
+        split.extend(committee[150..].iter().map(|id| attestation(id, "b")));
+        assert_eq!(
+            sync_attestation_quorum(split, &committee, &keys(&committee), QuorumThreshold::TWO_THIRDS).err(),
+            Some(QuorumSyncError::Unreachable { leading_votes: 151, needed: 201 })
+        );
+    }
+
+    #[test]
+    fn test_unauthenticated_votes_are_not_counted() {
+        let committee = committee(3);
+        let keys = keys(&committee);
+
+        // v1's vote claimed under v2's ID, and v0's vote with a tampered root
+        let mut stolen = attestation("v1", "good");
+        stolen.validator_id = "v2".into();
+        let mut tampered = attestation("v0", "good");
+        tampered.zk_root = "root-evil".into();
+
+        let incoming = vec![stolen, tampered, attestation("v1", "good")];
+        assert_eq!(
+            sync_attestation_quorum(incoming, &committee, &keys, QuorumThreshold::TWO_THIRDS).err(),
+            Some(QuorumSyncError::Pending { leading_votes: 1, needed: 2, outstanding: 2 })
+        );
+
+        // A member with no registered key cannot vote
+        let mut unkeyed = keys.clone();
+        unkeyed.remove("v0");
+        let incoming = vec![attestation("v0", "good"), attestation("v1", "good")];
+        assert_eq!(
+            sync_attestation_quorum(incoming, &committee, &unkeyed, QuorumThreshold::TWO_THIRDS).err(),
+            Some(QuorumSyncError::Pending { leading_votes: 1, needed: 2, outstanding: 2 })
+        );
+    }
+}
@@ -0,0 +1,356 @@
+// src/validator/reward_settlement.rs
+
+// Domex Epoch Reward Settlement
+// Turns finalized work into minted DOMEX once per epoch.
+//
+// - Work is read from each finalized block's `CommitteeFinality`: members
+//   that attested the finalized hash earn their role's `compute_reward`
+//   (the sortition-drawn member as `SelectedMajority`, the rest as
+//   `Core300`); dissenting and missing members earn nothing
+// - A block is identified by its height and finalized hash and counts once:
+//   heights must increase and a hash is never accrued twice; its transaction
+//   count is the trade count of the finalized batch, never a caller's figure
+// - A block over `max_block_reward_budget` is scaled pro rata
+// - At epoch end the total is scaled pro rata again if it exceeds the
+//   token state's `available_reward_pool` or either supply cap (token
+//   state and ledger), so neither mint can fail after the other; it is then
+//   minted through `mint_validator_reward` into the reward escrow of the
+//   token ledger
+// - Each validator's reward is split with its delegators
+//   (`DelegationPool::split_rewards`) and credited to a claimable ledger;
+//   claims move DOMEX from escrow to the claimant
+// - Scaling rounds down; dust is never minted
+
+use std::collections::{HashMap, HashSet};
+
+use crate::common::token_config::max_block_reward_budget;
+use crate::matching::zk::types::proof_batcher::ZkBatchProofInput;
+use crate::token::domex_token::DomexTokenLedger;
+use crate::token::mint_engine::mint_validator_reward;
+use crate::token::token_state::TokenState;
+use crate::validator::types::bftcomet::CommitteeFinality;
+use crate::validator::types::reward_settlement::EpochSettlement;
+use crate::validator::delegation_pool::DelegationPool;
+use crate::validator::staking_pool::StakingPool;
+use crate::validator::validator_rewards::{generate_block_rewards, RewardType, ValidatorReward};
+
+/// Ledger address holding minted but unclaimed rewards
+pub const REWARD_ESCROW: &str = "domex:reward-escrow";
+
+/// Scales `rewards` down pro rata so they sum to at most `budget`.
+/// Returns true if anything was cut.
+fn scale_to_budget(rewards: &mut [(String, u64)], budget: u64) -> bool {
+    let total: u128 = rewards.iter().map(|(_, r)| *r as u128).sum();
+    if total <= budget as u128 {
+        return false;
+    }
+    for (_, reward) in rewards.iter_mut() {
+        *reward = (*reward as u128 * budget as u128 / total) as u64;
+    }
+    true
+}
+
+/// Accrues rewards for finalized work and settles them at epoch end
+pub struct RewardSettlement {
+    epoch: u64,
+    blocks: u64,
+    scaled: bool,
+    last_height: Option<u64>,     // Highest block accrued so far
+    block_hashes: HashSet<String>, // Finalized hashes accrued this epoch
+    accrued: HashMap<String, u64>,
+    claimable: HashMap<String, u64>,
+    pub history: Vec<EpochSettlement>,
+}
+
+impl RewardSettlement {
+    pub fn new(epoch: u64) -> Self {
+        Self {
+            epoch,
+            blocks: 0,
+            scaled: false,
+            last_height: None,
+            block_hashes: HashSet::new(),
+            accrued: HashMap::new(),
+            claimable: HashMap::new(),
+            history: Vec::new(),
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Prices the work of the block finalized at `height` from its committee
+    /// finality and the batch it finalized, and accrues it; returns the block
+    /// total. Unfinalized records, batches from another epoch, heights at or
+    /// below the last accrued one and already accrued hashes are refused.
+    pub fn record_finalized_block(
+        &mut self,
+        height: u64,
+        finality: &CommitteeFinality,
+        batch: &ZkBatchProofInput,
+    ) -> Result<u64, String> {
+        if !finality.finalized {
+            return Err("Block is not finalized".into());
+        }
+        if batch.epoch != self.epoch {
+            return Err(format!("Batch is from epoch {}, settling epoch {}", batch.epoch, self.epoch));
+        }
+        if let Some(last) = self.last_height.filter(|last| height <= *last) {
+            return Err(format!("Block {} already accrued (last height {})", height, last));
+        }
+        if self.block_hashes.contains(&finality.attestation_hash) {
+            return Err(format!("Finalized hash {} already accrued", finality.attestation_hash));
+        }
+
+        let tx_count = batch.trades.len() as u64;
+        let work: Vec<ValidatorReward> = finality
+            .validators
+            .iter()
+            .map(|id| ValidatorReward {
+                validator: id.clone(),
+                reward_type: if finality.selected_majority.as_ref() == Some(id) {
+                    RewardType::SelectedMajority
+                } else {
+                    RewardType::Core300
+                },
+                tx_count,
+            })
+            .collect();
+        let mut rewards: Vec<(String, u64)> = generate_block_rewards(&work).into_iter().collect();
+        rewards.sort();
+        self.scaled |= scale_to_budget(&mut rewards, max_block_reward_budget());
+
+        self.blocks += 1;
+        self.last_height = Some(height);
+        self.block_hashes.insert(finality.attestation_hash.clone());
+        let mut total = 0;
+        for (validator, reward) in rewards {
+            *self.accrued.entry(validator).or_insert(0) += reward;
+            total += reward;
+        }
+        Ok(total)
+    }
+
+    /// Settles the epoch: caps to the reward pool, mints into escrow, credits
+    /// validators and their delegators, then starts the next epoch
+    pub fn settle_epoch(
+        &mut self,
+        state: &mut TokenState,
+        ledger: &mut DomexTokenLedger,
+        staking: &StakingPool,
+        delegations: &DelegationPool,
+    ) -> Result<EpochSettlement, String> {
+        let mut rewards: Vec<(String, u64)> = self.accrued.iter().map(|(v, r)| (v.clone(), *r)).collect();
+        rewards.sort();
+        let earned: u64 = rewards.iter().map(|(_, r)| *r).sum();
+
+        // Both caps are checked before either mint
+        let available_pool = state.available_reward_pool();
+        let budget = available_pool.min(state.mint_headroom()).min(ledger.mint_headroom());
+        let scaled = scale_to_budget(&mut rewards, budget) | self.scaled;
+        rewards.retain(|(_, r)| *r > 0);
+        let minted: u64 = rewards.iter().map(|(_, r)| *r).sum();
+
+        if minted > 0 {
+            mint_validator_reward(state, REWARD_ESCROW, minted)?;
+            ledger.mint(REWARD_ESCROW, minted)?;
+        }
+
+        for (validator, reward) in &rewards {
+            let split = delegations.split_rewards(validator, *reward, staking.get_balance(validator));
+            *self.claimable.entry(validator.clone()).or_insert(0) += split.validator_total();
+            for (delegator, share) in split.delegators {
+                *self.claimable.entry(delegator).or_insert(0) += share;
+            }
+        }
+
+        let settlement = EpochSettlement {
+            epoch: self.epoch,
+            blocks: self.blocks,
+            earned,
+            available_pool,
+            minted,
+            scaled,
+            rewards,
+        };
+        println!(
+            "[REWARDS] Epoch {} settled: {} blocks, earned {}, minted {}{}",
+            settlement.epoch,
+            settlement.blocks,
+            earned,
+            minted,
+            if scaled { " (scaled)" } else { "" }
+        );
+
+        self.history.push(settlement.clone());
+        self.epoch += 1;
+        self.blocks = 0;
+        self.scaled = false;
+        self.block_hashes.clear();
+        self.accrued.clear();
+        Ok(settlement)
+    }
+
+    /// Rewards accrued so far in the current, unsettled epoch
+    pub fn accrued(&self, validator: &str) -> u64 {
+        self.accrued.get(validator).copied().unwrap_or(0)
+    }
+
+    /// Settled rewards an account can claim
+    pub fn claimable(&self, account: &str) -> u64 {
+        self.claimable.get(account).copied().unwrap_or(0)
+    }
+
+    /// Pays out everything claimable by `account` from escrow
+    pub fn claim(&mut self, account: &str, ledger: &mut DomexTokenLedger) -> Result<u64, String> {
+        let amount = self.claimable(account);
+        if amount == 0 {
+            return Ok(0);
+        }
+        ledger.transfer(REWARD_ESCROW, account, amount)?;
+        self.claimable.remove(account);
+        Ok(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::signing::fixtures::key;
+    use crate::common::signing::public_key_hex;
+    use crate::validator::attestation::sign_commission;
+    use crate::validator::delegation_pool::fixtures::{delegate, delegator, funded_ledger};
+    use crate::common::token_config::{DOMEX_TOTAL_SUPPLY, FIRST_VALIDATOR_MINT};
+    use crate::validator::validator_rewards::compute_reward;
+    use crate::matching::zk::types::proof_input::ZkProofInput;
+
+    fn finality(validators: &[&str], dissenting: &[&str], selected: &str) -> CommitteeFinality {
+        CommitteeFinality {
+            attestation_hash: format!("{}/{}", validators.join(","), dissenting.join(",")),
+            finalized: true,
+            validators: validators.iter().map(|id| id.to_string()).collect(),
+            dissenting: dissenting.iter().map(|id| id.to_string()).collect(),
+            missing: vec!["v3".to_string()],
+            selected_majority: Some(selected.to_string()),
+        }
+    }
+
+    /// Finalized batch of `epoch` with `trades` trades
+    fn batch(epoch: u64, trades: usize) -> ZkBatchProofInput {
+        let trade = ZkProofInput {
+            vault_id: "vault1".into(),
+            token: "dBTC".into(),
+            executed_price: 1,
+            size: 1,
+            buyer: "buyer".into(),
+            seller: "seller".into(),
+            delta: Vec::new(),
+            total_liquidity: 1,
+        };
+        ZkBatchProofInput {
+            vault_id: "vault1".into(),
+            epoch,
+            pre_batch_root: "pre".into(),
+            post_batch_root: "post".into(),
+            trades: vec![trade; trades],
+        }
+    }
+
+    fn delegations_with(validator: &str) -> DelegationPool {
+        let keys = HashMap::from([(validator.to_string(), public_key_hex(&key(validator)))]);
+        let terms = sign_commission(&key(validator), validator, 0, 100, 0);
+        let mut delegations = DelegationPool::new();
+        delegations.register_validator(validator.into(), 0, 100, 0, &keys, &terms).unwrap();
+        delegations
+    }
+
+    #[test]
+    fn test_settle_mints_and_splits_with_delegators() {
+        let mut settlement = RewardSettlement::new(7);
+        let mut state = TokenState::new();
+        let mut ledger = funded_ledger(&["holder"], 1_000);
+        let holder = delegator("holder");
+
+        let mut staking = StakingPool::new();
+        staking.deposit("v1".into(), 1_000);
+        let mut delegations = delegations_with("v1");
+        delegate(&mut delegations, &mut ledger, "holder", "v1", 1_000).unwrap();
+
+        // Only members that attested the finalized hash earn; v2 dissents in the second block
+        settlement.record_finalized_block(1, &finality(&["v1", "v2"], &[], "v2"), &batch(7, 10_000)).unwrap();
+        settlement.record_finalized_block(2, &finality(&["v1"], &["v2"], "v2"), &batch(7, 10_000)).unwrap();
+
+        let v1 = 2 * compute_reward(10_000, RewardType::Core300);
+        let v2 = compute_reward(10_000, RewardType::SelectedMajority);
+        let minted_before = state.minted;
+
+        let result = settlement.settle_epoch(&mut state, &mut ledger, &staking, &delegations).unwrap();
+        assert_eq!(result.epoch, 7);
+        assert_eq!(result.blocks, 2);
+        assert_eq!(result.minted, v1 + v2);
+        assert!(!result.scaled);
+        assert_eq!(state.minted - minted_before, v1 + v2);
+        assert_eq!(ledger.balance_of(REWARD_ESCROW), v1 + v2);
+
+        // No commission, equal self and delegated stake: half each
+        assert_eq!(settlement.claimable("v1"), v1 / 2);
+        assert_eq!(settlement.claimable(&holder), v1 / 2);
+        assert_eq!(settlement.claimable("v2"), v2);
+        assert_eq!(settlement.claimable("v3"), 0);
+
+        assert_eq!(settlement.claim(&holder, &mut ledger), Ok(v1 / 2));
+        assert_eq!(ledger.balance_of(&holder), v1 / 2);
+        assert_eq!(settlement.claimable(&holder), 0);
+        assert_eq!(settlement.epoch(), 8);
+    }
+
+    #[test]
+    fn test_mint_fits_both_supply_caps() {
+        let mut settlement = RewardSettlement::new(1);
+        let mut state = TokenState::new();
+        let mut ledger = DomexTokenLedger::new("genesis");
+
+        // The ledger is 5 units from its cap; the token state is not
+        ledger.mint("elsewhere", DOMEX_TOTAL_SUPPLY - FIRST_VALIDATOR_MINT - 5).unwrap();
+        settlement.record_finalized_block(1, &finality(&["v1", "v2"], &[], "v2"), &batch(1, 10_000)).unwrap();
+
+        let minted_before = state.minted;
+        let result = settlement
+            .settle_epoch(&mut state, &mut ledger, &StakingPool::new(), &DelegationPool::new())
+            .unwrap();
+        assert!(result.scaled);
+        assert!(result.minted <= 5);
+        assert_eq!(state.minted - minted_before, result.minted);
+        assert_eq!(ledger.balance_of(REWARD_ESCROW), result.minted);
+    }
+
+    #[test]
+    fn test_each_finalized_block_accrues_once() {
+        let mut settlement = RewardSettlement::new(3);
+        let block = finality(&["v1"], &[], "v1");
+        let total = settlement.record_finalized_block(10, &block, &batch(3, 50)).unwrap();
+        assert_eq!(total, compute_reward(50, RewardType::SelectedMajority));
+
+        // Same block again, at its height or a later one
+        assert!(settlement.record_finalized_block(10, &finality(&["v2"], &[], "v2"), &batch(3, 50)).is_err());
+        assert!(settlement.record_finalized_block(11, &block, &batch(3, 50)).is_err());
+
+        // Work outside the settling epoch or without finality earns nothing
+        assert!(settlement.record_finalized_block(12, &finality(&["v2"], &[], "v2"), &batch(4, 50)).is_err());
+        let unfinalized = CommitteeFinality { finalized: false, ..finality(&["v2"], &[], "v2") };
+        assert!(settlement.record_finalized_block(12, &unfinalized, &batch(3, 50)).is_err());
+
+        assert_eq!(settlement.accrued("v1"), total);
+        assert_eq!(settlement.accrued("v2"), 0);
+    }
+
+    #[test]
+    fn test_scale_to_budget_is_pro_rata_and_rounds_down() {
+        let mut rewards = vec![("a".to_string(), 300), ("b".to_string(), 100)];
+        assert!(scale_to_budget(&mut rewards, 101));
+        assert_eq!(rewards, vec![("a".to_string(), 75), ("b".to_string(), 25)]);
+
+        assert!(!scale_to_budget(&mut rewards, 100));
+    }
+}
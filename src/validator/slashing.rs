@@ -0,0 +1,635 @@
+// src/validator/slashing.rs
+
+// Domex Unified Slashing
+// Single entry point for punishing validators: typed offences, verifiable
+// evidence, a per-offence penalty table, jailing, tombstoning and unjailing.
+//
+// - Evidence is checked before any stake moves; the same evidence is only
+//   ever applied once
+// - Penalties (share of stake burned, jail/tombstone) come from the policy
+//   table, never from call sites
+// - Stake that started unbonding at or after the offence epoch is burned too,
+//   so withdrawing before the evidence lands does not escape the penalty; the
+//   offence epoch is read from the evidence itself (the signed slot epoch),
+//   never supplied by the submitter
+// - Jailed validators are excluded from committees until their jail ends and
+//   they unjail with enough stake left; tombstoned ones never return
+// - An attestation over a single proof commits to it through
+//   `zk_root = proof_commitment(zk_payload)`
+// - Downtime is re-counted from the node's record of closed committees,
+//   and a validator's downtime windows never overlap, so no miss is punished
+//   twice; code tampering needs the offender's own signed code report
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::common::merkle::{hash_leaf, hash_to_hex};
+use crate::validator::types::bftcomet::CommitteeFinality;
+use crate::validator::types::liveness::LivenessConfig;
+use crate::validator::types::quorum_sync::QuorumThreshold;
+use crate::validator::types::slashing::{
+    JailPolicy, Offence, OffencePenalty, SlashRecord, SlashingError, SlashingEvidence, ValidatorStatus,
+};
+use crate::validator::attestation::{
+    code_report_hash, verify_attestation, verify_attestation_hash, verify_attestation_signature, ProofAttestation,
+};
+use crate::validator::delegation_pool::DelegationPool;
+use crate::validator::staking_pool::StakingPool;
+
+/// Basis points in 100%
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Default penalty table
+pub fn default_policy() -> HashMap<Offence, OffencePenalty> {
+    HashMap::from([
+        (Offence::Equivocation, OffencePenalty { slash_bps: 5_000, jail: JailPolicy::Tombstone }),
+        (Offence::InvalidProofAttested, OffencePenalty { slash_bps: 7_500, jail: JailPolicy::Tombstone }),
+        (Offence::AttestationMismatch, OffencePenalty { slash_bps: 1_000, jail: JailPolicy::Jail { epochs: 1_000 } }),
+        (Offence::Downtime, OffencePenalty { slash_bps: 10, jail: JailPolicy::Jail { epochs: 100 } }),
+        (Offence::CodeTampering, OffencePenalty { slash_bps: 7_500, jail: JailPolicy::Tombstone }),
+    ])
+}
+
+/// Penalty for `offence` under the default table
+pub fn default_penalty(offence: Offence) -> OffencePenalty {
+    default_policy()[&offence]
+}
+
+/// `slash_bps` of `stake`, rounded down
+pub fn penalty_amount(stake: u64, slash_bps: u16) -> u64 {
+    (stake as u128 * slash_bps.min(BPS_DENOMINATOR as u16) as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
+/// Commitment an attestation's zk_root makes to a single proof payload
+pub fn proof_commitment(zk_payload: &[u8]) -> String {
+    hash_to_hex(&hash_leaf(zk_payload))
+}
+
+/// Public data evidence is checked against
+pub struct EvidenceContext<'a> {
+    pub attestation_keys: &'a HashMap<String, String>,          // Committee member → public key (hex)
+    pub committee_size: usize,
+    pub finality_threshold: QuorumThreshold,
+    pub liveness: LivenessConfig,                              // Downtime window, min duties and missed share
+    pub finalities: &'a BTreeMap<u64, CommitteeFinality>,      // Committee duty record per closed epoch
+    pub registered_code: &'a HashMap<String, String>,           // Validator → registered code hash
+    pub verify_proof: fn(&[u8]) -> bool,
+}
+
+impl EvidenceContext<'_> {
+    /// Hash commits to the fields and the signature verifies under the named signer's key
+    fn authentic(&self, attestation: &ProofAttestation) -> bool {
+        verify_attestation_hash(attestation)
+            && self
+                .attestation_keys
+                .get(&attestation.validator_id)
+                .is_some_and(|key| verify_attestation(attestation, key))
+    }
+}
+
+impl SlashingEvidence {
+    pub fn offence(&self) -> Offence {
+        match self {
+            SlashingEvidence::Equivocation { .. } => Offence::Equivocation,
+            SlashingEvidence::InvalidProofAttested { .. } => Offence::InvalidProofAttested,
+            SlashingEvidence::AttestationMismatch { .. } => Offence::AttestationMismatch,
+            SlashingEvidence::Downtime { .. } => Offence::Downtime,
+            SlashingEvidence::CodeTampering { .. } => Offence::CodeTampering,
+        }
+    }
+
+    /// Validator the evidence accuses
+    pub fn offender(&self) -> &str {
+        match self {
+            SlashingEvidence::Equivocation { first, .. } => &first.validator_id,
+            SlashingEvidence::InvalidProofAttested { attestation, .. } => &attestation.validator_id,
+            SlashingEvidence::AttestationMismatch { attestation, .. } => &attestation.validator_id,
+            SlashingEvidence::Downtime { validator_id, .. } => validator_id,
+            SlashingEvidence::CodeTampering { validator_id, .. } => validator_id,
+        }
+    }
+
+    /// Epoch the offence was committed in: the attested slot's epoch, the
+    /// start of a downtime window, or the epoch a code report covers
+    pub fn offence_epoch(&self) -> u64 {
+        match self {
+            SlashingEvidence::Equivocation { first, .. } => first.epoch,
+            SlashingEvidence::InvalidProofAttested { attestation, .. }
+            | SlashingEvidence::AttestationMismatch { attestation, .. } => attestation.epoch,
+            SlashingEvidence::Downtime { from_epoch, .. } => *from_epoch,
+            SlashingEvidence::CodeTampering { epoch, .. } => *epoch,
+        }
+    }
+
+    /// Stable identifier, so one piece of evidence is punished once
+    pub fn evidence_id(&self) -> String {
+        let detail = match self {
+            SlashingEvidence::Equivocation { first, second } => {
+                let mut hashes = [first.attestation_hash.as_str(), second.attestation_hash.as_str()];
+                hashes.sort_unstable();
+                format!("{}:{}", hashes[0], hashes[1])
+            }
+            SlashingEvidence::InvalidProofAttested { attestation, .. }
+            | SlashingEvidence::AttestationMismatch { attestation, .. } => attestation.attestation_hash.clone(),
+            SlashingEvidence::Downtime { from_epoch, to_epoch, .. } => format!("{}-{}", from_epoch, to_epoch),
+            SlashingEvidence::CodeTampering { code_hash, epoch, .. } => format!("{}@{}", code_hash, epoch),
+        };
+        format!("{:?}:{}:{}", self.offence(), self.offender(), detail)
+    }
+
+    /// Checks that the evidence proves its offence
+    pub fn verify(&self, ctx: &EvidenceContext) -> Result<(), SlashingError> {
+        match self {
+            SlashingEvidence::Equivocation { first, second } => {
+                if first.validator_id != second.validator_id {
+                    return Err(SlashingError::InvalidEvidence("Attestations from different validators"));
+                }
+                if first.slot() != second.slot() {
+                    return Err(SlashingError::InvalidEvidence("Attestations are for different slots"));
+                }
+                if first.attestation_hash == second.attestation_hash {
+                    return Err(SlashingError::InvalidEvidence("Attestations are identical"));
+                }
+                if !ctx.authentic(first) || !ctx.authentic(second) {
+                    return Err(SlashingError::InvalidEvidence("Attestation not authenticated"));
+                }
+            }
+
+            SlashingEvidence::InvalidProofAttested { attestation, zk_payload } => {
+                if !ctx.authentic(attestation) {
+                    return Err(SlashingError::InvalidEvidence("Attestation not authenticated"));
+                }
+                if attestation.zk_root != proof_commitment(zk_payload) {
+                    return Err(SlashingError::InvalidEvidence("Payload is not the attested proof"));
+                }
+                if (ctx.verify_proof)(zk_payload) {
+                    return Err(SlashingError::InvalidEvidence("Attested proof verifies"));
+                }
+            }
+
+            SlashingEvidence::AttestationMismatch { attestation, quorum } => {
+                if !ctx.authentic(attestation) {
+                    return Err(SlashingError::InvalidEvidence("Attestation not authenticated"));
+                }
+                let finalized = quorum
+                    .first()
+                    .ok_or(SlashingError::InvalidEvidence("Empty quorum"))?;
+
+                let mut signers = HashSet::new();
+                for att in quorum {
+                    if att.slot() != attestation.slot() {
+                        return Err(SlashingError::InvalidEvidence("Quorum is for a different slot"));
+                    }
+                    if att.attestation_hash != finalized.attestation_hash {
+                        return Err(SlashingError::InvalidEvidence("Quorum attestations disagree"));
+                    }
+                    if !ctx.authentic(att) || !signers.insert(att.validator_id.as_str()) {
+                        return Err(SlashingError::InvalidEvidence("Quorum attestation invalid or repeated"));
+                    }
+                }
+                if !ctx.finality_threshold.is_met(signers.len() as u64, ctx.committee_size as u64) {
+                    return Err(SlashingError::InvalidEvidence("Quorum below finality threshold"));
+                }
+                if attestation.attestation_hash == finalized.attestation_hash {
+                    return Err(SlashingError::InvalidEvidence("Attestation matches the finalized hash"));
+                }
+            }
+
+            SlashingEvidence::Downtime { validator_id, from_epoch, to_epoch, duties, missed } => {
+                let liveness = &ctx.liveness;
+                if to_epoch < from_epoch || to_epoch - from_epoch >= liveness.window_epochs {
+                    return Err(SlashingError::InvalidEvidence("Malformed downtime window"));
+                }
+                // Counts come from the finality record, not from the submitter
+                if finality_duties(ctx.finalities, validator_id, *from_epoch, *to_epoch) != (*duties, *missed) {
+                    return Err(SlashingError::InvalidEvidence("Duty counts do not match committee records"));
+                }
+                // Punishable only strictly above the limit
+                if *duties < liveness.min_duties || !liveness.max_missed.is_exceeded((*missed).into(), (*duties).into()) {
+                    return Err(SlashingError::InvalidEvidence("Missed duties within the allowed share"));
+                }
+            }
+
+            SlashingEvidence::CodeTampering { validator_id, code_hash, epoch, signature } => {
+                let registered = ctx
+                    .registered_code
+                    .get(validator_id)
+                    .ok_or(SlashingError::InvalidEvidence("No registered code hash"))?;
+                let signed = ctx.attestation_keys.get(validator_id).is_some_and(|key| {
+                    verify_attestation_signature(key, validator_id, &code_report_hash(code_hash, *epoch), signature)
+                });
+                if !signed {
+                    return Err(SlashingError::InvalidEvidence("Code report not signed by the validator"));
+                }
+                if registered == code_hash {
+                    return Err(SlashingError::InvalidEvidence("Reported code matches registration"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// (duties, missed) of `validator_id` in the committee records of `from..=to`
+pub fn finality_duties(finalities: &BTreeMap<u64, CommitteeFinality>, validator_id: &str, from: u64, to: u64) -> (u32, u32) {
+    finalities.range(from..=to).fold((0, 0), |(duties, missed), (_, finality)| {
+        let member = |ids: &[String]| ids.iter().any(|id| id == validator_id);
+        if member(&finality.missing) {
+            (duties + 1, missed + 1)
+        } else if member(&finality.validators) || member(&finality.dissenting) {
+            (duties + 1, missed)
+        } else {
+            (duties, missed)
+        }
+    })
+}
+
+/// Applies verified evidence to stake and validator status
+pub struct Slasher {
+    policy: HashMap<Offence, OffencePenalty>,
+    status: HashMap<String, ValidatorStatus>,
+    applied: HashSet<String>,               // Evidence IDs already punished
+    downtime_through: HashMap<String, u64>, // Last epoch covered by punished downtime
+    pub records: Vec<SlashRecord>,
+}
+
+impl Slasher {
+    pub fn new() -> Self {
+        Self::with_policy(default_policy())
+    }
+
+    /// Slasher with a custom penalty table (missing offences use the default)
+    pub fn with_policy(policy: HashMap<Offence, OffencePenalty>) -> Self {
+        let mut table = default_policy();
+        table.extend(policy);
+        Self {
+            policy: table,
+            status: HashMap::new(),
+            applied: HashSet::new(),
+            downtime_through: HashMap::new(),
+            records: Vec::new(),
+        }
+    }
+
+    pub fn penalty(&self, offence: Offence) -> OffencePenalty {
+        self.policy[&offence]
+    }
+
+    /// Current status (validators never slashed are active)
+    pub fn status(&self, validator_id: &str) -> ValidatorStatus {
+        self.status.get(validator_id).copied().unwrap_or(ValidatorStatus::Active)
+    }
+
+    /// True if the validator may be selected for committees
+    pub fn is_eligible(&self, validator_id: &str) -> bool {
+        self.status(validator_id) == ValidatorStatus::Active
+    }
+
+    /// Jailed and tombstoned validators, to be excluded from committee selection
+    pub fn excluded(&self) -> HashSet<String> {
+        self.status
+            .iter()
+            .filter(|(_, status)| **status != ValidatorStatus::Active)
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Verifies evidence and applies its penalty: burns stake in `pool` and the
+    /// offender's delegations (including stake that started unbonding at or after
+    /// the evidence's offence epoch) and jails or tombstones the offender
+    pub fn submit_evidence(
+        &mut self,
+        evidence: &SlashingEvidence,
+        ctx: &EvidenceContext,
+        pool: &mut StakingPool,
+        delegations: &mut DelegationPool,
+        epoch: u64,
+    ) -> Result<SlashRecord, SlashingError> {
+        let offence_epoch = evidence.offence_epoch();
+        if offence_epoch > epoch {
+            return Err(SlashingError::InvalidEvidence("Offence epoch is in the future"));
+        }
+        evidence.verify(ctx)?;
+
+        let evidence_id = evidence.evidence_id();
+        if self.applied.contains(&evidence_id) {
+            return Err(SlashingError::DuplicateEvidence);
+        }
+
+        let offender = evidence.offender().to_string();
+        let current = self.status(&offender);
+        if current == ValidatorStatus::Tombstoned {
+            return Err(SlashingError::AlreadyTombstoned);
+        }
+        if let SlashingEvidence::Downtime { from_epoch, .. } = evidence {
+            if let Some(&punished_through) = self.downtime_through.get(&offender) {
+                if *from_epoch <= punished_through {
+                    return Err(SlashingError::OverlappingDowntime { punished_through });
+                }
+            }
+        }
+
+        let offence = evidence.offence();
+        let penalty = self.penalty(offence);
+        let slashed_amount = pool.slash(&offender, penalty.slash_bps, offence_epoch).unwrap_or(0)
+            + delegations.slash(&offender, penalty.slash_bps, offence_epoch);
+
+        let status = match (penalty.jail, current) {
+            (JailPolicy::Tombstone, _) => ValidatorStatus::Tombstoned,
+            (JailPolicy::Jail { epochs }, ValidatorStatus::Jailed { until_epoch }) => ValidatorStatus::Jailed {
+                until_epoch: until_epoch.max(epoch + epochs),
+            },
+            (JailPolicy::Jail { epochs }, _) => ValidatorStatus::Jailed { until_epoch: epoch + epochs },
+            (JailPolicy::None, current) => current,
+        };
+        if status == ValidatorStatus::Active {
+            pool.unlock(&offender); // No jail: slashing alone does not freeze the stake
+        } else {
+            self.status.insert(offender.clone(), status);
+        }
+
+        self.applied.insert(evidence_id.clone());
+        if let SlashingEvidence::Downtime { to_epoch, .. } = evidence {
+            self.downtime_through.insert(offender.clone(), *to_epoch);
+        }
+        let record = SlashRecord {
+            validator_id: offender,
+            offence,
+            evidence_id,
+            slashed_amount,
+            status,
+            epoch,
+        };
+
+        println!(
+            "[SLASH] {:?} by {}: burned {} ({} bps), now {:?}",
+            offence, record.validator_id, slashed_amount, penalty.slash_bps, status
+        );
+        self.records.push(record.clone());
+        Ok(record)
+    }
+
+    /// Ends a served jail term, if enough stake is left to rejoin
+    pub fn unjail(&mut self, validator_id: &str, epoch: u64, pool: &mut StakingPool) -> Result<(), SlashingError> {
+        match self.status(validator_id) {
+            ValidatorStatus::Active => return Err(SlashingError::NotJailed),
+            ValidatorStatus::Tombstoned => return Err(SlashingError::Tombstoned),
+            ValidatorStatus::Jailed { until_epoch } if epoch < until_epoch => {
+                return Err(SlashingError::StillJailed { until_epoch });
+            }
+            ValidatorStatus::Jailed { .. } => {}
+        }
+
+        if pool.get_balance(&validator_id.to_string()) < StakingPool::minimum_required_stake() {
+            return Err(SlashingError::InsufficientStake);
+        }
+
+        self.status.remove(validator_id);
+        pool.unlock(&validator_id.to_string());
+        println!("[SLASH] {} unjailed at epoch {}", validator_id, epoch);
+        Ok(())
+    }
+}
+
+impl Default for Slasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::signing::fixtures::{key, never_verifies};
+    use crate::common::signing::public_key_hex;
+    use crate::validator::delegation_pool::fixtures::{delegate, delegator, funded_ledger};
+    use crate::validator::attestation::{attestation_signature, compute_attestation_hash, sign_code_report, sign_commission};
+
+    /// `id`'s signed attestation of proof-1 in `epoch`
+    fn attest(id: &str, zk_root: &str, epoch: u64) -> ProofAttestation {
+        let attestation_hash = compute_attestation_hash("vault1", "dBTC", "owner", zk_root, epoch, "proof-1");
+        ProofAttestation {
+            vault_id: "vault1".into(),
+            token: "dBTC".into(),
+            size: 1,
+            owner_hash: "owner".into(),
+            zk_root: zk_root.into(),
+            signature: attestation_signature(&key(id), id, &attestation_hash),
+            attestation_hash,
+            epoch,
+            proof_id: "proof-1".into(),
+            validator_id: id.into(),
+        }
+    }
+
+    struct Fixture {
+        keys: HashMap<String, String>,
+        code: HashMap<String, String>,
+        finalities: BTreeMap<u64, CommitteeFinality>,
+        pool: StakingPool,
+        delegations: DelegationPool,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let mut pool = StakingPool::new();
+            let ids = ["v0", "v1", "v2"];
+            for id in ids {
+                pool.deposit(id.to_string(), 4 * StakingPool::minimum_required_stake());
+            }
+            Self {
+                keys: ids.iter().map(|id| (id.to_string(), public_key_hex(&key(id)))).collect(),
+                code: HashMap::from([("v0".to_string(), "good-build".to_string())]),
+                finalities: BTreeMap::new(),
+                pool,
+                delegations: DelegationPool::new(),
+            }
+        }
+
+        fn ctx(&self) -> EvidenceContext<'_> {
+            EvidenceContext {
+                attestation_keys: &self.keys,
+                committee_size: 3,
+                finality_threshold: QuorumThreshold::TWO_THIRDS,
+                liveness: LivenessConfig::default(),
+                finalities: &self.finalities,
+                registered_code: &self.code,
+                verify_proof: never_verifies,
+            }
+        }
+
+        /// Submits against the fixture's own stake and delegations
+        fn submit(&mut self, slasher: &mut Slasher, evidence: &SlashingEvidence) -> Result<SlashRecord, SlashingError> {
+            let mut pool = std::mem::take(&mut self.pool);
+            let mut delegations = std::mem::take(&mut self.delegations);
+            let result = slasher.submit_evidence(evidence, &self.ctx(), &mut pool, &mut delegations, 5);
+            self.pool = pool;
+            self.delegations = delegations;
+            result
+        }
+    }
+
+    #[test]
+    fn test_equivocation_tombstones_once() {
+        let mut f = Fixture::new();
+        let mut slasher = Slasher::new();
+        let evidence = SlashingEvidence::Equivocation {
+            first: attest("v0", "root-a", 1),
+            second: attest("v0", "root-b", 1),
+        };
+
+        let stake = f.pool.get_balance(&"v0".to_string());
+        let record = f.submit(&mut slasher, &evidence).unwrap();
+        assert_eq!(record.slashed_amount, penalty_amount(stake, 5_000));
+        assert_eq!(slasher.status("v0"), ValidatorStatus::Tombstoned);
+
+        let ctx = f.ctx();
+        assert_eq!(
+            slasher.submit_evidence(&evidence, &ctx, &mut StakingPool::new(), &mut DelegationPool::new(), 6),
+            Err(SlashingError::DuplicateEvidence)
+        );
+        assert_eq!(slasher.unjail("v0", 10_000, &mut StakingPool::new()), Err(SlashingError::Tombstoned));
+    }
+
+    #[test]
+    fn test_forged_or_mismatched_evidence_is_rejected() {
+        let f = Fixture::new();
+
+        // Different slots are not equivocation
+        let mut other_proof = attest("v1", "root-b", 1);
+        other_proof.proof_id = "proof-2".into();
+        other_proof.attestation_hash = compute_attestation_hash("vault1", "dBTC", "owner", "root-b", 1, "proof-2");
+        other_proof.signature = attestation_signature(&key("v1"), "v1", &other_proof.attestation_hash);
+        let evidence = SlashingEvidence::Equivocation { first: attest("v1", "root-a", 1), second: other_proof };
+        assert!(evidence.verify(&f.ctx()).is_err());
+        let evidence = SlashingEvidence::Equivocation {
+            first: attest("v1", "root-a", 1),
+            second: attest("v1", "root-b", 2),
+        };
+        assert!(evidence.verify(&f.ctx()).is_err());
+
+        // Signed by someone else
+        let mut forged = attest("v1", "root-a", 1);
+        forged.signature = attestation_signature(&key("v2"), "v1", &forged.attestation_hash);
+        let evidence = SlashingEvidence::Equivocation { first: forged, second: attest("v1", "root-b", 1) };
+        assert!(evidence.verify(&f.ctx()).is_err());
+
+        // Proof payload must be the attested one
+        let evidence = SlashingEvidence::InvalidProofAttested {
+            attestation: attest("v1", &proof_commitment(b"proof"), 1),
+            zk_payload: b"other".to_vec(),
+        };
+        assert!(evidence.verify(&f.ctx()).is_err());
+    }
+
+    #[test]
+    fn test_mismatch_jails_and_unjail_flow() {
+        let mut f = Fixture::new();
+        let mut slasher = Slasher::new();
+        let evidence = SlashingEvidence::AttestationMismatch {
+            attestation: attest("v2", "root-bad", 1),
+            quorum: vec![attest("v0", "root-good", 1), attest("v1", "root-good", 1)],
+        };
+
+        let terms = sign_commission(&key("v2"), "v2", 0, 100, 0);
+        f.delegations.register_validator("v2".into(), 0, 100, 0, &f.keys, &terms).unwrap();
+        let mut ledger = funded_ledger(&["holder"], 10_000);
+        delegate(&mut f.delegations, &mut ledger, "holder", "v2", 10_000).unwrap();
+
+        let stake = f.pool.get_balance(&"v2".to_string());
+        let record = f.submit(&mut slasher, &evidence).unwrap();
+        assert_eq!(record.status, ValidatorStatus::Jailed { until_epoch: 1_005 });
+        assert_eq!(record.slashed_amount, penalty_amount(stake, 1_000) + 1_000);
+        assert_eq!(f.delegations.delegation(&delegator("holder"), "v2"), 9_000);
+        assert!(!slasher.is_eligible("v2"));
+
+        assert_eq!(
+            slasher.unjail("v2", 1_004, &mut f.pool),
+            Err(SlashingError::StillJailed { until_epoch: 1_005 })
+        );
+        assert!(slasher.excluded().contains("v2"));
+        slasher.unjail("v2", 1_005, &mut f.pool).unwrap();
+        assert!(slasher.is_eligible("v2"));
+        assert!(!slasher.excluded().contains("v2"));
+    }
+
+    #[test]
+    fn test_mismatch_quorum_must_be_for_the_same_slot() {
+        let f = Fixture::new();
+        let evidence = SlashingEvidence::AttestationMismatch {
+            attestation: attest("v2", "root-bad", 1),
+            quorum: vec![attest("v0", "root-good", 2), attest("v1", "root-good", 2)],
+        };
+        assert_eq!(evidence.verify(&f.ctx()), Err(SlashingError::InvalidEvidence("Quorum is for a different slot")));
+    }
+
+    #[test]
+    fn test_downtime_is_recounted_and_never_overlaps() {
+        let mut f = Fixture::new();
+        let mut slasher = Slasher::new();
+
+        // v1 sits on every committee and misses 6 of the first 10 epochs
+        for epoch in 0..20u64 {
+            let missed = epoch % 5 < 3 || epoch >= 10;
+            let (validators, missing) = if missed {
+                (vec!["v0".to_string()], vec!["v1".to_string()])
+            } else {
+                (vec!["v0".to_string(), "v1".to_string()], Vec::new())
+            };
+            f.finalities.insert(epoch, CommitteeFinality { validators, missing, ..CommitteeFinality::default() });
+        }
+        let downtime = |from_epoch, to_epoch, duties, missed| SlashingEvidence::Downtime {
+            validator_id: "v1".into(),
+            from_epoch,
+            to_epoch,
+            duties,
+            missed,
+        };
+
+        // Claimed counts must match the finality record
+        assert!(downtime(0, 9, 10, 9).verify(&f.ctx()).is_err());
+        assert_eq!(finality_duties(&f.finalities, "v1", 0, 9), (10, 6));
+        f.submit(&mut slasher, &downtime(0, 9, 10, 6)).unwrap();
+
+        // Same misses in a shifted window are not punished again
+        assert_eq!(
+            f.submit(&mut slasher, &downtime(5, 14, 10, 8)),
+            Err(SlashingError::OverlappingDowntime { punished_through: 9 })
+        );
+    }
+
+    #[test]
+    fn test_code_tampering_needs_signed_report() {
+        let f = Fixture::new();
+        let report = |signer: &str, code_hash: &str| SlashingEvidence::CodeTampering {
+            validator_id: "v0".into(),
+            code_hash: code_hash.into(),
+            epoch: 4,
+            signature: sign_code_report(&key(signer), "v0", code_hash, 4),
+        };
+
+        assert!(report("v0", "patched-build").verify(&f.ctx()).is_ok());
+        // Anyone else's claim about v0's code proves nothing
+        assert!(report("v1", "patched-build").verify(&f.ctx()).is_err());
+        assert!(report("v0", "good-build").verify(&f.ctx()).is_err());
+    }
+
+    #[test]
+    fn test_offence_epoch_comes_from_the_evidence() {
+        let mut f = Fixture::new();
+        let mut slasher = Slasher::new();
+        let (v2, min) = ("v2".to_string(), StakingPool::minimum_required_stake());
+        f.pool.withdraw(&v2, min, 1).unwrap(); // Before the offence
+        f.pool.withdraw(&v2, min, 3).unwrap(); // After it: still punishable
+
+        // Slot in epoch 2
+        let evidence = SlashingEvidence::AttestationMismatch {
+            attestation: attest("v2", "root-bad", 2),
+            quorum: vec![attest("v0", "root-good", 2), attest("v1", "root-good", 2)],
+        };
+        assert_eq!(evidence.offence_epoch(), 2);
+        f.submit(&mut slasher, &evidence).unwrap();
+
+        let unbonding: Vec<u64> = f.pool.unbonding_entries(&v2).iter().map(|e| e.amount).collect();
+        assert_eq!(unbonding, vec![min, min - penalty_amount(min, 1_000)]);
+    }
+}
@@ -0,0 +1,152 @@
+// src/validator/slashing_engine.rs
+
+use chrono::Utc;
+use crate::validator::validator_registry::ValidatorRegistry;
+use crate::validator::delegation_pool::DelegationPool;
+use crate::validator::slashing::{EvidenceContext, Slasher};
+use crate::validator::staking_pool::StakingPool;
+use crate::validator::types::slashing::{SlashRecord, SlashingError, SlashingEvidence, ValidatorStatus};
+
+/// Slashing event record for auditing
+#[derive(Debug, Clone)]
+pub struct SlashingEvent {
+    pub validator_id: String,
+    pub reason: String,
+    pub slash_amount: u64,
+    pub epoch: u64,
+    pub timestamp: u64,
+}
+
+/// Main Domex slashing engine: applies evidence through the `Slasher`
+/// (validator/slashing.rs) and mirrors the result into the registry
+pub struct SlashingEngine {
+    pub registry: ValidatorRegistry,
+    pub slasher: Slasher,
+    pub pool: StakingPool,
+    pub delegations: DelegationPool,
+    pub events: Vec<SlashingEvent>,
+}
+
+impl SlashingEngine {
+    /// Initialize with validator registry (a clone shares the same set,
+    /// e.g. with the attestor) and the stake the penalties burn
+    pub fn new(registry: ValidatorRegistry, pool: StakingPool, delegations: DelegationPool) -> Self {
+        Self {
+            registry,
+            slasher: Slasher::new(),
+            pool,
+            delegations,
+            events: Vec::new(),
+        }
+    }
+
+    /// Verifies and applies evidence. The registry stake is set to what is
+    /// left bonded; tombstoned validators are removed from the registry.
+    pub fn submit_evidence(
+        &mut self,
+        evidence: &SlashingEvidence,
+        ctx: &EvidenceContext,
+        epoch: u64,
+    ) -> Result<SlashRecord, SlashingError> {
+        let record = self.slasher.submit_evidence(evidence, ctx, &mut self.pool, &mut self.delegations, epoch)?;
+
+        let validator_id = &record.validator_id;
+        if record.status == ValidatorStatus::Tombstoned {
+            if let Err(e) = self.registry.remove_tombstoned(validator_id, &self.slasher) {
+                eprintln!("[SLASH] Tombstoned {} not removed from registry: {:?}", validator_id, e);
+            }
+        } else if let Err(e) = self.registry.update_stake(validator_id, self.pool.get_balance(validator_id)) {
+            eprintln!("[SLASH] Registry stake not updated for {}: {:?}", validator_id, e);
+        }
+
+        self.events.push(SlashingEvent {
+            validator_id: validator_id.clone(),
+            reason: format!("{:?}", record.offence),
+            slash_amount: record.slashed_amount,
+            epoch,
+            timestamp: Utc::now().timestamp() as u64,
+        });
+        Ok(record)
+    }
+
+    /// Returns all slash records for monitoring/auditing
+    pub fn all_events(&self) -> &[SlashingEvent] {
+        &self.events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, HashMap};
+    use crate::common::signing::fixtures::{key, never_verifies};
+    use crate::common::signing::public_key_hex;
+    use crate::validator::types::bftcomet::Validator;
+    use crate::validator::types::liveness::LivenessConfig;
+    use crate::validator::types::quorum_sync::QuorumThreshold;
+    use crate::validator::attestation::{attestation_signature, compute_attestation_hash, ProofAttestation};
+
+    fn attest(id: &str, zk_root: &str) -> ProofAttestation {
+        let attestation_hash = compute_attestation_hash("vaultA", "dBTC", "ownerPoseidon", zk_root, 1, "proof-1");
+        ProofAttestation {
+            vault_id: "vaultA".into(),
+            token: "dBTC".into(),
+            size: 5,
+            owner_hash: "ownerPoseidon".into(),
+            zk_root: zk_root.into(),
+            signature: attestation_signature(&key(id), id, &attestation_hash),
+            attestation_hash,
+            epoch: 1,
+            proof_id: "proof-1".into(),
+            validator_id: id.into(),
+        }
+    }
+
+    #[test]
+    fn test_slashing_logic() {
+        let ids = ["v1", "v2", "validator123"];
+        let registry = ValidatorRegistry::new();
+        let mut pool = StakingPool::new();
+        for id in ids {
+            let stake = 4 * StakingPool::minimum_required_stake();
+            registry.add_validator(Validator {
+                id: id.to_string(),
+                stake,
+                last_active_epoch: 0,
+            });
+            pool.deposit(id.to_string(), stake);
+        }
+        let mut engine = SlashingEngine::new(registry, pool, DelegationPool::new());
+
+        let keys: HashMap<String, String> = ids.iter().map(|id| (id.to_string(), public_key_hex(&key(id)))).collect();
+        let (code, finalities) = (HashMap::new(), BTreeMap::new());
+        let ctx = EvidenceContext {
+            attestation_keys: &keys,
+            committee_size: 3,
+            finality_threshold: QuorumThreshold::TWO_THIRDS,
+            liveness: LivenessConfig::default(),
+            finalities: &finalities,
+            registered_code: &code,
+            verify_proof: never_verifies,
+        };
+
+        // A bare hash mismatch proves nothing; the finalizing quorum must be shown
+        let unproven = SlashingEvidence::AttestationMismatch {
+            attestation: attest("validator123", "badRoot"),
+            quorum: vec![attest("v1", "zkRoot")],
+        };
+        assert!(engine.submit_evidence(&unproven, &ctx, 1).is_err());
+
+        let evidence = SlashingEvidence::AttestationMismatch {
+            attestation: attest("validator123", "badRoot"),
+            quorum: vec![attest("v1", "zkRoot"), attest("v2", "zkRoot")],
+        };
+        let record = engine.submit_evidence(&evidence, &ctx, 1).unwrap();
+        assert_eq!(engine.events.len(), 1);
+        assert_eq!(
+            engine.registry.get_validator("validator123").unwrap().stake,
+            engine.pool.get_balance(&"validator123".to_string())
+        );
+        assert!(!engine.slasher.is_eligible(&record.validator_id));
+    }
+}
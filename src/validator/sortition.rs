@@ -0,0 +1,160 @@
+// src/validator/sortition.rs
+
+// Domex Stake-Weighted Sortition
+// Deterministic validator selection that every node (and any third party)
+// can recompute from public data.
+//
+// - Seed   = Poseidon(SORTITION || epoch || previous finalized root)
+// - Draw i = Poseidon(DRAW || seed || i), reduced modulo the remaining stake
+// - Candidates are sorted by validator ID and walked by cumulative stake,
+//   so a validator's chance per draw is proportional to its stake
+// - Draws are without replacement; zero-stake validators are never picked
+
+use pasta_curves::group::ff::PrimeField;
+use crate::common::poseidon_hasher::PoseidonHasher;
+use pasta_curves::Fp;
+
+use crate::validator::types::sortition::{SortitionCandidate, SortitionResult};
+use crate::validator::poseidon_utils::u64_to_fp;
+
+/// Domain tag for sortition seeds
+const SORTITION_DOMAIN: u64 = 4;
+
+/// Domain tag for individual draws
+const DRAW_DOMAIN: u64 = 5;
+
+/// 32 bytes as four u64 limbs (always canonical field elements)
+fn bytes_to_limbs(bytes: &[u8; 32]) -> [Fp; 4] {
+    let limb = |i: usize| u64_to_fp(u64::from_le_bytes(bytes[i * 8..i * 8 + 8].try_into().expect("8-byte limb")));
+    [limb(0), limb(1), limb(2), limb(3)]
+}
+
+/// Epoch seed: Poseidon(SORTITION || epoch || previous_root)
+pub fn sortition_seed(epoch: u64, previous_root: &[u8; 32]) -> [u8; 32] {
+    let [a, b, c, d] = bytes_to_limbs(previous_root);
+    let mut hasher = PoseidonHasher::new();
+    hasher
+        .hash(&[u64_to_fp(SORTITION_DOMAIN), u64_to_fp(epoch), a, b, c, d])
+        .to_repr()
+}
+
+/// Uniform-ish value for draw `index` (128 bits of Poseidon output)
+fn draw_value(seed: &[u8; 32], index: u64) -> u128 {
+    let [a, b, c, d] = bytes_to_limbs(seed);
+    let mut hasher = PoseidonHasher::new();
+    let digest = hasher
+        .hash(&[u64_to_fp(DRAW_DOMAIN), a, b, c, d, u64_to_fp(index)])
+        .to_repr();
+    u128::from_le_bytes(digest[..16].try_into().expect("16-byte prefix"))
+}
+
+/// Draws up to `count` distinct validators, each draw weighted by stake
+pub fn select_weighted(candidates: &[SortitionCandidate], seed: &[u8; 32], count: usize) -> Vec<String> {
+    let mut pool: Vec<&SortitionCandidate> = candidates.iter().filter(|c| c.stake > 0).collect();
+    pool.sort_by(|a, b| a.id.cmp(&b.id));
+    pool.dedup_by(|a, b| a.id == b.id);
+
+    let mut remaining: u128 = pool.iter().map(|c| c.stake as u128).sum();
+    let mut selected = Vec::with_capacity(count.min(pool.len()));
+
+    let mut index = 0u64;
+    while selected.len() < count && !pool.is_empty() {
+        let mut target = draw_value(seed, index) % remaining;
+        index += 1;
+
+        let position = pool
+            .iter()
+            .position(|c| {
+                if target < c.stake as u128 {
+                    true
+                } else {
+                    target -= c.stake as u128;
+                    false
+                }
+            })
+            .expect("target is below total remaining stake");
+
+        let winner = pool.remove(position);
+        remaining -= winner.stake as u128;
+        selected.push(winner.id.clone());
+    }
+
+    selected
+}
+
+/// Runs sortition for `epoch`, chained to the previous finalized root
+pub fn run_sortition(
+    candidates: &[SortitionCandidate],
+    epoch: u64,
+    previous_root: &[u8; 32],
+    count: usize,
+) -> SortitionResult {
+    let seed = sortition_seed(epoch, previous_root);
+    SortitionResult {
+        epoch,
+        previous_root: *previous_root,
+        seed,
+        selected: select_weighted(candidates, &seed, count),
+    }
+}
+
+/// Recomputes a published selection from the same public inputs
+pub fn verify_sortition(candidates: &[SortitionCandidate], result: &SortitionResult) -> bool {
+    result.seed == sortition_seed(result.epoch, &result.previous_root)
+        && select_weighted(candidates, &result.seed, result.selected.len()) == result.selected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidates() -> Vec<SortitionCandidate> {
+        (0..20)
+            .map(|i| SortitionCandidate {
+                id: format!("v{:02}", i),
+                stake: if i == 0 { 0 } else { 100 * i as u64 },
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_selection_ignores_input_order_and_verifies() {
+        let root = [7u8; 32];
+        let result = run_sortition(&candidates(), 3, &root, 5);
+
+        let mut reversed = candidates();
+        reversed.reverse();
+        assert_eq!(run_sortition(&reversed, 3, &root, 5), result);
+        assert!(verify_sortition(&reversed, &result));
+
+        // Distinct, never the zero-stake validator
+        let mut unique = result.selected.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), 5);
+        assert!(!result.selected.contains(&"v00".to_string()));
+
+        let mut tampered = result.clone();
+        tampered.selected[0] = "v00".into();
+        assert!(!verify_sortition(&candidates(), &tampered));
+    }
+
+    #[test]
+    fn test_seed_depends_on_epoch_and_root() {
+        let root = [1u8; 32];
+        assert_ne!(sortition_seed(1, &root), sortition_seed(2, &root));
+        assert_ne!(sortition_seed(1, &root), sortition_seed(1, &[2u8; 32]));
+    }
+
+    #[test]
+    fn test_heavier_stake_wins_more_often() {
+        let pool = vec![
+            SortitionCandidate { id: "heavy".into(), stake: 900 },
+            SortitionCandidate { id: "light".into(), stake: 100 },
+        ];
+        let heavy_wins = (0..200u64)
+            .filter(|epoch| run_sortition(&pool, *epoch, &[0u8; 32], 1).selected[0] == "heavy")
+            .count();
+        assert!(heavy_wins > 150, "heavy won {} of 200", heavy_wins);
+    }
+}
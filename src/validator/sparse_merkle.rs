@@ -0,0 +1,330 @@
+// src/validator/sparse_merkle.rs
+
+// Domex Sparse Merkle Tree for vault balances
+// Fixed depth (VAULT_MERKLE_TREE_HEIGHT = 256), keyed by Poseidon(identity, token).
+//
+// - A key's slot is the key itself, one level per key bit, so every key has
+//   its own slot: no collisions to grind for, and a user's slot never moves
+//   when other users join or leave
+// - Empty subtrees hash to precomputed defaults, only touched nodes are stored
+// - Proofs list only non-default siblings, with a bitmap of which levels they fill
+// - Leaves and nodes use the canonical domain-separated hashes (common/merkle.rs)
+// - Inclusion proofs show a leaf's balances; non-inclusion proofs show the
+//   key's slot is empty, e.g. "no balance" at offboarding
+
+use pasta_curves::group::ff::PrimeField;
+use crate::common::poseidon_hasher::PoseidonHasher;
+
+use crate::common::merkle::{hash_leaf as canonical_leaf, hash_node};
+use crate::common::zk_constants::VAULT_MERKLE_TREE_HEIGHT;
+use crate::matching::core::poseidon_utils::{bytes_to_limbs, string_to_limbs, RAW_BYTES_TAG};
+use crate::validator::poseidon_utils::u64_to_fp;
+use crate::validator::types::sparse_merkle::{SmtError, SmtHash, SmtLeaf, SmtProof};
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Domain tag for balance keys
+const KEY_DOMAIN: u64 = 1;
+
+/// Hash of an empty leaf slot
+pub const EMPTY_LEAF: SmtHash = [0u8; 32];
+
+/// Tree key for an (identity, token) balance: Poseidon(KEY || identity || token),
+/// identity and token as length-prefixed limbs
+pub fn balance_key(identity: &[u8; 32], token: &str) -> SmtHash {
+    let mut inputs = vec![u64_to_fp(KEY_DOMAIN)];
+    inputs.extend(bytes_to_limbs(RAW_BYTES_TAG, identity));
+    inputs.extend(string_to_limbs(token));
+
+    let mut hasher = PoseidonHasher::new();
+    hasher.hash(&inputs).to_repr()
+}
+
+/// Canonical leaf hash over (key || available || reserved)
+pub fn hash_leaf(leaf: &SmtLeaf) -> SmtHash {
+    let mut data = [0u8; 48];
+    data[..32].copy_from_slice(&leaf.key);
+    data[32..40].copy_from_slice(&leaf.available.to_le_bytes());
+    data[40..].copy_from_slice(&leaf.reserved.to_le_bytes());
+    canonical_leaf(&data)
+}
+
+/// Default hashes per level: `[0]` is an empty leaf, `[HEIGHT]` the empty root
+pub fn empty_hashes() -> &'static [SmtHash] {
+    static DEFAULTS: OnceLock<Vec<SmtHash>> = OnceLock::new();
+    DEFAULTS.get_or_init(|| {
+        let mut defaults = Vec::with_capacity(VAULT_MERKLE_TREE_HEIGHT + 1);
+        defaults.push(EMPTY_LEAF);
+        for level in 0..VAULT_MERKLE_TREE_HEIGHT {
+            let below = defaults[level];
+            defaults.push(hash_node(&below, &below));
+        }
+        defaults
+    })
+}
+
+/// Bit `i` of a key read as a little-endian 256-bit integer
+fn bit(key: &SmtHash, i: usize) -> bool {
+    (key[i / 8] >> (i % 8)) & 1 == 1
+}
+
+/// Node index at `level` on a key's path: the key shifted right by `level` bits
+fn path_index(key: &SmtHash, level: usize) -> SmtHash {
+    let (bytes, rem) = (level / 8, level % 8);
+    let mut out = [0u8; 32];
+    for i in 0..32usize.saturating_sub(bytes) {
+        let lo = key[i + bytes] >> rem;
+        let hi = match key.get(i + bytes + 1) {
+            Some(next) if rem > 0 => next << (8 - rem),
+            _ => 0,
+        };
+        out[i] = lo | hi;
+    }
+    out
+}
+
+/// The other child of the same parent
+fn sibling_index(mut index: SmtHash) -> SmtHash {
+    index[0] ^= 1;
+    index
+}
+
+/// Sparse Merkle tree over vault balances
+pub struct SparseMerkleTree {
+    leaves: HashMap<SmtHash, SmtLeaf>,         // key → leaf
+    nodes: HashMap<(usize, SmtHash), SmtHash>, // (level, index) → non-default hash
+}
+
+impl SparseMerkleTree {
+    pub fn new() -> Self {
+        Self {
+            leaves: HashMap::new(),
+            nodes: HashMap::new(),
+        }
+    }
+
+    pub fn root(&self) -> SmtHash {
+        self.node(VAULT_MERKLE_TREE_HEIGHT, &[0u8; 32])
+    }
+
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    pub fn get(&self, key: &SmtHash) -> Option<&SmtLeaf> {
+        self.leaves.get(key)
+    }
+
+    /// Set (identity, token) balances. Zero balances remove the leaf.
+    pub fn set_balance(&mut self, identity: &[u8; 32], token: &str, available: u64, reserved: u64) -> SmtHash {
+        let key = balance_key(identity, token);
+        if available == 0 && reserved == 0 {
+            self.remove(&key);
+            return self.root();
+        }
+        self.insert(SmtLeaf { key, available, reserved })
+    }
+
+    /// Insert or update a leaf. O(depth).
+    pub fn insert(&mut self, leaf: SmtLeaf) -> SmtHash {
+        self.leaves.insert(leaf.key, leaf);
+        self.write_path(&leaf.key, hash_leaf(&leaf));
+        self.root()
+    }
+
+    /// Remove a leaf, returning it if present. O(depth).
+    pub fn remove(&mut self, key: &SmtHash) -> Option<SmtLeaf> {
+        let removed = self.leaves.remove(key)?;
+        self.write_path(key, EMPTY_LEAF);
+        Some(removed)
+    }
+
+    /// Proof that `key` is in the tree, with its leaf
+    pub fn prove_inclusion(&self, key: &SmtHash) -> Result<(SmtLeaf, SmtProof), SmtError> {
+        let leaf = *self.get(key).ok_or(SmtError::KeyNotFound)?;
+        Ok((leaf, self.proof_for(key)))
+    }
+
+    /// Proof that `key` is absent: its slot is empty
+    pub fn prove_non_inclusion(&self, key: &SmtHash) -> Result<SmtProof, SmtError> {
+        if self.leaves.contains_key(key) {
+            return Err(SmtError::KeyPresent);
+        }
+        Ok(self.proof_for(key))
+    }
+
+    fn node(&self, level: usize, index: &SmtHash) -> SmtHash {
+        self.nodes
+            .get(&(level, *index))
+            .copied()
+            .unwrap_or(empty_hashes()[level])
+    }
+
+    fn set_node(&mut self, level: usize, index: SmtHash, hash: SmtHash) {
+        if hash == empty_hashes()[level] {
+            self.nodes.remove(&(level, index));
+        } else {
+            self.nodes.insert((level, index), hash);
+        }
+    }
+
+    /// Rewrite a leaf slot and every ancestor up to the root
+    fn write_path(&mut self, key: &SmtHash, leaf_hash: SmtHash) {
+        self.set_node(0, *key, leaf_hash);
+
+        let mut current = leaf_hash;
+        for level in 0..VAULT_MERKLE_TREE_HEIGHT {
+            let sibling = self.node(level, &sibling_index(path_index(key, level)));
+            current = if bit(key, level) {
+                hash_node(&sibling, &current)
+            } else {
+                hash_node(&current, &sibling)
+            };
+            self.set_node(level + 1, path_index(key, level + 1), current);
+        }
+    }
+
+    fn proof_for(&self, key: &SmtHash) -> SmtProof {
+        let mut bitmap = [0u8; 32];
+        let mut siblings = Vec::new();
+        for level in 0..VAULT_MERKLE_TREE_HEIGHT {
+            let sibling = self.node(level, &sibling_index(path_index(key, level)));
+            if sibling != empty_hashes()[level] {
+                bitmap[level / 8] |= 1 << (level % 8);
+                siblings.push(sibling);
+            }
+        }
+        SmtProof { path: *key, bitmap, siblings }
+    }
+}
+
+impl Default for SparseMerkleTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Folds a slot hash up the proof path; None if the proof is malformed
+fn root_from_path(leaf_hash: SmtHash, proof: &SmtProof) -> Option<SmtHash> {
+    let mut siblings = proof.siblings.iter();
+    let mut current = leaf_hash;
+    for level in 0..VAULT_MERKLE_TREE_HEIGHT {
+        let sibling = if bit(&proof.bitmap, level) {
+            *siblings.next()?
+        } else {
+            empty_hashes()[level]
+        };
+        current = if bit(&proof.path, level) {
+            hash_node(&sibling, &current)
+        } else {
+            hash_node(&current, &sibling)
+        };
+    }
+
+    // Every listed sibling must be used
+    if siblings.next().is_some() {
+        return None;
+    }
+    Some(current)
+}
+
+/// Verifies that `leaf` sits at its key's slot under `root`
+pub fn verify_inclusion(root: &SmtHash, leaf: &SmtLeaf, proof: &SmtProof) -> bool {
+    proof.path == leaf.key && root_from_path(hash_leaf(leaf), proof).as_ref() == Some(root)
+}
+
+/// Verifies that `key`'s slot is empty under `root`
+pub fn verify_non_inclusion(root: &SmtHash, key: &SmtHash, proof: &SmtProof) -> bool {
+    &proof.path == key && root_from_path(EMPTY_LEAF, proof).as_ref() == Some(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inclusion_proof_and_empty_root() {
+        let mut tree = SparseMerkleTree::new();
+        assert_eq!(tree.root(), empty_hashes()[VAULT_MERKLE_TREE_HEIGHT]);
+
+        let root = tree.set_balance(&[1u8; 32], "dBTC", 500, 20);
+        let key = balance_key(&[1u8; 32], "dBTC");
+        let (leaf, proof) = tree.prove_inclusion(&key).unwrap();
+
+        assert_eq!(leaf.available, 500);
+        assert!(verify_inclusion(&root, &leaf, &proof));
+
+        let forged = SmtLeaf { available: 501, ..leaf };
+        assert!(!verify_inclusion(&root, &forged, &proof));
+    }
+
+    #[test]
+    fn test_non_inclusion_survives_other_joins() {
+        let mut tree = SparseMerkleTree::new();
+        tree.set_balance(&[1u8; 32], "dBTC", 500, 0);
+
+        let absent = balance_key(&[9u8; 32], "dBTC");
+
+        tree.set_balance(&[2u8; 32], "dBTC", 100, 0);
+        let root = tree.set_balance(&[3u8; 32], "dUSDT", 7, 0);
+
+        let proof = tree.prove_non_inclusion(&absent).unwrap();
+        assert_eq!(proof.path, absent);
+        assert!(verify_non_inclusion(&root, &absent, &proof));
+
+        // Present keys cannot be proven absent
+        let present = balance_key(&[1u8; 32], "dBTC");
+        assert_eq!(tree.prove_non_inclusion(&present), Err(SmtError::KeyPresent));
+    }
+
+    #[test]
+    fn test_keys_sharing_a_prefix_never_collide() {
+        // Same low 31 bytes, differ only in the top byte
+        let mut a = [0x5au8; 32];
+        let mut b = a;
+        a[31] = 0x01;
+        b[31] = 0x02;
+
+        let mut tree = SparseMerkleTree::new();
+        tree.insert(SmtLeaf { key: a, available: 1, reserved: 0 });
+        let root = tree.insert(SmtLeaf { key: b, available: 2, reserved: 0 });
+
+        for key in [a, b] {
+            let (leaf, proof) = tree.prove_inclusion(&key).unwrap();
+            assert!(verify_inclusion(&root, &leaf, &proof));
+        }
+        assert_eq!(tree.len(), 2);
+
+        // A proof with an unused extra sibling is rejected
+        let (leaf, mut proof) = tree.prove_inclusion(&a).unwrap();
+        proof.siblings.push(EMPTY_LEAF);
+        assert!(!verify_inclusion(&root, &leaf, &proof));
+    }
+
+    #[test]
+    fn test_path_index_shifts_across_bytes() {
+        let mut key = [0u8; 32];
+        key[1] = 0b1000_0001;
+        assert_eq!(path_index(&key, 7)[0], 0b0000_0010);
+        assert_eq!(path_index(&key, 8)[0], 0b1000_0001);
+        assert_eq!(path_index(&key, 256), [0u8; 32]);
+    }
+
+    #[test]
+    fn test_zero_balance_restores_empty_root() {
+        let mut tree = SparseMerkleTree::new();
+        let empty = tree.root();
+
+        tree.set_balance(&[1u8; 32], "dBTC", 500, 0);
+        assert_ne!(tree.root(), empty);
+
+        tree.set_balance(&[1u8; 32], "dBTC", 0, 0);
+        assert_eq!(tree.root(), empty);
+        assert!(tree.is_empty());
+    }
+}
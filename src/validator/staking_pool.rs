@@ -5,15 +5,25 @@
 // Handles staking deposits, withdrawals, slashing, and locked status.
 // Used during validator onboarding and punishment phases.
 //
+// Withdrawals go through an unbonding queue: requested stake stops
+// counting toward the validator's bond but is only claimable after
+// `unbonding_epochs`. Until claimed it is still slashed for offences
+// committed at or before the epoch the unbonding started.
+//
 // Delegation logic is handled separately in delegation_pool.rs.
 //
 
 use std::collections::HashMap;
-use crate::validator::validator_identity::ValidatorId;
-use crate::token::token_config::DOMEX_DECIMAL_MULTIPLIER;
+use crate::validator::types::staking_pool::UnbondingEntry;
+use crate::validator::types::validator_identity::ValidatorId;
+use crate::token::token_config::DOMEX_DECIMALS;
+use crate::validator::slashing::penalty_amount;
 
 pub type Amount = u64;
 
+/// Default epochs between a withdraw request and the stake becoming claimable
+pub const DEFAULT_UNBONDING_EPOCHS: u64 = 1_000;
+
 /// Validator staking state
 #[derive(Debug, Clone)]
 pub struct StakeInfo {
@@ -22,19 +32,33 @@ pub struct StakeInfo {
 }
 
 /// Global staking pool
-#[derive(Default)]
 pub struct StakingPool {
     pool: HashMap<ValidatorId, StakeInfo>,
+    unbonding: HashMap<ValidatorId, Vec<UnbondingEntry>>,
+    unbonding_epochs: u64,
+    next_unbonding_id: u64,
 }
 
 impl StakingPool {
     /// Create a new empty staking pool
     pub fn new() -> Self {
+        Self::with_unbonding_period(DEFAULT_UNBONDING_EPOCHS)
+    }
+
+    /// Empty pool whose withdrawals mature after `unbonding_epochs`
+    pub fn with_unbonding_period(unbonding_epochs: u64) -> Self {
         Self {
             pool: HashMap::new(),
+            unbonding: HashMap::new(),
+            unbonding_epochs,
+            next_unbonding_id: 0,
         }
     }
 
+    pub fn unbonding_epochs(&self) -> u64 {
+        self.unbonding_epochs
+    }
+
     /// Deposit stake for validator
     pub fn deposit(&mut self, validator: ValidatorId, amount: Amount) {
         let entry = self.pool.entry(validator).or_insert(StakeInfo {
@@ -45,16 +69,28 @@ impl StakingPool {
         entry.staked_amount += amount;
     }
 
-    /// Slash validator for bad proof (75%)
-    pub fn slash(&mut self, validator: &ValidatorId) -> Option<Amount> {
+    /// Burn `slash_bps` basis points of a validator's stake and lock the rest.
+    /// Unbonding entries started at or after `offence_epoch` are burned at the
+    /// same rate. Rates come from the slashing policy table (validator/slashing.rs).
+    pub fn slash(&mut self, validator: &ValidatorId, slash_bps: u16, offence_epoch: u64) -> Option<Amount> {
+        let mut penalty = None;
+
         if let Some(info) = self.pool.get_mut(validator) {
-            let penalty = (info.staked_amount * 75) / 100;
-            info.staked_amount -= penalty;
+            let burned = penalty_amount(info.staked_amount, slash_bps);
+            info.staked_amount -= burned;
             info.is_locked = true;
-            Some(penalty)
-        } else {
-            None
+            penalty = Some(burned);
+        }
+
+        if let Some(entries) = self.unbonding.get_mut(validator) {
+            for entry in entries.iter_mut().filter(|e| e.start_epoch >= offence_epoch) {
+                let burned = penalty_amount(entry.amount, slash_bps);
+                entry.amount -= burned;
+                penalty = Some(penalty.unwrap_or(0) + burned);
+            }
         }
+
+        penalty
     }
 
     /// Unlock validator after review
@@ -72,8 +108,9 @@ impl StakingPool {
             .unwrap_or(0)
     }
 
-    /// Withdraw stake (only if not locked)
-    pub fn withdraw(&mut self, validator: &ValidatorId, amount: Amount) -> Result<(), String> {
+    /// Start unbonding stake (only if not locked). The amount leaves the bond
+    /// now and becomes claimable at `epoch + unbonding_epochs`; returns the entry ID.
+    pub fn withdraw(&mut self, validator: &ValidatorId, amount: Amount, epoch: u64) -> Result<u64, String> {
         if let Some(info) = self.pool.get_mut(validator) {
             if info.is_locked {
                 return Err("Stake is locked due to slashing or review".into());
@@ -82,14 +119,95 @@ impl StakingPool {
                 return Err("Insufficient stake".into());
             }
             info.staked_amount -= amount;
-            Ok(())
         } else {
-            Err("Validator not found".into())
+            return Err("Validator not found".into());
         }
+
+        let id = self.next_unbonding_id;
+        self.next_unbonding_id += 1;
+        self.unbonding.entry(validator.clone()).or_default().push(UnbondingEntry {
+            id,
+            amount,
+            start_epoch: epoch,
+            mature_epoch: epoch + self.unbonding_epochs,
+        });
+        Ok(id)
+    }
+
+    /// Release every matured unbonding entry (only if not locked); returns the
+    /// amount paid out
+    pub fn claim_unbonded(&mut self, validator: &ValidatorId, epoch: u64) -> Result<Amount, String> {
+        if self.pool.get(validator).is_some_and(|info| info.is_locked) {
+            return Err("Stake is locked due to slashing or review".into());
+        }
+
+        let entries = match self.unbonding.get_mut(validator) {
+            Some(entries) => entries,
+            None => return Ok(0),
+        };
+        let mut released = 0;
+        entries.retain(|entry| {
+            if entry.is_mature(epoch) {
+                released += entry.amount;
+                false
+            } else {
+                true
+            }
+        });
+        if entries.is_empty() {
+            self.unbonding.remove(validator);
+        }
+        Ok(released)
+    }
+
+    /// Pending unbonding entries, oldest first
+    pub fn unbonding_entries(&self, validator: &ValidatorId) -> &[UnbondingEntry] {
+        self.unbonding.get(validator).map_or(&[], |entries| entries.as_slice())
+    }
+
+    /// Total stake still unbonding (matured but unclaimed included)
+    pub fn unbonding_balance(&self, validator: &ValidatorId) -> Amount {
+        self.unbonding_entries(validator).iter().map(|e| e.amount).sum()
     }
 
     /// Minimum stake required to join (example: 10,000 DOMEX)
     pub fn minimum_required_stake() -> Amount {
-        10_000 * DOMEX_DECIMAL_MULTIPLIER
+        10_000 * DOMEX_DECIMALS
+    }
+}
+
+impl Default for StakingPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_withdraw_unbonds_and_stays_slashable() {
+        let v = "v1".to_string();
+        let mut pool = StakingPool::with_unbonding_period(10);
+        pool.deposit(v.clone(), 1_000);
+
+        let early = pool.withdraw(&v, 200, 5).unwrap();
+        pool.withdraw(&v, 100, 8).unwrap();
+        assert_eq!(pool.get_balance(&v), 700);
+        assert_eq!(pool.unbonding_balance(&v), 300);
+        assert_eq!(pool.claim_unbonded(&v, 14), Ok(0));
+
+        // Offence at epoch 6: the epoch-5 entry had already left the bond
+        assert_eq!(pool.slash(&v, 1_000, 6), Some(70 + 10));
+        assert_eq!(pool.unbonding_entries(&v)[0].id, early);
+        assert_eq!(pool.unbonding_entries(&v)[0].amount, 200);
+        assert_eq!(pool.unbonding_entries(&v)[1].amount, 90);
+
+        assert!(pool.claim_unbonded(&v, 15).is_err()); // Locked by the slash
+        pool.unlock(&v);
+        assert_eq!(pool.claim_unbonded(&v, 15), Ok(200));
+        assert_eq!(pool.claim_unbonded(&v, 18), Ok(90));
+        assert!(pool.unbonding_entries(&v).is_empty());
     }
 }
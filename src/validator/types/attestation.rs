@@ -19,4 +19,6 @@ pub struct ProofAttestation {
     pub zk_root: String,
     pub attestation_hash: String,
     pub timestamp: u64,
+    pub validator_id: String,
+    pub signature: String,
 }
@@ -1,6 +1,7 @@
 // src/types/bftcomet.rs
 
 use serde::{Serialize, Deserialize};
+use crate::validator::types::slashing::SlashingEvidence;
 
 /// Validator representation in the Domex global validator set
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -26,3 +27,51 @@ pub struct MajorityValidators(pub Vec<String>); // List of Validator IDs
 /// Full committee consisting of the minority committee plus one selected majority validator
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FullCommittee(pub Vec<String>); // List of Validator IDs
+
+/// Why an attestation was left out of slashing detection
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttestationRejection {
+    /// Signer is not in the current full committee
+    NotCommitteeMember,
+
+    /// Signer already attested this round (first attestation counts)
+    Duplicate,
+
+    /// Signature does not verify under the signer's registered public key
+    BadSignature,
+}
+
+/// An attestation dropped before counting, with its claimed signer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RejectedAttestation {
+    pub validator_id: String,
+    pub reason: AttestationRejection,
+}
+
+/// Result of checking a round's attestations for slashable behaviour
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlashDetection {
+    pub quorum_hash: Option<String>,        // Most attested hash among valid attestations
+    pub slashable: Vec<String>,             // Authenticated signers of a different hash
+    pub rejected: Vec<RejectedAttestation>, // Not counted, not attributable
+}
+
+/// Committee members of a closed epoch partitioned by how they attested its
+/// leading hash; kept for every epoch, finalized or not, as the duty record
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitteeFinality {
+    pub attestation_hash: String, // Leading hash (empty if nobody attested)
+    pub finalized: bool,          // Leading hash reached the finality threshold
+    pub validators: Vec<String>, // Agreed with the leading hash
+    pub dissenting: Vec<String>, // Attested another hash
+    pub missing: Vec<String>,    // Did not attest (downtime)
+    pub selected_majority: Option<String>, // Member drawn from the majority by sortition
+}
+
+/// Outcome of closing an epoch (`BFTCometRotation::epoch_step`)
+#[derive(Debug, Clone)]
+pub struct EpochReport {
+    pub epoch: u64,                          // Epoch that was closed
+    pub finality: Option<CommitteeFinality>, // None if the committee did not finalize
+    pub downtime: Vec<SlashingEvidence>,     // Validators over the downtime limit
+}
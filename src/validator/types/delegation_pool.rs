@@ -0,0 +1,81 @@
+// src/validator/types/delegation_pool.rs
+// Commission, redelegation and reward-split types for delegated staking
+
+/// Commission a validator takes from rewards before delegators are paid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Commission {
+    pub rate_bps: u16,                  // Current rate, in basis points
+    pub max_change_bps: u16,            // Largest change allowed in one epoch
+    pub last_change_epoch: Option<u64>, // At most one change per epoch
+}
+
+/// Delegation operation a delegator signs (`attestation::delegation_hash`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DelegationAction {
+    Delegate = 1,
+    Undelegate = 2,
+    Redelegate = 3,
+}
+
+/// Stake moved between validators; stays slashable for the source
+/// validator's offences until it matures
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redelegation {
+    pub delegator: String,
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    pub start_epoch: u64,
+    pub mature_epoch: u64,
+}
+
+/// One validator's reward divided between commission, its own stake and delegators
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RewardSplit {
+    pub validator_id: String,
+    pub commission: u64,
+    pub self_share: u64,                 // Includes rounding dust
+    pub delegators: Vec<(String, u64)>, // Sorted by delegator ID
+}
+
+impl RewardSplit {
+    /// Commission plus the validator's own pro rata share
+    pub fn validator_total(&self) -> u64 {
+        self.commission + self.self_share
+    }
+}
+
+/// Why a delegation operation was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DelegationError {
+    UnknownValidator,
+    AlreadyRegistered,
+    ZeroAmount,
+    InsufficientDelegation,
+    SameValidator,
+
+    /// Rate above 100%
+    RateTooHigh,
+
+    /// Commission already changed this epoch
+    CommissionChangedThisEpoch,
+
+    /// Change larger than the validator's per-epoch limit
+    CommissionChangeTooLarge { max_change_bps: u16 },
+
+    /// Per-epoch change limit above `MAX_COMMISSION_CHANGE_BPS`
+    MaxChangeTooHigh { limit: u16 },
+
+    /// Commission terms not signed by the validator's registered key, or a
+    /// delegation operation not signed by the delegator for its current nonce
+    BadSignature,
+
+    /// Delegator's ledger balance, or the escrow, cannot cover the transfer
+    InsufficientBalance,
+
+    /// Stake redelegated into the source has not matured yet
+    RedelegationPending,
+
+    /// Delegation would exceed the representable amount
+    Overflow,
+}
@@ -0,0 +1,15 @@
+// src/validator/types/epoch_oracle.rs
+// What drives epoch progression
+
+use serde::{Serialize, Deserialize};
+
+/// Where epoch boundaries come from. Both are anchored at genesis, so every
+/// node derives the same epoch from the same input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EpochSource {
+    /// epoch = (now - genesis_ts) / interval_secs
+    WallClock { genesis_ts: u64, interval_secs: u64 },
+
+    /// epoch = (finalized height - genesis_height) / blocks_per_epoch
+    BlockHeight { genesis_height: u64, blocks_per_epoch: u64 },
+}
@@ -0,0 +1,27 @@
+// src/validator/types/global_attestor.rs
+// Result of a GlobalAttestor round
+
+/// Outcome of one stake-weighted attestation round
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttestationOutcome {
+    /// A batch hash reached the stake threshold
+    Finalized {
+        batch_hash: String,
+        attesting_stake: u64,
+        committee_stake: u64,
+        slashed: Vec<String>, // Committee members who attested a different hash
+    },
+
+    /// No hash reached the threshold; nobody is slashed
+    NoFinality {
+        leading_hash: Option<String>,
+        leading_stake: u64,
+        committee_stake: u64,
+    },
+}
+
+impl AttestationOutcome {
+    pub fn is_finalized(&self) -> bool {
+        matches!(self, AttestationOutcome::Finalized { .. })
+    }
+}
@@ -0,0 +1,48 @@
+// src/validator/types/liveness.rs
+// Configuration for missed-attestation tracking
+
+/// Share of duties a validator may miss; downtime is strictly above it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissedShareLimit {
+    numerator: u64,
+    denominator: u64,
+}
+
+impl MissedShareLimit {
+    pub const HALF: MissedShareLimit = MissedShareLimit { numerator: 1, denominator: 2 };
+
+    pub fn new(numerator: u64, denominator: u64) -> Result<Self, &'static str> {
+        if denominator == 0 || numerator > denominator {
+            return Err("Missed share must be in [0, 1]");
+        }
+        Ok(Self { numerator, denominator })
+    }
+
+    /// True if `missed / duties` is strictly above the limit (never for zero duties)
+    pub fn is_exceeded(&self, missed: u64, duties: u64) -> bool {
+        duties > 0 && missed as u128 * self.denominator as u128 > duties as u128 * self.numerator as u128
+    }
+}
+
+/// When missed attestations count as downtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LivenessConfig {
+    /// Duties older than this many epochs are forgotten
+    pub window_epochs: u64,
+
+    /// Downtime when missed / duties is strictly above this share
+    pub max_missed: MissedShareLimit,
+
+    /// No judgement before this many duties are in the window
+    pub min_duties: u32,
+}
+
+impl Default for LivenessConfig {
+    fn default() -> Self {
+        Self {
+            window_epochs: 100,
+            max_missed: MissedShareLimit::HALF,
+            min_duties: 10,
+        }
+    }
+}
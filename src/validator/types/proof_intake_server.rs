@@ -0,0 +1,14 @@
+// types/proof_intake_server.rs
+
+use crate::common::common_types::ProofId;
+use crate::validator::types::inbound_proof::NormalizedProof;
+
+/// A proof accepted by the intake, waiting for verification and attestation
+#[derive(Debug, Clone)]
+pub struct AcceptedProof {
+    pub proof_id: ProofId,
+    pub idempotency_key: String,
+    pub circuit: String,
+    pub public_inputs_hash: String,
+    pub proof: NormalizedProof,
+}
@@ -12,6 +12,9 @@ pub struct ZkAttestationPackage {
     pub token: String,
     pub size: u64,
     pub owner_hash: String,
+    pub epoch: u64,                 // Oracle epoch of the attested slot, committed to by attestation_hash
+    pub proof_id: String,           // Proof or batch the slot is for, committed to by attestation_hash
+    pub signature: String,          // Signer's secp256k1 signature over (validator_id, attestation_hash)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,5 +24,108 @@ pub struct QuorumSyncResult {
     pub vault_id: String,
     pub token: String,
     pub size: u64,
+    pub owner_hash: String,
     pub validators: Vec<String>,        // List of all validator IDs that agreed
+    pub dissenting: Vec<String>,        // Committee members who attested another hash
+    pub missing: Vec<String>,           // Committee members with no attestation (downtime)
+}
+
+/// Why a quorum could not (yet) be formed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuorumSyncError {
+    /// No committee to form a quorum from
+    EmptyCommittee,
+
+    /// Threshold not reached yet, but outstanding members could still reach it
+    Pending { leading_votes: usize, needed: usize, outstanding: usize },
+
+    /// No hash can reach the threshold even if every outstanding member agrees
+    Unreachable { leading_votes: usize, needed: usize },
+}
+
+/// Fraction of weight (stake or votes) needed for finality.
+/// Met when `part / total >= numerator / denominator`.
+///
+/// Always in [2/3, 1], so two conflicting quorums share more than a third
+/// of the weight. Deserialized values go through the same check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawQuorumThreshold")]
+pub struct QuorumThreshold {
+    numerator: u64,
+    denominator: u64,
+}
+
+/// Unchecked wire form of `QuorumThreshold`
+#[derive(Deserialize)]
+struct RawQuorumThreshold {
+    numerator: u64,
+    denominator: u64,
+}
+
+impl TryFrom<RawQuorumThreshold> for QuorumThreshold {
+    type Error = &'static str;
+
+    fn try_from(raw: RawQuorumThreshold) -> Result<Self, Self::Error> {
+        Self::new(raw.numerator, raw.denominator)
+    }
+}
+
+impl QuorumThreshold {
+    /// Classic BFT supermajority (≥ 2/3, i.e. 201 of 301)
+    pub const TWO_THIRDS: QuorumThreshold = QuorumThreshold { numerator: 2, denominator: 3 };
+
+    pub fn new(numerator: u64, denominator: u64) -> Result<Self, &'static str> {
+        if denominator == 0 || numerator > denominator {
+            return Err("Threshold must be a fraction no greater than 1");
+        }
+        if (3 * numerator as u128) < (2 * denominator as u128) {
+            return Err("Threshold must be at least 2/3");
+        }
+        Ok(Self { numerator, denominator })
+    }
+
+    pub fn numerator(&self) -> u64 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> u64 {
+        self.denominator
+    }
+
+    /// Smallest part of `total` that meets the threshold
+    pub fn required(&self, total: u64) -> u64 {
+        let product = total as u128 * self.numerator as u128;
+        product.div_ceil(self.denominator as u128) as u64
+    }
+
+    /// True if `part` of `total` reaches the threshold. Never met for zero total.
+    pub fn is_met(&self, part: u64, total: u64) -> bool {
+        total > 0 && part as u128 * self.denominator as u128 >= total as u128 * self.numerator as u128
+    }
+}
+
+impl Default for QuorumThreshold {
+    fn default() -> Self {
+        Self::TWO_THIRDS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threshold_bounds() {
+        assert!(QuorumThreshold::new(201, 301).is_ok());
+        assert!(QuorumThreshold::new(3, 3).is_ok());
+        assert!(QuorumThreshold::new(1, 2).is_err());
+        assert!(QuorumThreshold::new(0, 3).is_err());
+        assert!(QuorumThreshold::new(0, 0).is_err());
+        assert!(QuorumThreshold::new(4, 3).is_err());
+
+        let parsed: Result<QuorumThreshold, _> = serde_json::from_str(r#"{"numerator":1,"denominator":2}"#);
+        assert!(parsed.is_err());
+        let parsed: QuorumThreshold = serde_json::from_str(r#"{"numerator":2,"denominator":3}"#).unwrap();
+        assert_eq!(parsed, QuorumThreshold::TWO_THIRDS);
+    }
 }
@@ -0,0 +1,14 @@
+// src/validator/types/reward_settlement.rs
+// Result of one end-of-epoch reward settlement
+
+/// What an epoch earned, what was minted, and why they differ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpochSettlement {
+    pub epoch: u64,
+    pub blocks: u64,                     // Finalized blocks counted
+    pub earned: u64,                     // After per-block budget scaling
+    pub available_pool: u64,             // Reward pool when settling
+    pub minted: u64,                     // Sum of `rewards`
+    pub scaled: bool,                    // True if a budget or the pool cut rewards
+    pub rewards: Vec<(String, u64)>,     // Per validator, sorted by ID
+}
@@ -0,0 +1,131 @@
+// src/validator/types/slashing.rs
+// Offences, evidence and outcomes for the unified slashing module
+
+use crate::validator::attestation::ProofAttestation;
+
+/// Slashable offences
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Offence {
+    /// Two different attestations for the same vault and slot
+    Equivocation,
+
+    /// Attested a proof that does not verify
+    InvalidProofAttested,
+
+    /// Attested a hash other than the one the committee finalized
+    AttestationMismatch,
+
+    /// Missed too many attestation duties
+    Downtime,
+
+    /// Running code that differs from the registered build
+    CodeTampering,
+}
+
+/// Verifiable evidence, one variant per offence
+#[derive(Debug, Clone)]
+pub enum SlashingEvidence {
+    /// Both authenticated by the offender, same slot (epoch, proof ID), different hashes
+    Equivocation {
+        first: ProofAttestation,
+        second: ProofAttestation,
+    },
+
+    /// Authenticated attestation committing to a payload that fails verification
+    InvalidProofAttested {
+        attestation: ProofAttestation,
+        zk_payload: Vec<u8>,
+    },
+
+    /// Offender's authenticated attestation plus the quorum that finalized another hash
+    AttestationMismatch {
+        attestation: ProofAttestation,
+        quorum: Vec<ProofAttestation>,
+    },
+
+    /// Missed duties over an epoch window, re-counted by the verifier from
+    /// its record of closed committees, finalized or not
+    Downtime {
+        validator_id: String,
+        from_epoch: u64,
+        to_epoch: u64,
+        duties: u32,
+        missed: u32,
+    },
+
+    /// The offender's own signed report of the code it runs in `epoch`
+    /// (`attestation::sign_code_report`), differing from its registration
+    CodeTampering {
+        validator_id: String,
+        code_hash: String,
+        epoch: u64,
+        signature: String,
+    },
+}
+
+/// What happens to a validator's participation after a slash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JailPolicy {
+    /// Keep participating
+    None,
+
+    /// Excluded for this many epochs, then may unjail
+    Jail { epochs: u64 },
+
+    /// Excluded permanently; can never unjail
+    Tombstone,
+}
+
+/// Penalty for one offence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OffencePenalty {
+    pub slash_bps: u16, // Share of stake burned, in basis points
+    pub jail: JailPolicy,
+}
+
+/// Participation status of a validator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidatorStatus {
+    Active,
+    Jailed { until_epoch: u64 },
+    Tombstoned,
+}
+
+/// Record of an applied slash
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlashRecord {
+    pub validator_id: String,
+    pub offence: Offence,
+    pub evidence_id: String,
+    pub slashed_amount: u64,
+    pub status: ValidatorStatus,
+    pub epoch: u64,
+}
+
+/// Why evidence or an unjail request was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SlashingError {
+    /// Evidence does not prove the offence
+    InvalidEvidence(&'static str),
+
+    /// Same evidence was already processed
+    DuplicateEvidence,
+
+    /// Downtime window starts inside one that was already punished
+    OverlappingDowntime { punished_through: u64 },
+
+    /// Offender is already tombstoned
+    AlreadyTombstoned,
+
+    /// Validator is not jailed
+    NotJailed,
+
+    /// Jail period has not ended
+    StillJailed { until_epoch: u64 },
+
+    /// Tombstoned validators cannot unjail
+    Tombstoned,
+
+    /// Remaining stake is below the minimum to rejoin
+    InsufficientStake,
+}
@@ -0,0 +1,20 @@
+// src/validator/types/sortition.rs
+// Inputs and results of stake-weighted sortition
+
+use serde::{Serialize, Deserialize};
+
+/// A validator eligible for selection, weighted by stake
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SortitionCandidate {
+    pub id: String,
+    pub stake: u64,
+}
+
+/// A selection anyone can recompute from public data
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SortitionResult {
+    pub epoch: u64,
+    pub previous_root: [u8; 32], // Last finalized root mixed into the seed
+    pub seed: [u8; 32],          // Poseidon(SORTITION || epoch || previous_root)
+    pub selected: Vec<String>,   // In draw order
+}
@@ -0,0 +1,33 @@
+// src/types/sparse_merkle.rs
+
+use serde::{Serialize, Deserialize};
+
+/// 32-byte node hash (canonical Pasta Fp encoding)
+pub type SmtHash = [u8; 32];
+
+/// A balance leaf in the sparse vault tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmtLeaf {
+    pub key: SmtHash,   // Poseidon(identity, token)
+    pub available: u64, // Spendable balance
+    pub reserved: u64,  // Locked by resting orders
+}
+
+/// Authentication path from a leaf slot to the root (leaf level first).
+/// Only non-default siblings are listed; `bitmap` bit L is set when level L has one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmtProof {
+    pub path: SmtHash,          // Leaf slot: the key itself, one bit per level
+    pub bitmap: SmtHash,        // Levels whose sibling is listed
+    pub siblings: Vec<SmtHash>, // Non-default siblings, bottom-up
+}
+
+/// Sparse Merkle tree errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SmtError {
+    /// Key is not in the tree
+    KeyNotFound,
+
+    /// Key is in the tree, so non-inclusion cannot be proven
+    KeyPresent,
+}
@@ -0,0 +1,17 @@
+// src/validator/types/staking_pool.rs
+// Unbonding entries for delayed stake withdrawal
+
+/// Stake leaving the pool; still slashable until it matures and is claimed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnbondingEntry {
+    pub id: u64,
+    pub amount: u64,
+    pub start_epoch: u64,  // Epoch the withdraw was requested
+    pub mature_epoch: u64, // First epoch the amount can be claimed
+}
+
+impl UnbondingEntry {
+    pub fn is_mature(&self, epoch: u64) -> bool {
+        epoch >= self.mature_epoch
+    }
+}
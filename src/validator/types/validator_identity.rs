@@ -3,6 +3,9 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Validator identifier (identity hash hex) used as a map key across staking
+pub type ValidatorId = String;
+
 /// A unique cryptographic identity for each validator, bound to real-world hardware and network metadata.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidatorIdentity {
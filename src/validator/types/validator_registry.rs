@@ -0,0 +1,64 @@
+// src/validator/types/validator_registry.rs
+// Registration requests, registry entries and errors for the validator registry
+
+use serde::{Serialize, Deserialize};
+use crate::validator::types::bftcomet::Validator;
+
+/// What a node submits to join the validator set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistrationRequest {
+    pub pubkey: String,         // secp256k1 signing key (hex), committed to by the identity hash
+    pub metadata: String,
+    pub epoch: u64,             // Epoch the proof was made for
+    pub identity_proof: String, // Signature over the epoch's registration challenge
+}
+
+/// A registered validator, as persisted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryEntry {
+    pub identity_hash: String, // Also the validator ID
+    pub pubkey: String,        // Canonical; verifies this validator's signatures
+    pub metadata: String,
+    pub registered_epoch: u64,
+    pub validator: Validator,
+}
+
+/// One change to the registry, as written to storage
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RegistryRecord {
+    Put(RegistryEntry),
+    Delete { identity_hash: String },
+
+    /// Removal after tombstoning; the identity hash can never register again
+    Tombstone { identity_hash: String },
+}
+
+/// Why a registry operation failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    AlreadyRegistered,
+    NotRegistered,
+
+    /// Another identity already uses this pubkey
+    PubkeyInUse,
+
+    /// Bonded stake below `StakingPool::minimum_required_stake`
+    InsufficientStake { staked: u64, required: u64 },
+
+    /// Pubkey is not a valid secp256k1 public key
+    InvalidPubkey,
+
+    /// Proof is not for the current epoch or does not verify under the pubkey
+    BadIdentityProof,
+
+    /// Deregistration not signed by the validator's pubkey
+    BadDeregistrationProof,
+
+    /// Removal without the validator's signature needs it to be tombstoned
+    NotTombstoned,
+
+    /// Identity was removed after tombstoning and cannot register again
+    Tombstoned,
+
+    Storage(String),
+}
@@ -1,7 +1,7 @@
 // src/types/validator_selection.rs
 
 use serde::{Serialize, Deserialize};
-use crate::types::validator::Validator;
+use crate::validator::types::bftcomet::Validator;
 
 /// Represents a collection of Validators forming the minority committee
 #[derive(Debug, Clone, Serialize, Deserialize)]
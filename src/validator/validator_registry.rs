@@ -0,0 +1,517 @@
+// src/validator/validator_registry.rs
+
+// Domex Validator Registry
+// One registry shared by the slashing engine, the attestor and registration.
+//
+// - Clones share the same state (`Arc<RwLock<..>>`), safe across threads
+// - Every change is written to a `RegistryStore` before it is applied;
+//   `open` replays the store, so a restarted node gets the same set back
+// - Registration requires a valid identity proof (identity_registration.rs)
+//   and at least `StakingPool::minimum_required_stake` bonded under the
+//   identity hash
+// - Leaving requires the validator's signed deregistration; the only removal
+//   without it is of a validator the `Slasher` has tombstoned, which stores a
+//   tombstone so its identity hash can never register again
+// - Lookup by identity hash (the validator ID) or by pubkey
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+
+use crate::common::signing::canonical_public_key;
+use crate::validator::types::bftcomet::Validator;
+use crate::validator::types::slashing::ValidatorStatus;
+use crate::validator::types::validator_registry::{RegistrationRequest, RegistryEntry, RegistryError, RegistryRecord};
+use crate::validator::attestation::verify_attestation_signature;
+use crate::validator::identity_registration::{deregistration_challenge, identity_hash, verify_identity_proof};
+use crate::validator::slashing::Slasher;
+use crate::validator::staking_pool::StakingPool;
+
+/// Durable storage for registry changes
+pub trait RegistryStore: Send + Sync {
+    fn append(&self, record: &RegistryRecord) -> Result<(), RegistryError>;
+    fn load(&self) -> Result<Vec<RegistryRecord>, RegistryError>;
+}
+
+/// In-memory store (tests, ephemeral nodes)
+#[derive(Default)]
+pub struct MemoryStore {
+    records: Mutex<Vec<RegistryRecord>>,
+}
+
+impl RegistryStore for MemoryStore {
+    fn append(&self, record: &RegistryRecord) -> Result<(), RegistryError> {
+        self.records.lock().expect("registry store lock poisoned").push(record.clone());
+        Ok(())
+    }
+
+    fn load(&self) -> Result<Vec<RegistryRecord>, RegistryError> {
+        Ok(self.records.lock().expect("registry store lock poisoned").clone())
+    }
+}
+
+/// Append-only JSON-lines file, one record per line
+pub struct FileStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+}
+
+impl RegistryStore for FileStore {
+    fn append(&self, record: &RegistryRecord) -> Result<(), RegistryError> {
+        let _guard = self.lock.lock().expect("registry store lock poisoned");
+        let json = serde_json::to_string(record).map_err(|e| RegistryError::Storage(e.to_string()))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| RegistryError::Storage(e.to_string()))?;
+        writeln!(file, "{}", json)
+            .and_then(|_| file.sync_data())
+            .map_err(|e| RegistryError::Storage(e.to_string()))
+    }
+
+    fn load(&self) -> Result<Vec<RegistryRecord>, RegistryError> {
+        let _guard = self.lock.lock().expect("registry store lock poisoned");
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let file = File::open(&self.path).map_err(|e| RegistryError::Storage(e.to_string()))?;
+        BufReader::new(file)
+            .lines()
+            .map(|line| {
+                let line = line.map_err(|e| RegistryError::Storage(e.to_string()))?;
+                serde_json::from_str(&line).map_err(|e| RegistryError::Storage(e.to_string()))
+            })
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct RegistryIndex {
+    by_hash: HashMap<String, RegistryEntry>,
+    by_pubkey: HashMap<String, String>, // pubkey -> identity hash
+    tombstoned: HashSet<String>,        // Identity hashes barred for good
+}
+
+impl RegistryIndex {
+    fn apply(&mut self, record: RegistryRecord) {
+        match record {
+            RegistryRecord::Put(entry) => {
+                self.by_pubkey.insert(entry.pubkey.clone(), entry.identity_hash.clone());
+                self.by_hash.insert(entry.identity_hash.clone(), entry);
+            }
+            RegistryRecord::Delete { identity_hash } => self.remove(&identity_hash),
+            RegistryRecord::Tombstone { identity_hash } => {
+                self.remove(&identity_hash);
+                self.tombstoned.insert(identity_hash);
+            }
+        }
+    }
+
+    fn remove(&mut self, identity_hash: &str) {
+        if let Some(entry) = self.by_hash.remove(identity_hash) {
+            self.by_pubkey.remove(&entry.pubkey);
+        }
+    }
+}
+
+/// Thread-safe, storage-backed set of registered validators
+#[derive(Clone)]
+pub struct ValidatorRegistry {
+    index: Arc<RwLock<RegistryIndex>>,
+    store: Arc<dyn RegistryStore>,
+}
+
+impl ValidatorRegistry {
+    /// Empty registry backed by memory only
+    pub fn new() -> Self {
+        Self {
+            index: Arc::new(RwLock::new(RegistryIndex::default())),
+            store: Arc::new(MemoryStore::default()),
+        }
+    }
+
+    /// Registry restored from `store`
+    pub fn open(store: Arc<dyn RegistryStore>) -> Result<Self, RegistryError> {
+        let mut index = RegistryIndex::default();
+        for record in store.load()? {
+            index.apply(record);
+        }
+        Ok(Self {
+            index: Arc::new(RwLock::new(index)),
+            store,
+        })
+    }
+
+    /// Registers a validator: checks the identity proof for `epoch`, that the
+    /// identity and pubkey are new and not tombstoned, and the stake bonded
+    /// under the identity hash
+    pub fn register(
+        &self,
+        request: &RegistrationRequest,
+        staking: &StakingPool,
+        epoch: u64,
+    ) -> Result<RegistryEntry, RegistryError> {
+        let pubkey = canonical_public_key(&request.pubkey).ok_or(RegistryError::InvalidPubkey)?;
+        let request = RegistrationRequest { pubkey, ..request.clone() };
+        if !verify_identity_proof(&request, epoch) {
+            return Err(RegistryError::BadIdentityProof);
+        }
+
+        let hash = identity_hash(&request.pubkey);
+        let staked = staking.get_balance(&hash);
+        let required = StakingPool::minimum_required_stake();
+        if staked < required {
+            return Err(RegistryError::InsufficientStake { staked, required });
+        }
+
+        let entry = RegistryEntry {
+            identity_hash: hash.clone(),
+            pubkey: request.pubkey.clone(),
+            metadata: request.metadata.clone(),
+            registered_epoch: epoch,
+            validator: Validator {
+                id: hash,
+                last_active_epoch: epoch,
+                stake: staked,
+            },
+        };
+        self.insert(entry.clone())?;
+
+        println!("[REGISTRY] Registered {} ({})", entry.identity_hash, entry.pubkey);
+        Ok(entry)
+    }
+
+    /// Removes a validator at its own request: `proof` is its signature over
+    /// the deregistration challenge for its registration and `epoch`
+    /// (`identity_registration::deregistration_proof`). Returns its last entry.
+    pub fn deregister(&self, identity_hash: &str, epoch: u64, proof: &str) -> Result<RegistryEntry, RegistryError> {
+        self.delete(identity_hash, false, |entry| {
+            let challenge = deregistration_challenge(entry.registered_epoch, epoch);
+            if verify_attestation_signature(&entry.pubkey, identity_hash, &challenge, proof) {
+                Ok(())
+            } else {
+                Err(RegistryError::BadDeregistrationProof)
+            }
+        })
+    }
+
+    /// Removes a validator the slasher has tombstoned and bars its identity
+    /// hash from registering again; returns its last entry
+    pub fn remove_tombstoned(&self, validator_id: &str, slasher: &Slasher) -> Result<RegistryEntry, RegistryError> {
+        self.delete(validator_id, true, |_| match slasher.status(validator_id) {
+            ValidatorStatus::Tombstoned => Ok(()),
+            _ => Err(RegistryError::NotTombstoned),
+        })
+    }
+
+    /// Inserts a validator without registration checks (genesis set, tests).
+    /// Its ID doubles as pubkey, so unless the ID is a public key none of its
+    /// signatures verify.
+    pub fn add_validator(&self, validator: Validator) {
+        let pubkey = validator.id.clone();
+        self.add_validator_with_pubkey(validator, &pubkey);
+    }
+
+    /// Inserts a validator whose signatures verify under `pubkey`, without
+    /// registration checks (genesis set, tests)
+    pub fn add_validator_with_pubkey(&self, validator: Validator, pubkey: &str) {
+        let entry = RegistryEntry {
+            identity_hash: validator.id.clone(),
+            pubkey: canonical_public_key(pubkey).unwrap_or_else(|| pubkey.to_string()),
+            metadata: String::new(),
+            registered_epoch: validator.last_active_epoch,
+            validator,
+        };
+        if let Err(e) = self.insert(entry) {
+            eprintln!("[REGISTRY] Failed to add validator: {:?}", e);
+        }
+    }
+
+    pub fn get_validator(&self, validator_id: &str) -> Option<Validator> {
+        self.get_by_hash(validator_id).map(|entry| entry.validator)
+    }
+
+    pub fn get_by_hash(&self, identity_hash: &str) -> Option<RegistryEntry> {
+        self.index.read().expect("registry lock poisoned").by_hash.get(identity_hash).cloned()
+    }
+
+    pub fn get_by_pubkey(&self, pubkey: &str) -> Option<RegistryEntry> {
+        let index = self.index.read().expect("registry lock poisoned");
+        index.by_pubkey.get(pubkey).and_then(|hash| index.by_hash.get(hash)).cloned()
+    }
+
+    /// Records a validator's current stake (e.g. after slashing or delegation)
+    pub fn update_stake(&self, validator_id: &str, stake: u64) -> Result<(), RegistryError> {
+        let mut index = self.index.write().expect("registry lock poisoned");
+        let mut entry = index.by_hash.get(validator_id).cloned().ok_or(RegistryError::NotRegistered)?;
+        entry.validator.stake = stake;
+        let record = RegistryRecord::Put(entry);
+        self.store.append(&record)?;
+        index.apply(record);
+        Ok(())
+    }
+
+    /// Whether `identity_hash` was removed after tombstoning
+    pub fn is_tombstoned(&self, identity_hash: &str) -> bool {
+        self.index.read().expect("registry lock poisoned").tombstoned.contains(identity_hash)
+    }
+
+    /// All registered validators, sorted by ID
+    pub fn validators(&self) -> Vec<Validator> {
+        let index = self.index.read().expect("registry lock poisoned");
+        let mut validators: Vec<Validator> = index.by_hash.values().map(|e| e.validator.clone()).collect();
+        validators.sort_by(|a, b| a.id.cmp(&b.id));
+        validators
+    }
+
+    /// Public keys of registered validators, for evidence, committee and commission checks
+    pub fn attestation_keys(&self) -> HashMap<String, String> {
+        self.index
+            .read()
+            .expect("registry lock poisoned")
+            .by_hash
+            .values()
+            .map(|e| (e.identity_hash.clone(), e.pubkey.clone()))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.read().expect("registry lock poisoned").by_hash.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds a new entry; identity and pubkey must both be unused and the
+    /// identity not tombstoned
+    fn insert(&self, entry: RegistryEntry) -> Result<(), RegistryError> {
+        let mut index = self.index.write().expect("registry lock poisoned");
+        if index.tombstoned.contains(&entry.identity_hash) {
+            return Err(RegistryError::Tombstoned);
+        }
+        if index.by_hash.contains_key(&entry.identity_hash) {
+            return Err(RegistryError::AlreadyRegistered);
+        }
+        if index.by_pubkey.contains_key(&entry.pubkey) {
+            return Err(RegistryError::PubkeyInUse);
+        }
+        let record = RegistryRecord::Put(entry);
+        self.store.append(&record)?;
+        index.apply(record);
+        Ok(())
+    }
+
+    /// Deletes an entry once `authorize` accepts it, tombstoning its identity
+    /// hash if `tombstone`
+    fn delete(
+        &self,
+        identity_hash: &str,
+        tombstone: bool,
+        authorize: impl FnOnce(&RegistryEntry) -> Result<(), RegistryError>,
+    ) -> Result<RegistryEntry, RegistryError> {
+        let mut index = self.index.write().expect("registry lock poisoned");
+        let entry = index
+            .by_hash
+            .get(identity_hash)
+            .cloned()
+            .ok_or(RegistryError::NotRegistered)?;
+        authorize(&entry)?;
+
+        let identity_hash = identity_hash.to_string();
+        let record = if tombstone {
+            RegistryRecord::Tombstone { identity_hash }
+        } else {
+            RegistryRecord::Delete { identity_hash }
+        };
+        self.store.append(&record)?;
+        index.apply(record);
+
+        println!("[REGISTRY] Deregistered {}{}", entry.identity_hash, if tombstone { " (tombstoned)" } else { "" });
+        Ok(entry)
+    }
+}
+
+impl Default for ValidatorRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use crate::common::signing::fixtures::never_verifies;
+    use crate::common::signing::{public_key_hex, SigningKey};
+    use crate::validator::attestation::{attestation_signature, compute_attestation_hash, ProofAttestation};
+    use crate::validator::delegation_pool::DelegationPool;
+    use crate::validator::identity_registration::{deregistration_proof, registration_request};
+    use crate::validator::slashing::EvidenceContext;
+    use crate::validator::types::liveness::LivenessConfig;
+    use crate::validator::types::quorum_sync::QuorumThreshold;
+    use crate::validator::types::slashing::SlashingEvidence;
+
+    fn staked(hash: &str) -> StakingPool {
+        let mut staking = StakingPool::new();
+        staking.deposit(hash.to_string(), StakingPool::minimum_required_stake());
+        staking
+    }
+
+    #[test]
+    fn test_register_requires_proof_and_stake() {
+        let registry = ValidatorRegistry::new();
+        let key = SigningKey::from_slice(&[7u8; 32]).unwrap();
+        let pubkey = public_key_hex(&key);
+        let hash = identity_hash(&pubkey);
+        assert_eq!(hash, identity_hash(&pubkey)); // Reproducible
+
+        let request = registration_request("eu-west", &key, 4);
+        assert_eq!(
+            registry.register(&request, &StakingPool::new(), 4),
+            Err(RegistryError::InsufficientStake { staked: 0, required: StakingPool::minimum_required_stake() })
+        );
+        // Stale epoch, or a proof made with another key
+        assert_eq!(registry.register(&request, &staked(&hash), 5), Err(RegistryError::BadIdentityProof));
+        let mut forged = request.clone();
+        let other = SigningKey::from_slice(&[8u8; 32]).unwrap();
+        forged.identity_proof = registration_request("eu-west", &other, 4).identity_proof;
+        assert_eq!(registry.register(&forged, &staked(&hash), 4), Err(RegistryError::BadIdentityProof));
+
+        // The pubkey must be a key; any other label cannot be claimed
+        let mut label = request.clone();
+        label.pubkey = "pk1".into();
+        assert_eq!(registry.register(&label, &staked(&hash), 4), Err(RegistryError::InvalidPubkey));
+
+        // Non-canonical encodings of the same key register the same identity
+        let mut upper = request.clone();
+        upper.pubkey = pubkey.to_uppercase();
+        let entry = registry.register(&upper, &staked(&hash), 4).unwrap();
+        assert_eq!(entry.identity_hash, hash);
+        assert_eq!(registry.get_by_pubkey(&pubkey), Some(entry.clone()));
+        assert_eq!(registry.attestation_keys().get(&hash), Some(&pubkey));
+        assert_eq!(registry.register(&request, &staked(&hash), 4), Err(RegistryError::AlreadyRegistered));
+    }
+
+    #[test]
+    fn test_leaving_needs_the_validators_signature() {
+        let registry = ValidatorRegistry::new();
+        let key = SigningKey::from_slice(&[7u8; 32]).unwrap();
+        let hash = identity_hash(&public_key_hex(&key));
+        registry.register(&registration_request("eu-west", &key, 4), &staked(&hash), 4).unwrap();
+
+        let other = SigningKey::from_slice(&[8u8; 32]).unwrap();
+        assert_eq!(
+            registry.deregister(&hash, 9, &deregistration_proof(&other, 4, 9)),
+            Err(RegistryError::BadDeregistrationProof)
+        );
+        // Signed for another epoch or another registration
+        assert_eq!(
+            registry.deregister(&hash, 9, &deregistration_proof(&key, 4, 8)),
+            Err(RegistryError::BadDeregistrationProof)
+        );
+        assert_eq!(
+            registry.deregister(&hash, 9, &deregistration_proof(&key, 3, 9)),
+            Err(RegistryError::BadDeregistrationProof)
+        );
+        // Not tombstoned, so no unsigned removal either
+        assert_eq!(registry.remove_tombstoned(&hash, &Slasher::new()), Err(RegistryError::NotTombstoned));
+
+        registry.deregister(&hash, 9, &deregistration_proof(&key, 4, 9)).unwrap();
+        assert!(registry.get_by_hash(&hash).is_none());
+        assert_eq!(
+            registry.deregister(&hash, 9, &deregistration_proof(&key, 4, 9)),
+            Err(RegistryError::NotRegistered)
+        );
+    }
+
+    /// `id`'s signed attestation of proof-1 in epoch 1 with `zk_root`
+    fn attest(key: &SigningKey, id: &str, zk_root: &str) -> ProofAttestation {
+        let attestation_hash = compute_attestation_hash("vault1", "dBTC", "owner", zk_root, 1, "proof-1");
+        ProofAttestation {
+            vault_id: "vault1".into(),
+            token: "dBTC".into(),
+            size: 1,
+            owner_hash: "owner".into(),
+            zk_root: zk_root.into(),
+            signature: attestation_signature(key, id, &attestation_hash),
+            attestation_hash,
+            epoch: 1,
+            proof_id: "proof-1".into(),
+            validator_id: id.into(),
+        }
+    }
+
+    #[test]
+    fn test_tombstoned_identity_never_returns() {
+        let store: Arc<dyn RegistryStore> = Arc::new(MemoryStore::default());
+        let registry = ValidatorRegistry::open(store.clone()).unwrap();
+        let key = SigningKey::from_slice(&[7u8; 32]).unwrap();
+        let hash = identity_hash(&public_key_hex(&key));
+        registry.register(&registration_request("eu-west", &key, 4), &staked(&hash), 4).unwrap();
+
+        let mut slasher = Slasher::new();
+        let ctx = EvidenceContext {
+            attestation_keys: &registry.attestation_keys(),
+            committee_size: 3,
+            finality_threshold: QuorumThreshold::TWO_THIRDS,
+            liveness: LivenessConfig::default(),
+            finalities: &BTreeMap::new(),
+            registered_code: &HashMap::new(),
+            verify_proof: never_verifies,
+        };
+        let evidence = SlashingEvidence::Equivocation {
+            first: attest(&key, &hash, "root-a"),
+            second: attest(&key, &hash, "root-b"),
+        };
+        slasher
+            .submit_evidence(&evidence, &ctx, &mut staked(&hash), &mut DelegationPool::new(), 5)
+            .unwrap();
+
+        registry.remove_tombstoned(&hash, &slasher).unwrap();
+        assert!(registry.is_tombstoned(&hash));
+        assert_eq!(registry.update_stake(&hash, 1), Err(RegistryError::NotRegistered));
+        assert_eq!(
+            registry.register(&registration_request("eu-west", &key, 6), &staked(&hash), 6),
+            Err(RegistryError::Tombstoned)
+        );
+
+        // The tombstone survives a restart
+        let restored = ValidatorRegistry::open(store).unwrap();
+        assert!(restored.is_tombstoned(&hash));
+        assert_eq!(
+            restored.register(&registration_request("eu-west", &key, 6), &staked(&hash), 6),
+            Err(RegistryError::Tombstoned)
+        );
+    }
+
+    #[test]
+    fn test_clones_share_state_and_store_replays() {
+        let store: Arc<dyn RegistryStore> = Arc::new(MemoryStore::default());
+        let registry = ValidatorRegistry::open(store.clone()).unwrap();
+        let shared = registry.clone();
+
+        let handle = std::thread::spawn(move || {
+            shared.add_validator(Validator { id: "v1".into(), last_active_epoch: 1, stake: 500 });
+        });
+        handle.join().unwrap();
+        registry.add_validator(Validator { id: "v2".into(), last_active_epoch: 1, stake: 700 });
+        registry.update_stake("v2", 650).unwrap();
+
+        let restored = ValidatorRegistry::open(store).unwrap();
+        assert_eq!(restored.validators(), registry.validators());
+        assert_eq!(restored.get_validator("v2").map(|v| v.stake), Some(650));
+    }
+}
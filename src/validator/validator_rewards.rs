@@ -6,15 +6,18 @@
 // Uses constants from token_config.rs and validator stake context.
 //
 // Final state is recorded after successful proof attestation and quorum agreement.
+// Minting happens once per epoch in reward_settlement.rs, which prices finalized
+// work with `compute_reward`.
 //
 
 use crate::token::token_config::{
     MAX_VALIDATOR_REWARD,
-    recycled_fuel_amount,
-    DOMEX_DECIMAL_MULTIPLIER,
+    DOMEX_DECIMALS,
 };
 
-use crate::validator::validator_identity::ValidatorId;
+use crate::validator::types::validator_identity::ValidatorId;
+use crate::validator::slashing::{default_penalty, penalty_amount};
+use crate::validator::types::slashing::Offence;
 use std::collections::HashMap;
 
 /// Validator reward types
@@ -36,11 +39,11 @@ pub struct ValidatorReward {
 pub fn compute_reward(tx_count: u64, role: RewardType) -> u64 {
     match role {
         RewardType::Core300 => {
-            let reward = (tx_count / 10_000) * 10 * DOMEX_DECIMAL_MULTIPLIER;
+            let reward = (tx_count / 10_000) * 10 * DOMEX_DECIMALS;
             reward.min(MAX_VALIDATOR_REWARD)
         },
         RewardType::SelectedMajority => {
-            6 * DOMEX_DECIMAL_MULTIPLIER
+            6 * DOMEX_DECIMALS
         },
     }
 }
@@ -62,7 +65,7 @@ pub fn generate_block_rewards(
     rewards
 }
 
-/// Calculates slash penalty (75% of stake)
-pub fn slash_penalty(staked_amount: u64) -> u64 {
-    (staked_amount * 75) / 100
+/// Calculates the slash penalty for an offence from the default policy table
+pub fn slash_penalty(staked_amount: u64, offence: Offence) -> u64 {
+    penalty_amount(staked_amount, default_penalty(offence).slash_bps)
 }
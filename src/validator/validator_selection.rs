@@ -1,9 +1,10 @@
 // src/validator/validator_selection.rs
 
+use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
-use crate::types::bftcomet::Validator;
-use crate::common::poseidon_utils::poseidon_hash_u64;
-use rand::{seq::IteratorRandom, thread_rng};
+use crate::validator::types::bftcomet::Validator;
+use crate::validator::types::sortition::{SortitionCandidate, SortitionResult};
+use crate::validator::sortition::run_sortition;
 
 /// Configurable constants
 pub const MINORITY_SIZE: usize = 300;
@@ -19,6 +20,9 @@ pub struct ValidatorSelection {
 
     /// Set of origin submitters (e.g., up to 99,700 global validators)
     pub origin_validators: HashSet<String>,
+
+    /// Jailed or tombstoned validators, excluded from every selection
+    pub excluded: HashSet<String>,
 }
 
 impl ValidatorSelection {
@@ -28,34 +32,56 @@ impl ValidatorSelection {
             all_validators: HashMap::new(),
             minority_committee: HashSet::new(),
             origin_validators: HashSet::new(),
+            excluded: HashSet::new(),
         };
 
         for v in all {
             vs.all_validators.insert(v.id.clone(), v);
         }
 
+        vs.reselect();
+        vs
+    }
+
+    /// Replace the excluded set (e.g. `Slasher::excluded`) and reselect.
+    /// Excluded minority members are replaced by the next best eligible validators.
+    pub fn set_excluded(&mut self, excluded: HashSet<String>) {
+        self.excluded = excluded;
+        self.reselect();
+    }
+
+    /// Replace each validator's stake with its selection weight (self-stake plus
+    /// delegations, see `DelegationPool::weights`) and reselect
+    pub fn update_weights(&mut self, weights: &HashMap<String, u64>) {
+        for (id, v) in self.all_validators.iter_mut() {
+            if let Some(weight) = weights.get(id) {
+                v.stake = *weight;
+            }
+        }
+        self.reselect();
+    }
+
+    fn reselect(&mut self) {
         // Select 300 minority validators based on stake and activity
-        vs.minority_committee = vs.select_minority_committee();
+        self.minority_committee = self.select_minority_committee();
 
-        // All others are treated as origin submitters
-        vs.origin_validators = vs
+        // All other eligible validators are treated as origin submitters
+        self.origin_validators = self
             .all_validators
             .keys()
-            .filter(|id| !vs.minority_committee.contains(*id))
+            .filter(|id| !self.minority_committee.contains(*id) && !self.excluded.contains(*id))
             .cloned()
             .collect();
-
-        vs
     }
 
     /// Select top 300 validators by stake and recent activity
     fn select_minority_committee(&self) -> HashSet<String> {
-        let mut ranked: Vec<&Validator> = self.all_validators.values().collect();
-        ranked.sort_by(|a, b| {
-            b.stake
-                .cmp(&a.stake)
-                .then_with(|| b.last_active_epoch.cmp(&a.last_active_epoch))
-        });
+        let mut ranked: Vec<&Validator> = self
+            .all_validators
+            .values()
+            .filter(|v| !self.excluded.contains(&v.id))
+            .collect();
+        ranked.sort_by(|a, b| rank(a, b));
 
         ranked
             .into_iter()
@@ -64,37 +90,46 @@ impl ValidatorSelection {
             .collect()
     }
 
-    /// Select one validator from origin pool using Poseidon(epoch) as seed
-    pub fn select_origin_submitter(&self, epoch: u64) -> Option<String> {
-        let seed = poseidon_hash_u64(epoch);
-        let available: Vec<&String> = self.origin_validators.iter().collect();
-        if available.is_empty() {
-            None
-        } else {
-            let index = (seed % available.len() as u64) as usize;
-            Some(available[index].clone())
-        }
+    /// Select one origin submitter by stake-weighted sortition, seeded with
+    /// Poseidon(epoch || previous finalized root)
+    pub fn select_origin_submitter(&self, epoch: u64, previous_root: &[u8; 32]) -> Option<String> {
+        self.origin_sortition(epoch, previous_root).selected.pop()
     }
 
-    /// Rotate minority committee by removing lowest and inserting next best
+    /// Full, publicly verifiable sortition record for the origin submitter
+    pub fn origin_sortition(&self, epoch: u64, previous_root: &[u8; 32]) -> SortitionResult {
+        let candidates: Vec<SortitionCandidate> = self
+            .origin_validators
+            .iter()
+            .filter_map(|id| self.all_validators.get(id))
+            .map(|v| SortitionCandidate { id: v.id.clone(), stake: v.stake })
+            .collect();
+        run_sortition(&candidates, epoch, previous_root, 1)
+    }
+
+    /// Rotate minority committee by removing lowest and inserting next best.
+    /// Both ends use the committee ranking, so stake ties break by ID on every node.
     pub fn rotate_minority(&mut self) {
         if let Some(lowest) = self
             .minority_committee
             .iter()
-            .min_by_key(|id| self.all_validators.get(*id).map(|v| v.stake).unwrap_or(0))
-            .cloned()
+            .filter_map(|id| self.all_validators.get(id))
+            .max_by(|a, b| rank(a, b))
+            .map(|v| v.id.clone())
         {
             self.minority_committee.remove(&lowest);
 
             let candidates: Vec<&Validator> = self
                 .all_validators
                 .values()
-                .filter(|v| !self.minority_committee.contains(&v.id))
+                .filter(|v| {
+                    v.id != lowest && !self.minority_committee.contains(&v.id) && !self.excluded.contains(&v.id)
+                })
                 .collect();
 
             if let Some(next_best) = candidates
                 .into_iter()
-                .max_by_key(|v| v.stake)
+                .min_by(|a, b| rank(a, b))
                 .map(|v| v.id.clone())
             {
                 self.minority_committee.insert(next_best);
@@ -104,13 +139,21 @@ impl ValidatorSelection {
             self.origin_validators = self
                 .all_validators
                 .keys()
-                .filter(|id| !self.minority_committee.contains(*id))
+                .filter(|id| !self.minority_committee.contains(*id) && !self.excluded.contains(*id))
                 .cloned()
                 .collect();
         }
     }
 }
 
+/// Committee ranking, best first: stake, then recent activity, then ID
+fn rank(a: &Validator, b: &Validator) -> Ordering {
+    b.stake
+        .cmp(&a.stake)
+        .then_with(|| b.last_active_epoch.cmp(&a.last_active_epoch))
+        .then_with(|| a.id.cmp(&b.id))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,11 +176,39 @@ mod tests {
         assert_eq!(selection.minority_committee.len(), MINORITY_SIZE);
         assert_eq!(selection.origin_validators.len(), 100);
 
-        if let Some(selected) = selection.select_origin_submitter(42) {
-            assert!(selection.origin_validators.contains(&selected));
-        }
+        let selected = selection.select_origin_submitter(42, &[9u8; 32]).unwrap();
+        assert!(selection.origin_validators.contains(&selected));
+
+        // Same epoch and root give the same submitter on every node
+        let again = ValidatorSelection::new(
+            (0..400).rev().map(|i| make_validator(&format!("v{}", i), 1000 - i as u64, 20)).collect(),
+        );
+        assert_eq!(again.select_origin_submitter(42, &[9u8; 32]), Some(selected));
 
         selection.rotate_minority();
         assert_eq!(selection.minority_committee.len(), MINORITY_SIZE);
+
+        // Jailed validators leave both pools; the committee refills from origin
+        selection.set_excluded(["v0".to_string(), "v399".to_string()].into_iter().collect());
+        assert_eq!(selection.minority_committee.len(), MINORITY_SIZE);
+        assert!(!selection.minority_committee.contains("v0"));
+        assert!(!selection.origin_validators.contains("v399"));
+    }
+
+    #[test]
+    fn test_rotation_breaks_stake_ties_by_id() {
+        // Equal stakes everywhere: only the IDs decide who rotates
+        let validators: Vec<Validator> =
+            (0..MINORITY_SIZE + 3).map(|i| make_validator(&format!("v{:03}", i), 10, 1)).collect();
+
+        let mut a = ValidatorSelection::new(validators.clone());
+        let mut b = ValidatorSelection::new(validators.into_iter().rev().collect());
+        a.rotate_minority();
+        b.rotate_minority();
+        assert_eq!(a.minority_committee, b.minority_committee);
+
+        // The highest ID leaves, the lowest-ID outsider joins
+        assert!(!a.minority_committee.contains("v299"));
+        assert!(a.minority_committee.contains("v300"));
     }
 }
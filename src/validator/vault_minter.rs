@@ -4,7 +4,7 @@
 use std::collections::HashMap;
 use pasta_curves::Fp;
 use crate::types::zk_client::ZkOnboardingPublicInputs;
-use crate::types::poseidon_utils::IdentityHash;
+use crate::validator::types::poseidon_utils::IdentityHash;
 
 /// In-memory vault registry (simulates Merkle + global state layer)
 /// In production, this maps to Domex state root (via Verkle tree or validator backend)
@@ -5,7 +5,7 @@ use bitcoin::Txid;
 use serde::{Serialize, Deserialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::poseidon_utils::poseidon_hash4;
+use crate::matching::core::poseidon_utils::poseidon_hash4;
 
 /// Vault state tracked by Domex global validators
 /// Used to compute Poseidon-based Merkle tree leaves
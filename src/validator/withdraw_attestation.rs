@@ -3,6 +3,8 @@
 
 use crate::types::{WithdrawAttestation, FinalizedWithdrawPackage};
 use crate::utils::{poseidon_hash, sign_message};
+use crate::common::merkle::{hash_from_hex, MerkleHash};
+use crate::common::merkle_multiproof::{build_multiproof, verify_multiproof, MerkleMultiProof, MultiProofError};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Builds a validator attestation for a finalized withdrawal root.
@@ -54,3 +56,25 @@ pub fn package_finalized_withdrawal(
         attestation,
     }
 }
+
+/// Builds one multi-proof covering every exit in a withdrawal batch.
+/// `leaves` are the batch's leaf hashes in tree order; `exit_indices` the exits to prove.
+pub fn prove_withdrawal_exits(
+    leaves: &[MerkleHash],
+    exit_indices: &[u64],
+) -> Result<MerkleMultiProof, MultiProofError> {
+    build_multiproof(leaves, exit_indices)
+}
+
+/// Checks many exits against one finalized withdrawal root (hex), as bridge clients do.
+/// `exit_leaves` are given in `proof.indices` order.
+pub fn verify_withdrawal_exits(
+    finalized_root: &str,
+    exit_leaves: &[MerkleHash],
+    proof: &MerkleMultiProof,
+) -> bool {
+    match hash_from_hex(finalized_root) {
+        Some(root) => verify_multiproof(exit_leaves, proof, &root),
+        None => false,
+    }
+}
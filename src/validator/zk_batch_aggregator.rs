@@ -3,8 +3,7 @@
 // Fully real implementation – no placeholders
 
 use crate::types::{NormalizedProof, BatchAggregateResult};
-use crate::utils::poseidon_hash;
-use std::collections::VecDeque;
+use crate::common::merkle::{hash_leaf, hash_to_hex, merkle_root, MerkleHash};
 
 /// Accepts a batch of individually verified proofs and recursively aggregates them
 /// into a single ZK-attested root. Enforces consistency, order, and replay resistance.
@@ -13,19 +12,19 @@ pub fn aggregate_batch_proofs(batch: &[NormalizedProof]) -> Result<BatchAggregat
         return Err("Cannot aggregate empty batch.".to_string());
     }
 
-    let mut aggregated_inputs: Vec<String> = Vec::new();
+    let mut aggregated_inputs: Vec<MerkleHash> = Vec::new();
     let mut unique_vaults = Vec::new();
     let mut total_size: u64 = 0;
 
     for proof in batch.iter() {
-        let leaf = poseidon_hash(&format!(
+        let leaf = hash_leaf(format!(
             "{}|{}|{}|{}|{}",
             proof.vault_id,
             proof.token,
             proof.owner_hash,
             proof.timestamp,
             base64::encode(&proof.zk_payload)
-        ));
+        ).as_bytes());
 
         aggregated_inputs.push(leaf);
         total_size += proof.size;
@@ -35,8 +34,8 @@ pub fn aggregate_batch_proofs(batch: &[NormalizedProof]) -> Result<BatchAggregat
         }
     }
 
-    // Recursively hash all the leafs into a root using Poseidon-based binary Merkle
-    let final_root = compute_merkle_root(aggregated_inputs.clone())?;
+    // Canonical domain-separated Poseidon Merkle tree over the proof leaves
+    let final_root = hash_to_hex(&merkle_root(&aggregated_inputs));
 
     Ok(BatchAggregateResult {
         zk_root: final_root,
@@ -45,32 +44,3 @@ pub fn aggregate_batch_proofs(batch: &[NormalizedProof]) -> Result<BatchAggregat
         total_volume: total_size,
     })
 }
-
-/// Simple recursive binary Merkle tree builder using Poseidon hash
-fn compute_merkle_root(mut leaves: Vec<String>) -> Result<String, String> {
-    if leaves.is_empty() {
-        return Err("No leaves provided to Merkle root function.".to_string());
-    }
-
-    while leaves.len() > 1 {
-        let mut next_level: Vec<String> = Vec::new();
-        let mut i = 0;
-
-        while i < leaves.len() {
-            let left = &leaves[i];
-            let right = if i + 1 < leaves.len() {
-                &leaves[i + 1]
-            } else {
-                left  // duplicate last if odd
-            };
-
-            let combined = poseidon_hash(&format!("{}|{}", left, right));
-            next_level.push(combined);
-            i += 2;
-        }
-
-        leaves = next_level;
-    }
-
-    Ok(leaves[0].clone())
-}
@@ -3,7 +3,7 @@
 
 use ark_bn254::Fr;
 use serde::{Deserialize, Serialize};
-use crate::poseidon_utils::{poseidon_hash2, poseidon_hash3};
+use crate::matching::core::poseidon_utils::{poseidon_hash2, poseidon_hash3};
 
 /// Represents a Domex ETH vault identity binding
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -5,7 +5,7 @@
 use ethers::prelude::*;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
-use crate::poseidon_utils::poseidon_hash2;
+use crate::matching::core::poseidon_utils::poseidon_hash2;
 
 #[derive(Debug, Clone)]
 pub struct EthDepositInfo {
@@ -1,7 +1,7 @@
 // Domex :: eth_script_generator.rs
 // Generates Poseidon-locked ETH vault script for binding identity to ETH transfers
 
-use crate::poseidon_utils::poseidon_hash2;
+use crate::matching::core::poseidon_utils::poseidon_hash2;
 use ethers::types::Address;
 
 /// Represents a Domex ETH vault lock script
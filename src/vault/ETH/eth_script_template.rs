@@ -2,7 +2,7 @@
 // ETH vault script templates — used to generate Poseidon-based ETH vault bindings
 
 use ethers::types::{Address, U256};
-use crate::poseidon_utils::poseidon_hash2;
+use crate::matching::core::poseidon_utils::poseidon_hash2;
 
 /// Represents a Domex ETH vault script template
 /// This is *not* a smart contract — it's a hash-bound logic template
@@ -4,7 +4,7 @@
 use serde::{Deserialize, Serialize};
 use ethers::types::H160;
 use std::time::{SystemTime, UNIX_EPOCH};
-use crate::poseidon_utils::poseidon_hash2;
+use crate::matching::core::poseidon_utils::poseidon_hash2;
 
 /// Represents a non-custodial ETH vault tracked by Domex
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,7 +55,7 @@ impl EthVault {
 
     /// Return a Poseidon-style Merkle leaf for vault tracking
     pub fn to_merkle_leaf(&self) -> [u8; 32] {
-        use crate::poseidon_utils::poseidon_hash4_u128;
+        use crate::matching::core::poseidon_utils::poseidon_hash4_u128;
         poseidon_hash4_u128(
             &self.identity_hash,
             &self.script_hash,
@@ -2,7 +2,7 @@
 // ETH withdrawal intent builder for Poseidon-bound Domex vaults
 
 use ethers::types::Address;
-use crate::poseidon_utils::poseidon_hash4;
+use crate::matching::core::poseidon_utils::poseidon_hash4;
 use serde::{Serialize, Deserialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
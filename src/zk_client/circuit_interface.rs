@@ -3,7 +3,7 @@
 // ===============================
 
 use crate::types::zk_client::{ZkOnboardingPublicInputs, ZkPrivateInput};
-use crate::poseidon_utils::{u64_to_goldilocks, bytes_to_goldilocks};
+use crate::matching::core::poseidon_utils::{u64_to_goldilocks, bytes_to_goldilocks};
 use plonky2::field::goldilocks_field::GoldilocksField;
 use plonky2_backend::generate_ponkey2_onboarding_proof;
 
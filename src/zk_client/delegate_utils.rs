@@ -5,7 +5,7 @@
 // Supports vault-specific delegation of ZK proof rights (claim + withdrawal),
 // and identification of fuel-bearing executors (delegators).
 
-use crate::poseidon_utils::{u64_to_goldilocks, bytes_to_goldilocks};
+use crate::matching::core::poseidon_utils::{u64_to_goldilocks, bytes_to_goldilocks};
 use plonky2::field::goldilocks_field::GoldilocksField;
 use plonky2::hash::poseidon::poseidon_hash;
 
@@ -6,7 +6,7 @@
 // Used to bind vault activity to an authorized fuel-burning wallet.
 // Allows attribution of gas usage, validator rewards, and replay checks.
 
-use crate::poseidon_utils::bytes_to_goldilocks;
+use crate::matching::core::poseidon_utils::bytes_to_goldilocks;
 use plonky2::field::goldilocks_field::GoldilocksField;
 use serde::{Serialize, Deserialize};
 
@@ -2,7 +2,7 @@
 // deposit_address.rs — Domex Deterministic Deposit Address (w/ Delegation)
 // ==========================================================
 
-use crate::crypto::poseidon_hasher::PoseidonHasher;
+use crate::common::poseidon_hasher::PoseidonHasher;
 use crate::types::common::{Token, ZkIdentity, DepositAddress};
 
 /// Error type for deposit address generation
@@ -7,7 +7,7 @@ use crate::zk_client::client_identity::compute_identity_hash;
 use crate::zk_client::delegate_utils::compute_delegate_hash;
 use crate::types::zk_client::{ZkOnboardingPublicInputs, ZkPrivateInput, ZkOnboardingRequest};
 use plonky2::field::goldilocks_field::GoldilocksField;
-use crate::poseidon_utils::{u64_to_field, bytes32_to_field};
+use crate::matching::core::poseidon_utils::{u64_to_field, bytes32_to_field};
 
 /// Generates a ZK onboarding proof linking a secret key to a vault and zk-node identity,
 /// and binds it to a delegate (who must be the one submitting the proof).